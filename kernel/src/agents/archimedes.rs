@@ -14,13 +14,85 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::vec;
 use alloc::format;
-use super::{Agent, AgentId, AgentState, AgentContext};
+use super::{Agent, AgentId, AgentState, AgentContext, AgentHealth, ActivityLog, strings_bytes};
 use super::message::{Message, MessageKind, FeedbackType, SystemEvent};
-use super::prompts::character_ids;
+use super::prompts::{character_ids, CertificationLevel};
+use super::protection::ChangeKind;
 use super::prompts::library::with_library;
 use crate::serial_println;
 use crate::storage::filesystem;
 
+/// Ticks between memory scans for the ambition's themes (~2 min)
+const THEME_SCAN_INTERVAL_TICKS: u64 = 12000;
+
+/// Timer payload for the theme scan wakeup
+const WAKE_THEME_SCAN: &str = "theme_scan";
+
+/// Fallback (phase title, opening question) for each phase of the ambition
+/// interview, used when the active prompt doesn't describe that phase
+const DEFAULT_INTERVIEW: [(&str, &str); 4] = [
+    ("Ambition Discovery", "What do you want us to accomplish today?"),
+    ("Context Bridge", "What happened yesterday that you're building on?"),
+    ("Co-Creation & Partnership", "Where specifically could AI help you today?"),
+    ("Partnership Challenge", "What am I not seeing that you notice?"),
+];
+
+/// The 4-phase interview as (phase title, opening question) pairs, read from
+/// Archimedes's active prompt so an evolved prompt changes the questions
+pub fn interview_questions() -> Vec<(String, String)> {
+    let prompt = with_library(|lib| {
+        lib.get_active(character_ids::ARCHIMEDES_VOICE).map(|p| p.system_prompt.clone())
+    }).flatten().unwrap_or_default();
+    DEFAULT_INTERVIEW
+        .iter()
+        .enumerate()
+        .map(|(i, (title, question))| {
+            phase_question(&prompt, i + 1)
+                .unwrap_or_else(|| (String::from(*title), String::from(*question)))
+        })
+        .collect()
+}
+
+/// The `### Phase <n>: <title>` heading's title and the first quoted
+/// `- "..."` question listed under it
+fn phase_question(prompt: &str, phase: usize) -> Option<(String, String)> {
+    let heading = format!("### Phase {phase}:");
+    let mut lines = prompt.lines().skip_while(|line| !line.trim_start().starts_with(&heading));
+    let title = lines.next()?.trim().strip_prefix(&heading)?.trim();
+    let question = lines
+        .take_while(|line| !line.trim_start().starts_with('#'))
+        .find_map(|line| {
+            let quoted = line.trim().strip_prefix("- \"")?;
+            Some(&quoted[..quoted.find('"')?])
+        })?;
+    Some((String::from(title), String::from(question)))
+}
+
+/// Fold the interview answers (one per phase) into a "Today, I want us to..."
+/// statement. Phase 1 is the ambition itself; the others add what it builds
+/// on, how we share the work, and what to watch for. Blank answers are left out.
+pub fn synthesize_ambition(answers: &[String]) -> String {
+    let answer = |phase: usize| {
+        answers
+            .get(phase)
+            .map(|a| a.trim().trim_end_matches(['.', '!', '?']).trim())
+            .filter(|a| !a.is_empty())
+    };
+    let goal = answer(0).map(|a| a.strip_prefix("to ").unwrap_or(a)).unwrap_or("make progress together");
+    let mut ambition = format!("Today, I want us to {goal}");
+    if let Some(context) = answer(1) {
+        ambition.push_str(&format!(", building on {context}"));
+    }
+    if let Some(partnership) = answer(2) {
+        ambition.push_str(&format!("; together: {partnership}"));
+    }
+    if let Some(challenge) = answer(3) {
+        ambition.push_str(&format!("; watching for {challenge}"));
+    }
+    ambition.push('.');
+    ambition
+}
+
 /// Archimedes - The Daily Ambition Agent
 #[derive(Debug)]
 pub struct Archimedes {
@@ -36,12 +108,22 @@ pub struct Archimedes {
     commitments: Vec<String>,
     /// Workspace folders created
     workspace_folders: Vec<String>,
-    /// Counter for periodic memory theme scanning
-    memory_scan_counter: u64,
+    /// Whether the theme scan timer has been scheduled
+    theme_scan_scheduled: bool,
     /// Whether boot continuity check has been performed
     boot_memory_checked: bool,
     /// Whether current ambition has been saved to memory
     ambition_saved: bool,
+    /// Topic memory searches are biased toward (the ambition's main keyword)
+    focus_topic: Option<String>,
+    /// Recent notable actions (`agent log archimedes`)
+    activity: ActivityLog,
+    /// Memory search to send on the next tick (queued by `on_ambition_change`)
+    pending_search: Option<String>,
+    /// Number of messages processed
+    messages_received: u64,
+    /// Last tick Archimedes processed any messages
+    last_active_tick: u64,
 }
 
 impl Archimedes {
@@ -65,9 +147,14 @@ impl Archimedes {
             today_ambition: None,
             commitments: Vec::new(),
             workspace_folders: Vec::new(),
-            memory_scan_counter: 0,
+            theme_scan_scheduled: false,
             boot_memory_checked: false,
             ambition_saved: false,
+            focus_topic: None,
+            activity: ActivityLog::default(),
+            pending_search: None,
+            messages_received: 0,
+            last_active_tick: 0,
         }
     }
     
@@ -88,6 +175,7 @@ impl Archimedes {
             serial_println!("[ARCHIMEDES] No ambition file found - will create new one");
             // Create default ambition
             self.today_ambition = Some(String::from("Today, I want us to build something amazing together."));
+            self.set_focus(self.today_ambition.as_deref().and_then(crate::storage::memory_store::main_keyword));
             self.commitments = vec![
                 String::from("YOU: Set clear goals"),
                 String::from("AI: Support with tools and insights"),
@@ -96,8 +184,9 @@ impl Archimedes {
         }
     }
     
-    /// Parse ambition document to extract commitments
+    /// Parse ambition document to extract commitments (and refocus on it)
     fn parse_ambition(&mut self, content: &str) {
+        self.set_focus(crate::storage::memory_store::main_keyword(content));
         self.commitments.clear();
         
         // Simple parsing: look for "Key Commitments" section
@@ -125,8 +214,10 @@ impl Archimedes {
     }
     
     /// Create workspace folders aligned with ambition
-    fn create_workspace_folders(&mut self) {
-        serial_println!("[ARCHIMEDES] Creating workspace folders aligned with ambition...");
+    /// Ask the supervisor to create today's workspace folders. Each folder
+    /// joins `workspace_folders` once the supervisor acks it.
+    fn create_workspace_folders(&mut self, outbox: &mut Vec<Message>) -> usize {
+        serial_println!("[ARCHIMEDES] Requesting workspace folders aligned with ambition...");
         
         // Create today's workspace structure
         let folders = vec![
@@ -137,13 +228,19 @@ impl Archimedes {
         ];
         
         for folder in &folders {
-            if let Err(_) = filesystem::create_dir(folder) {
-                // Directory might already exist, that's okay
-            }
-            self.workspace_folders.push((*folder).to_string());
+            outbox.push(Message::new(
+                self.id,
+                None, // To supervisor
+                MessageKind::FileOp {
+                    change: ChangeKind::Create,
+                    path: (*folder).to_string(),
+                    content: None,
+                },
+            ));
         }
         
-        serial_println!("[ARCHIMEDES] Workspace folders created: {} folders", folders.len());
+        serial_println!("[ARCHIMEDES] Workspace folders requested: {} folders", folders.len());
+        folders.len()
     }
     
     /// Prepare desktop layout based on ambition
@@ -168,6 +265,60 @@ impl Agent for Archimedes {
         self.state
     }
     
+    fn character_id(&self) -> Option<u32> {
+        Some(self.character_id)
+    }
+    
+    fn focus(&self) -> Option<&str> {
+        self.focus_topic.as_deref()
+    }
+    
+    fn recent_activity(&self) -> Vec<String> {
+        self.activity.to_vec()
+    }
+
+    fn estimated_memory_bytes(&self) -> usize {
+        self.today_ambition.as_ref().map_or(0, String::capacity)
+            + strings_bytes(&self.commitments)
+            + strings_bytes(&self.workspace_folders)
+            + self.focus_topic.as_ref().map_or(0, String::capacity)
+            + self.pending_search.as_ref().map_or(0, String::capacity)
+            + self.activity.estimated_bytes()
+    }
+    
+    fn set_focus(&mut self, topic: Option<String>) {
+        self.focus_topic = topic;
+    }
+    
+    fn describe(&self) -> String {
+        let level = with_library(|lib| {
+            lib.get_active(self.character_id).map(|p| p.certification)
+        }).flatten().unwrap_or(CertificationLevel::None);
+        format!("Archimedes — {} {}. \"Every day begins with an ambition.\"", level.badge(), level.name())
+    }
+    
+    fn handle_query(&self, topic: &str, _args: &[String]) -> Option<Vec<String>> {
+        match topic {
+            "commitments" => Some(self.commitments.clone()),
+            "ambition" => Some(self.today_ambition.iter().cloned().collect()),
+            "workspace_folders" => Some(self.workspace_folders.clone()),
+            _ => None,
+        }
+    }
+    
+    fn health(&self) -> Option<AgentHealth> {
+        Some(AgentHealth {
+            state: self.state,
+            last_tick: self.last_active_tick,
+            errors: 0,
+            messages_processed: self.messages_received,
+            notes: vec![
+                format!("ambition loaded: {}", if self.today_ambition.is_some() { "yes" } else { "no" }),
+                format!("workspace folders: {}", self.workspace_folders.len()),
+            ],
+        })
+    }
+    
     fn init(&mut self) {
         serial_println!("[ARCHIMEDES] Initializing Daily Ambition Agent...");
         self.state = AgentState::Initializing;
@@ -190,6 +341,10 @@ impl Agent for Archimedes {
     }
     
     fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+        if !ctx.inbox.is_empty() {
+            self.last_active_tick = ctx.tick;
+        }
+
         // Process messages
         for msg in ctx.inbox.iter() {
             self.receive(msg);
@@ -199,14 +354,14 @@ impl Agent for Archimedes {
                 serial_println!("[ARCHIMEDES] Environment setup: Organizing workspace...");
 
                 // Create workspace folders
-                self.create_workspace_folders();
+                let requested = self.create_workspace_folders(ctx.outbox);
 
                 // Send feedback about workspace preparation
                 let feedback = Message::new(
                     self.id,
                     None, // To supervisor
                     MessageKind::Feedback(FeedbackType::Resource {
-                        description: format!("Workspace prepared with {} folders", self.workspace_folders.len()),
+                        description: format!("Workspace prepared with {} folders", requested),
                         location: String::from("/workspaces/today"),
                     }),
                 );
@@ -218,28 +373,15 @@ impl Agent for Archimedes {
             // Listen for Heartbeat (ambition DNA)
             if let MessageKind::Heartbeat(ref ambition) = &msg.kind {
                 serial_println!("[ARCHIMEDES] Received heartbeat: \"{}\"", ambition);
-                // Update today's ambition if it changed
-                if self.today_ambition.as_ref() != Some(ambition) {
-                    self.today_ambition = Some(ambition.clone());
-                    self.parse_ambition(ambition);
-                    serial_println!("[ARCHIMEDES] Ambition updated from heartbeat");
-                    // Reset ambition_saved so the new ambition gets persisted
-                    self.ambition_saved = false;
-                    // Search memory for related past insights
-                    let first_word = ambition.split_whitespace().next().unwrap_or("ambition");
-                    let search = Message::new(
-                        self.id,
-                        None,
-                        MessageKind::MemorySearch {
-                            query: String::from(first_word),
-                        },
-                    );
-                    ctx.outbox.push(search);
-                    serial_println!("[ARCHIMEDES] Searching memory for past insights related to: {}", first_word);
-                }
+                ctx.outbox.push(Message::new(
+                    self.id,
+                    None,
+                    MessageKind::HeartbeatAck { agent_id: self.id },
+                ));
             }
 
-            // Handle Telegram messages — respond as conversational partner
+            // Handle Telegram messages — respond as conversational partner, both
+            // to `@archimedes` and to messages without a wake word
             if let MessageKind::Text(ref text) = &msg.kind {
                 if let Some(telegram_msg) = text.strip_prefix("TELEGRAM: ") {
                     serial_println!("[ARCHIMEDES] Telegram message received: {}", telegram_msg);
@@ -258,7 +400,7 @@ impl Agent for Archimedes {
                     } else {
                         format!("Hello from Genesis! I received: \"{}\". No ambition set yet — use 'breathe' in the shell to set one.", telegram_msg)
                     };
-                    serial_println!("[TELEGRAM_REPLY] {}", reply);
+                    crate::notify!("[TELEGRAM_REPLY] {}", reply);
                     // Also store the conversation in memory
                     let store = Message::new(
                         self.id,
@@ -288,6 +430,7 @@ impl Agent for Archimedes {
                     );
                     ctx.outbox.push(connection);
                     serial_println!("[ARCHIMEDES] Found connection between {} past entries", results.len());
+                    self.activity.record(&format!("Found connection: \"{first}\" <-> \"{second}\""));
                 }
             }
         }
@@ -308,6 +451,17 @@ impl Agent for Archimedes {
             ctx.outbox.push(search);
         }
 
+        // Search memory for insights related to a changed ambition
+        if let Some(query) = self.pending_search.take() {
+            let search = Message::new(
+                self.id,
+                None,
+                MessageKind::MemorySearch { query: query.clone() },
+            );
+            ctx.outbox.push(search);
+            serial_println!("[ARCHIMEDES] Searching memory for past insights related to: {}", query);
+        }
+
         // Save current ambition to memory (once per ambition)
         if !self.ambition_saved {
             if let Some(ref ambition) = self.today_ambition {
@@ -325,10 +479,18 @@ impl Agent for Archimedes {
             }
         }
 
-        // Theme scan: search memory for keywords from current ambition (every 12000 ticks ~2 min)
-        self.memory_scan_counter += 1;
-        if self.memory_scan_counter >= 12000 {
-            self.memory_scan_counter = 0;
+        // Theme scan: search memory for keywords from current ambition, on a
+        // supervisor timer that reschedules itself
+        if !self.theme_scan_scheduled {
+            ctx.schedule(THEME_SCAN_INTERVAL_TICKS, WAKE_THEME_SCAN);
+            self.theme_scan_scheduled = true;
+        }
+        for payload in ctx.fired_timers() {
+            if payload != WAKE_THEME_SCAN {
+                serial_println!("[ARCHIMEDES] Ignoring unknown timer '{}'", payload);
+                continue;
+            }
+            ctx.schedule(THEME_SCAN_INTERVAL_TICKS, WAKE_THEME_SCAN);
             if let Some(ref ambition) = self.today_ambition {
                 // Extract a keyword from the ambition for theme searching
                 let keyword = ambition.split_whitespace()
@@ -351,6 +513,8 @@ impl Agent for Archimedes {
     }
     
     fn receive(&mut self, msg: &Message) {
+        self.messages_received += 1;
+        
         match &msg.kind {
             MessageKind::SystemEvent(SystemEvent::EnvironmentSetup) => {
                 // Handled in tick()
@@ -361,12 +525,42 @@ impl Agent for Archimedes {
             MessageKind::MemoryResults { .. } => {
                 // Handled in tick()
             }
+            MessageKind::Response { success, data } if data.starts_with("/workspaces/") => {
+                if *success {
+                    if !self.workspace_folders.iter().any(|f| f == data) {
+                        self.workspace_folders.push(data.clone());
+                    }
+                } else {
+                    serial_println!("[ARCHIMEDES] Workspace folder refused: {}", data);
+                }
+            }
             _ => {
                 serial_println!("[ARCHIMEDES] Received: {:?}", msg.kind);
             }
         }
     }
     
+    fn handle_shutdown(&mut self, ctx: &mut AgentContext) {
+        if let Some(reason) = ctx.shutdown_reason() {
+            serial_println!("[ARCHIMEDES] Winding down ({})", reason);
+        }
+        // An ambition set since the last tick hasn't reached memory yet
+        if !self.ambition_saved {
+            if let Some(ref ambition) = self.today_ambition {
+                self.ambition_saved = true;
+                ctx.outbox.push(Message::new(
+                    self.id,
+                    None,
+                    MessageKind::MemoryStore {
+                        content: format!("Ambition: {ambition}"),
+                        kind: String::from("observation"),
+                    },
+                ));
+                serial_println!("[ARCHIMEDES] Saved ambition to memory before shutdown");
+            }
+        }
+    }
+
     fn shutdown(&mut self) {
         serial_println!("[ARCHIMEDES] Shutting down...");
         self.state = AgentState::ShuttingDown;
@@ -399,10 +593,11 @@ impl Agent for Archimedes {
         ]
     }
     
-    fn reflect(&mut self) {
+    fn reflect(&mut self) -> Vec<String> {
         serial_println!("[ARCHIMEDES] Reflecting on the day...");
-        if let Some(ref ambition) = self.today_ambition {
-            serial_println!("[ARCHIMEDES] Today we aimed to: {}", ambition);
+        match self.today_ambition {
+            Some(ref ambition) => vec![format!("Today we aimed to: {}", ambition)],
+            None => vec![String::from("No ambition was set today")],
         }
     }
     
@@ -443,13 +638,25 @@ impl Agent for Archimedes {
         self.today_ambition = Some(String::from(ambition));
         self.parse_ambition(ambition);
         serial_println!("[ARCHIMEDES] \"What do we want to accomplish today?\" - I'm ready to co-create!");
+        self.activity.record(&format!("Imprinted with ambition: \"{ambition}\""));
+    }
+    
+    fn on_ambition_change(&mut self, _old: Option<&str>, new: &str) {
+        self.today_ambition = Some(String::from(new));
+        self.parse_ambition(new);
+        serial_println!("[ARCHIMEDES] Ambition updated from heartbeat");
+        self.activity.record(&format!("Ambition updated: \"{new}\""));
+        // Reset ambition_saved so the new ambition gets persisted
+        self.ambition_saved = false;
+        let first_word = new.split_whitespace().next().unwrap_or("ambition");
+        self.pending_search = Some(String::from(first_word));
     }
     
     fn clarify_role(&mut self) -> &str {
         "Co-Creator" // Archimedes co-creates ambitions with humans
     }
     
-    fn handle_environment_setup(&mut self, _ctx: &mut AgentContext) {
+    fn handle_environment_setup(&mut self, ctx: &mut AgentContext) {
         serial_println!("[ARCHIMEDES] Environment setup: Organizing workspace around ambition...");
         
         // Load ambition if not already loaded
@@ -458,7 +665,7 @@ impl Agent for Archimedes {
         }
         
         // Create workspace folders
-        self.create_workspace_folders();
+        self.create_workspace_folders(ctx.outbox);
         
         serial_println!("[ARCHIMEDES] Desktop layout prepared around ambition");
     }
@@ -18,6 +18,7 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 use super::AgentId;
+use super::protection::ChangeKind;
 
 /// Priority levels for messages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -54,6 +55,40 @@ pub enum FeedbackType {
     },
 }
 
+/// Severity of an `Announce`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnounceLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl AnnounceLevel {
+    /// Short lowercase name ("info", "warn", "error")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnounceLevel::Info => "info",
+            AnnounceLevel::Warn => "warn",
+            AnnounceLevel::Error => "error",
+        }
+    }
+}
+
+/// Ticks between each step of feeling decay
+pub const FEELING_DECAY_INTERVAL: u64 = 100;
+
+/// Intensity points a feeling loses per decay step
+pub const FEELING_DECAY_STEP: u8 = 10;
+
+/// Effective intensity of a feeling that is `age_ticks` old
+///
+/// A step function: the full intensity holds for `FEELING_DECAY_INTERVAL`
+/// ticks, then drops by `FEELING_DECAY_STEP` each interval until it hits 0.
+pub fn decayed_intensity(intensity: u8, age_ticks: u64) -> u8 {
+    let loss = (age_ticks / FEELING_DECAY_INTERVAL).saturating_mul(FEELING_DECAY_STEP as u64);
+    intensity.saturating_sub(loss.min(u8::MAX as u64) as u8)
+}
+
 /// Types of messages agents can send
 #[derive(Debug, Clone)]
 pub enum MessageKind {
@@ -84,14 +119,23 @@ pub enum MessageKind {
     /// Keyboard input event
     KeyboardInput(char),
     
-    /// Ping (for testing connectivity)
-    Ping,
+    /// Ping (for testing connectivity), stamped with the tick it was sent
+    Ping {
+        sent_tick: u64,
+    },
     
-    /// Pong (response to ping)
-    Pong,
+    /// Pong (response to ping), echoing the ping's `sent_tick`
+    Pong {
+        sent_tick: u64,
+    },
     
     /// The periodic broadcast of the ambition DNA (the heartbeat)
     Heartbeat(String),
+
+    /// An agent's acknowledgement that it received and re-imprinted a heartbeat
+    HeartbeatAck {
+        agent_id: AgentId,
+    },
     
     /// Feedback from agents back to the core (Sparks, Connections, etc.)
     Feedback(FeedbackType),
@@ -117,6 +161,193 @@ pub enum MessageKind {
     MemoryResults {
         results: Vec<(u64, String)>, // (id, content preview)
     },
+
+    /// Structured results of one test suite (one `details` line per test)
+    TestReport {
+        suite: String,
+        passed: u32,
+        failed: u32,
+        details: Vec<String>,
+    },
+
+    /// Structured question for another agent (answered via `Agent::handle_query`)
+    Query {
+        topic: String,
+        args: Vec<String>,
+    },
+
+    /// Answer to a `Query`; `correlation_id` is the id of the query message.
+    /// `rows` is empty when the target can't serve the topic.
+    QueryResult {
+        correlation_id: u64,
+        topic: String,
+        rows: Vec<String>,
+    },
+
+    /// A scheduled wakeup fired by the supervisor (see `Supervisor::schedule`)
+    Timer {
+        payload: String,
+    },
+
+    /// A filesystem change for the supervisor to apply after a protection
+    /// tier check. `Create` with no content makes a directory. Acked with a
+    /// `Response` whose `data` is the path (or the reason it was refused).
+    FileOp {
+        change: ChangeKind,
+        path: String,
+        content: Option<String>,
+    },
+
+    /// An agent proposing an evolved version of its own system prompt. The
+    /// supervisor starts an A/B experiment against the active prompt (rate
+    /// limited per agent) and acks with a `Response`.
+    PromptEvolution {
+        system_prompt: String,
+        hypothesis: String,
+    },
+
+    /// One-way status chatter ("all tests passed"). The supervisor sends it to
+    /// the event log (and the console for warnings and errors), never into the
+    /// constellation of insights or the memory store.
+    Announce {
+        agent_name: String,
+        text: String,
+        level: AnnounceLevel,
+    },
+
+    /// The supervisor is about to stop the addressed agent. Delivered one
+    /// tick before `Agent::shutdown()`; that tick calls `Agent::handle_shutdown`
+    /// instead of `tick()`, and its outbox is still routed.
+    Shutdown {
+        reason: String,
+    },
+}
+
+impl MessageKind {
+    /// The `MessageFilter` bit for this kind
+    pub fn filter_bit(&self) -> MessageFilter {
+        match self {
+            MessageKind::Text(_) => MessageFilter::TEXT,
+            MessageKind::Request { .. } => MessageFilter::REQUEST,
+            MessageKind::Response { .. } => MessageFilter::RESPONSE,
+            MessageKind::StatusUpdate { .. } => MessageFilter::STATUS_UPDATE,
+            MessageKind::SystemEvent(_) => MessageFilter::SYSTEM_EVENT,
+            MessageKind::KeyboardInput(_) => MessageFilter::KEYBOARD_INPUT,
+            MessageKind::Ping { .. } => MessageFilter::PING,
+            MessageKind::Pong { .. } => MessageFilter::PONG,
+            MessageKind::Heartbeat(_) => MessageFilter::HEARTBEAT,
+            MessageKind::HeartbeatAck { .. } => MessageFilter::HEARTBEAT_ACK,
+            MessageKind::Feedback(_) => MessageFilter::FEEDBACK,
+            MessageKind::FirstBreath { .. } => MessageFilter::FIRST_BREATH,
+            MessageKind::MemoryStore { .. } => MessageFilter::MEMORY_STORE,
+            MessageKind::MemorySearch { .. } => MessageFilter::MEMORY_SEARCH,
+            MessageKind::MemoryResults { .. } => MessageFilter::MEMORY_RESULTS,
+            MessageKind::TestReport { .. } => MessageFilter::TEST_REPORT,
+            MessageKind::Query { .. } => MessageFilter::QUERY,
+            MessageKind::QueryResult { .. } => MessageFilter::QUERY_RESULT,
+            MessageKind::Timer { .. } => MessageFilter::TIMER,
+            MessageKind::FileOp { .. } => MessageFilter::FILE_OP,
+            MessageKind::PromptEvolution { .. } => MessageFilter::PROMPT_EVOLUTION,
+            MessageKind::Announce { .. } => MessageFilter::ANNOUNCE,
+            MessageKind::Shutdown { .. } => MessageFilter::SHUTDOWN,
+        }
+    }
+
+    /// Variant name ("Ping", "Heartbeat", ...), for logs and metrics
+    pub fn kind_name(&self) -> &'static str {
+        let bit = self.filter_bit();
+        MessageFilter::NAMED
+            .iter()
+            .find(|(named, _)| *named == bit)
+            .map_or("Unknown", |(_, name)| *name)
+    }
+}
+
+/// Bitset of message kinds an agent wants from broadcasts
+/// (see `Agent::subscriptions`). Combine with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageFilter(u32);
+
+impl MessageFilter {
+    pub const ALL: MessageFilter = MessageFilter(u32::MAX);
+
+    pub const TEXT: MessageFilter = MessageFilter(1 << 0);
+    pub const REQUEST: MessageFilter = MessageFilter(1 << 1);
+    pub const RESPONSE: MessageFilter = MessageFilter(1 << 2);
+    pub const STATUS_UPDATE: MessageFilter = MessageFilter(1 << 3);
+    pub const SYSTEM_EVENT: MessageFilter = MessageFilter(1 << 4);
+    pub const KEYBOARD_INPUT: MessageFilter = MessageFilter(1 << 5);
+    pub const PING: MessageFilter = MessageFilter(1 << 6);
+    pub const PONG: MessageFilter = MessageFilter(1 << 7);
+    pub const HEARTBEAT: MessageFilter = MessageFilter(1 << 8);
+    pub const HEARTBEAT_ACK: MessageFilter = MessageFilter(1 << 9);
+    pub const FEEDBACK: MessageFilter = MessageFilter(1 << 10);
+    pub const FIRST_BREATH: MessageFilter = MessageFilter(1 << 11);
+    pub const MEMORY_STORE: MessageFilter = MessageFilter(1 << 12);
+    pub const MEMORY_SEARCH: MessageFilter = MessageFilter(1 << 13);
+    pub const MEMORY_RESULTS: MessageFilter = MessageFilter(1 << 14);
+    pub const TEST_REPORT: MessageFilter = MessageFilter(1 << 15);
+    pub const QUERY: MessageFilter = MessageFilter(1 << 16);
+    pub const QUERY_RESULT: MessageFilter = MessageFilter(1 << 17);
+    pub const TIMER: MessageFilter = MessageFilter(1 << 18);
+    pub const FILE_OP: MessageFilter = MessageFilter(1 << 19);
+    pub const PROMPT_EVOLUTION: MessageFilter = MessageFilter(1 << 20);
+    pub const ANNOUNCE: MessageFilter = MessageFilter(1 << 21);
+    pub const SHUTDOWN: MessageFilter = MessageFilter(1 << 22);
+
+    /// Every kind bit with its display name
+    const NAMED: [(MessageFilter, &'static str); 23] = [
+        (Self::TEXT, "Text"),
+        (Self::REQUEST, "Request"),
+        (Self::RESPONSE, "Response"),
+        (Self::STATUS_UPDATE, "StatusUpdate"),
+        (Self::SYSTEM_EVENT, "SystemEvent"),
+        (Self::KEYBOARD_INPUT, "KeyboardInput"),
+        (Self::PING, "Ping"),
+        (Self::PONG, "Pong"),
+        (Self::HEARTBEAT, "Heartbeat"),
+        (Self::HEARTBEAT_ACK, "HeartbeatAck"),
+        (Self::FEEDBACK, "Feedback"),
+        (Self::FIRST_BREATH, "FirstBreath"),
+        (Self::MEMORY_STORE, "MemoryStore"),
+        (Self::MEMORY_SEARCH, "MemorySearch"),
+        (Self::MEMORY_RESULTS, "MemoryResults"),
+        (Self::TEST_REPORT, "TestReport"),
+        (Self::QUERY, "Query"),
+        (Self::QUERY_RESULT, "QueryResult"),
+        (Self::TIMER, "Timer"),
+        (Self::FILE_OP, "FileOp"),
+        (Self::PROMPT_EVOLUTION, "PromptEvolution"),
+        (Self::ANNOUNCE, "Announce"),
+        (Self::SHUTDOWN, "Shutdown"),
+    ];
+
+    /// Whether messages of this kind get through
+    pub fn accepts(&self, kind: &MessageKind) -> bool {
+        self.0 & kind.filter_bit().0 != 0
+    }
+
+    /// Whether every kind gets through
+    pub fn is_all(&self) -> bool {
+        Self::NAMED.iter().all(|(bit, _)| self.0 & bit.0 != 0)
+    }
+
+    /// Names of the kinds that get through
+    pub fn names(&self) -> Vec<&'static str> {
+        Self::NAMED
+            .iter()
+            .filter(|(bit, _)| self.0 & bit.0 != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+impl core::ops::BitOr for MessageFilter {
+    type Output = MessageFilter;
+
+    fn bitor(self, rhs: MessageFilter) -> MessageFilter {
+        MessageFilter(self.0 | rhs.0)
+    }
 }
 
 /// System-level events from the supervisor
@@ -130,8 +361,6 @@ pub enum SystemEvent {
     EndOfDay,
     /// Time for reflection
     NightReflection,
-    /// System is shutting down
-    Shutdown,
     /// Agent birth protocol - imprinting with purpose
     GenesisProtocol,
     /// Environment setup phase - agents organize before GUI
@@ -186,14 +415,14 @@ impl Message {
         Self::new(from, None, kind)
     }
     
-    /// Create a ping message
-    pub fn ping(from: AgentId, to: AgentId) -> Self {
-        Self::new(from, Some(to), MessageKind::Ping)
+    /// Create a ping message sent at `tick`
+    pub fn ping(from: AgentId, to: AgentId, tick: u64) -> Self {
+        Self::new(from, Some(to), MessageKind::Ping { sent_tick: tick })
     }
     
-    /// Create a pong response
-    pub fn pong(from: AgentId, to: AgentId) -> Self {
-        Self::new(from, Some(to), MessageKind::Pong)
+    /// Create a pong answering a ping that was sent at `sent_tick`
+    pub fn pong(from: AgentId, to: AgentId, sent_tick: u64) -> Self {
+        Self::new(from, Some(to), MessageKind::Pong { sent_tick })
     }
 }
 
@@ -55,15 +55,23 @@ pub mod archimedes;
 pub mod prompts;
 pub mod protection;
 
+use alloc::collections::VecDeque;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
+use core::sync::atomic::Ordering;
+
+/// Entries an agent's `ActivityLog` keeps (oldest are discarded first)
+const ACTIVITY_CAP: usize = 20;
 
 /// Unique identifier for an agent
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AgentId(pub u64);
 
 impl AgentId {
+    /// Pseudo-agent for messages the operator sends from the shell
+    pub const OPERATOR: AgentId = AgentId(u64::MAX);
+
     pub fn new(id: u64) -> Self {
         AgentId(id)
     }
@@ -84,10 +92,95 @@ pub enum AgentState {
     Completed,
     /// Agent encountered an error
     Error,
+    /// Agent is suspended and should do no work until resumed
+    Paused,
     /// Agent is shutting down
     ShuttingDown,
 }
 
+impl AgentState {
+    /// Whether the lifecycle allows moving from this state to `next`
+    ///
+    /// Staying put is always legal. Shutdown is final, and re-initializing
+    /// is only a recovery path out of `Error`.
+    pub fn can_transition_to(&self, next: AgentState) -> bool {
+        match (*self, next) {
+            (current, next) if current == next => true,
+            (AgentState::ShuttingDown, _) => false,
+            (current, AgentState::Initializing) => current == AgentState::Error,
+            _ => true,
+        }
+    }
+
+    /// Display color for the state: the `status` label in text mode, and
+    /// the dashboard and agent graph dots (mode 13h's first 16 palette
+    /// entries are the same colors, so `as u8` works for both)
+    pub fn color(self) -> crate::vga_buffer::Color {
+        use crate::vga_buffer::Color;
+        match self {
+            AgentState::Ready => Color::LightGreen,
+            AgentState::Running => Color::Yellow,
+            AgentState::Waiting => Color::LightBlue,
+            AgentState::Initializing => Color::LightGray,
+            AgentState::Completed => Color::Cyan,
+            AgentState::Error => Color::LightRed,
+            AgentState::Paused => Color::Brown,
+            AgentState::ShuttingDown => Color::DarkGray,
+        }
+    }
+}
+
+/// A small ring of an agent's own notable actions (see `Agent::recent_activity`)
+///
+/// Unlike the global event log, each agent keeps its own, so one agent's
+/// history can be read without the others' noise.
+#[derive(Debug, Clone, Default)]
+pub struct ActivityLog {
+    entries: VecDeque<String>,
+}
+
+impl ActivityLog {
+    /// Append an action, stamped with the current supervisor tick
+    pub fn record(&mut self, action: &str) {
+        let tick = supervisor::PANIC_CONTEXT.tick.load(Ordering::Relaxed);
+        if self.entries.len() >= ACTIVITY_CAP {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(alloc::format!("[t={tick}] {action}"));
+    }
+
+    /// All kept actions, oldest first
+    pub fn to_vec(&self) -> Vec<String> {
+        self.entries.iter().cloned().collect()
+    }
+
+    /// Rough heap footprint of the kept actions
+    pub fn estimated_bytes(&self) -> usize {
+        self.entries.capacity() * core::mem::size_of::<String>()
+            + self.entries.iter().map(String::capacity).sum::<usize>()
+    }
+}
+
+/// Rough heap footprint of a list of strings: the `String` headers plus each one's text
+pub fn strings_bytes(strings: &[String]) -> usize {
+    core::mem::size_of_val(strings) + strings.iter().map(String::capacity).sum::<usize>()
+}
+
+/// An agent's structured self-report (see `Agent::health`)
+#[derive(Debug, Clone)]
+pub struct AgentHealth {
+    /// Current lifecycle state
+    pub state: AgentState,
+    /// Last tick the agent processed any messages
+    pub last_tick: u64,
+    /// Errors/failures the agent has seen
+    pub errors: u64,
+    /// Messages the agent has processed
+    pub messages_processed: u64,
+    /// Agent-specific details (e.g. test pass rate)
+    pub notes: Vec<String>,
+}
+
 /// Context passed to agents during their tick
 pub struct AgentContext<'a> {
     /// Messages waiting for this agent
@@ -96,6 +189,52 @@ pub struct AgentContext<'a> {
     pub outbox: &'a mut Vec<message::Message>,
     /// Current tick number (for timing)
     pub tick: u64,
+    /// The agent being ticked (sender of messages built by the helpers below)
+    pub agent_id: AgentId,
+    /// Wakeups requested this tick: (ticks from now, payload)
+    pub timers: &'a mut Vec<(u64, String)>,
+}
+
+impl AgentContext<'_> {
+    /// Propose an evolved version of this agent's own system prompt
+    ///
+    /// The supervisor A/B tests it against the agent's active library prompt.
+    /// Proposals are rate-limited per agent; the outcome arrives as a `Response`.
+    pub fn propose_prompt_evolution(&mut self, new_system_prompt: &str, hypothesis: &str) {
+        self.outbox.push(message::Message::new(
+            self.agent_id,
+            None, // To supervisor
+            message::MessageKind::PromptEvolution {
+                system_prompt: String::from(new_system_prompt),
+                hypothesis: String::from(hypothesis),
+            },
+        ));
+    }
+
+    /// Ask for a `MessageKind::Timer` carrying `payload`, delivered to this
+    /// agent `in_ticks` from now (see `Supervisor::schedule`)
+    pub fn schedule(&mut self, in_ticks: u64, payload: &str) {
+        self.timers.push((in_ticks, String::from(payload)));
+    }
+
+    /// Payloads of the `Timer` messages in the inbox
+    pub fn fired_timers(&self) -> Vec<String> {
+        self.inbox
+            .iter()
+            .filter_map(|msg| match &msg.kind {
+                message::MessageKind::Timer { payload } => Some(payload.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reason carried by a `Shutdown` message in the inbox, if one arrived
+    pub fn shutdown_reason(&self) -> Option<&str> {
+        self.inbox.iter().find_map(|msg| match &msg.kind {
+            message::MessageKind::Shutdown { reason } => Some(reason.as_str()),
+            _ => None,
+        })
+    }
 }
 
 /// The core Agent trait - what makes something an "agent" in Genesis
@@ -129,6 +268,14 @@ pub trait Agent: Send + Debug {
     
     /// Shutdown the agent gracefully
     fn shutdown(&mut self);
+
+    /// Wind down before `shutdown()`: called instead of `tick()` on the tick
+    /// a `Shutdown` message arrives (it is in `ctx.inbox`). Messages pushed
+    /// to `ctx.outbox` are still routed, so this is the place for final
+    /// reports and saves.
+    ///
+    /// Default: nothing to flush.
+    fn handle_shutdown(&mut self, _ctx: &mut AgentContext) {}
     
     // =========================================================================
     // Daily Rhythm Methods (The Ambition Symphony)
@@ -154,8 +301,9 @@ pub trait Agent: Send + Debug {
     
     /// Night: Reflect on performance and learn
     /// This is where agents can adjust their behavior
-    fn reflect(&mut self) {
-        // Default: no reflection
+    /// Returns the lessons/observations from the day
+    fn reflect(&mut self) -> Vec<String> {
+        Vec::new() // Default: no reflection
     }
     
     // =========================================================================
@@ -175,6 +323,113 @@ pub trait Agent: Send + Debug {
         protection::ProtectionTier::Playground
     }
 
+    // =========================================================================
+    // Health & Capabilities
+    // =========================================================================
+
+    /// Structured health self-report
+    ///
+    /// Default: None. The trait can't see an agent's counters, so agents
+    /// opt in by overriding this.
+    fn health(&self) -> Option<AgentHealth> {
+        None
+    }
+
+    /// Message kinds this agent wants from broadcasts
+    ///
+    /// Default: all. Broadcasts of other kinds are not delivered; messages
+    /// addressed to this agent always are.
+    fn subscriptions(&self) -> message::MessageFilter {
+        message::MessageFilter::ALL
+    }
+
+    /// Serializable summary of this agent's state for `snapshot save`
+    ///
+    /// Default: empty (nothing to save; the agent restores to defaults).
+    fn snapshot(&self) -> String {
+        String::new()
+    }
+
+    /// Reload state saved by `snapshot()`
+    ///
+    /// Default: no-op.
+    fn restore(&mut self, _data: &str) {
+        // Default: keep current state
+    }
+
+    /// Answer a structured `Query` about `topic`
+    ///
+    /// Default: None (topic not served).
+    fn handle_query(&self, _topic: &str, _args: &[String]) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Rough heap bytes held by the agent's own buffers
+    ///
+    /// Default: 0. Agents override this to sum their `String`/`Vec` fields;
+    /// it's an accounting aid for spotting buffers that grow unbounded, not
+    /// an exact measurement.
+    fn estimated_memory_bytes(&self) -> usize {
+        0
+    }
+
+    /// The agent's recent notable actions, oldest first
+    ///
+    /// Default: empty. Agents that keep an `ActivityLog` override this.
+    fn recent_activity(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The Academy character this agent embodies, if any
+    fn character_id(&self) -> Option<u32> {
+        None
+    }
+
+    /// Topic this agent's memory searches are biased toward
+    ///
+    /// Default: None (no bias).
+    fn focus(&self) -> Option<&str> {
+        None
+    }
+
+    /// Declare (or clear) the agent's focus topic
+    ///
+    /// Default: ignored; agents that keep a focus override this and `focus`.
+    fn set_focus(&mut self, _topic: Option<String>) {
+        // Default: no focus to set
+    }
+
+    /// What this agent can do
+    ///
+    /// Default: the capabilities advertised by the agent's active prompt.
+    fn capabilities(&self) -> Vec<String> {
+        self.character_id()
+            .and_then(|id| {
+                prompts::library::with_library(|lib| {
+                    lib.get_active(id).map(|p| p.capabilities.clone())
+                }).flatten()
+            })
+            .unwrap_or_default()
+    }
+
+    /// One-line self-description for `status`, `roster` and `academy`
+    ///
+    /// Default: "{name} ({role}) — {badge} {certification}" from the agent's
+    /// active prompt, or just the name if it has none.
+    fn describe(&self) -> String {
+        let cert = self.character_id().and_then(|id| {
+            prompts::library::with_library(|lib| {
+                lib.get_active(id).map(|p| (p.role, p.certification))
+            }).flatten()
+        });
+        match cert {
+            Some((role, level)) => alloc::format!(
+                "{} ({:?}) — {} {}", self.name(), role, level.badge(), level.name()
+            ),
+            None => String::from(self.name()),
+        }
+    }
+
     // =========================================================================
     // Journal — "As the Kernel Turns"
     // =========================================================================
@@ -198,6 +453,16 @@ pub trait Agent: Send + Debug {
     fn imprint(&mut self, _ambition: &str) {
         // Default: agents can override to store the ambition
     }
+
+    /// The living ambition changed since this agent last received it
+    ///
+    /// Called by the supervisor from `breathe` and the heartbeat, only when
+    /// `new` differs from what this agent was last given (`old`, None if it
+    /// was born before any ambition was set). Not called at registration;
+    /// that's `imprint`.
+    fn on_ambition_change(&mut self, _old: Option<&str>, _new: &str) {
+        // Default: no-op
+    }
     
     /// Agent determines its role based on the current ambition
     /// 
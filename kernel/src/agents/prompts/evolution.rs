@@ -292,6 +292,21 @@ impl EvolutionEngine {
         Some(result)
     }
     
+    /// Conclude an experiment now, even if it hasn't collected all its trials
+    pub fn force_conclude(&mut self, experiment_id: u64) -> Option<ExperimentResult> {
+        let experiment = self.experiments.get_mut(&experiment_id)?;
+        if experiment.status == ExperimentStatus::Running {
+            serial_println!(
+                "[EVOLUTION] Force-concluding experiment {} after {}/{} trials",
+                experiment_id,
+                experiment.current_trials,
+                experiment.target_trials
+            );
+            experiment.status = ExperimentStatus::Complete;
+        }
+        self.conclude_experiment(experiment_id)
+    }
+    
     /// Analyze all prompts and generate optimization suggestions
     pub fn analyze_all(&mut self, library: &PromptLibrary) -> Vec<OptimizationSuggestion> {
         self.suggestions.clear();
@@ -378,6 +393,11 @@ impl EvolutionEngine {
             .collect()
     }
     
+    /// Get every experiment, in ID order
+    pub fn experiments(&self) -> Vec<&Experiment> {
+        self.experiments.values().collect()
+    }
+    
     /// Get experiment by ID
     pub fn get_experiment(&self, id: u64) -> Option<&Experiment> {
         self.experiments.get(&id)
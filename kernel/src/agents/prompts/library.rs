@@ -95,6 +95,12 @@ impl PromptLibrary {
             .and_then(|id| self.prompts.get(id))
     }
     
+    /// Get mutable reference to the active prompt for a character
+    pub fn get_active_mut(&mut self, character_id: u32) -> Option<&mut Prompt> {
+        let id = *self.active_prompts.get(&character_id)?;
+        self.prompts.get_mut(&id)
+    }
+    
     /// Get a specific prompt version
     pub fn get(&self, id: &PromptId) -> Option<&Prompt> {
         self.prompts.get(id)
@@ -195,6 +201,14 @@ impl PromptLibrary {
         }
     }
     
+    /// Get the active prompt of every character, by character ID
+    pub fn active(&self) -> Vec<&Prompt> {
+        self.active_prompts
+            .values()
+            .filter_map(|id| self.prompts.get(id))
+            .collect()
+    }
+    
     /// Get all prompts for a character (all versions)
     pub fn get_all_versions(&self, character_id: u32) -> Vec<&Prompt> {
         self.prompts
@@ -230,6 +244,8 @@ impl PromptLibrary {
                 level.badge(),
                 level.name()
             );
+            crate::event_log::record(&alloc::format!("{} certified {} (was {})",
+                prompt.name, level.name(), old_level.name()));
         }
     }
     
@@ -129,6 +129,18 @@ impl CertificationLevel {
         }
     }
     
+    /// Text color marker matching the badge (see `vga_buffer`)
+    pub fn color_marker(&self) -> &'static str {
+        use crate::vga_buffer::{MARK_BLUE, MARK_GREEN, MARK_MAGENTA, MARK_RESET, MARK_YELLOW};
+        match self {
+            CertificationLevel::None => MARK_RESET,
+            CertificationLevel::Rookie => MARK_GREEN,
+            CertificationLevel::Certified => MARK_BLUE,
+            CertificationLevel::Expert => MARK_MAGENTA,
+            CertificationLevel::Master => MARK_YELLOW,
+        }
+    }
+    
     pub fn name(&self) -> &'static str {
         match self {
             CertificationLevel::None => "Uncertified",
@@ -116,6 +116,22 @@ pub enum ChangeKind {
 }
 
 impl ChangeKind {
+    /// Names accepted by `from_str`, in declaration order
+    pub const NAMES: &'static [&'static str] = &["read", "modify", "create", "delete", "recommend", "secret"];
+
+    /// Parse a change kind by its short name (see `NAMES`)
+    pub fn from_str(s: &str) -> Option<ChangeKind> {
+        match s {
+            "read" => Some(ChangeKind::Read),
+            "modify" => Some(ChangeKind::Modify),
+            "create" => Some(ChangeKind::Create),
+            "delete" => Some(ChangeKind::Delete),
+            "recommend" => Some(ChangeKind::Recommend),
+            "secret" => Some(ChangeKind::AccessSecret),
+            _ => None,
+        }
+    }
+
     pub fn description(&self) -> &'static str {
         match self {
             ChangeKind::Read => "Read file contents",
@@ -324,4 +340,13 @@ mod tests {
         assert!(result.proceed); // Allowed — just needs discussion first
         assert!(result.tier.requires_discussion());
     }
+
+    #[test]
+    fn change_kind_names_parse() {
+        for name in ChangeKind::NAMES {
+            assert!(ChangeKind::from_str(name).is_some(), "{name} should parse");
+        }
+        assert_eq!(ChangeKind::from_str("secret"), Some(ChangeKind::AccessSecret));
+        assert_eq!(ChangeKind::from_str("write"), None);
+    }
 }
@@ -31,20 +31,291 @@
 //! See: https://as-the-cloud-turns-web.onrender.com/#academy
 
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 use alloc::string::String;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use super::{Agent, AgentId, AgentContext};
-use super::message::{Message, MessageKind, SystemEvent, FeedbackType};
-use super::prompts::{library, evolution, character_ids};
+use super::message::{self, Message, MessageKind, SystemEvent, FeedbackType};
+use super::prompts::{library, evolution, character_ids, CertificationLevel, PromptRole};
 use super::prompts::academy;
 use super::protection::{self, ProtectionTier};
 use crate::{println, serial_println};
-use crate::storage::memory_store::{self, MemoryKind};
+use crate::storage::memory_store::{self, MemoryEntry, MemoryKind};
+
+/// Ticks between ambition heartbeats
+const HEARTBEAT_INTERVAL: u64 = 100;
+
+/// Heartbeat cycles without an ack before an agent is flagged unresponsive
+const UNRESPONSIVE_CYCLES: u64 = 3;
+
+/// Default number of outbox messages accepted per agent per tick
+const DEFAULT_OUTBOX_CAP: usize = 32;
+
+/// Default ticks between memory WAL checkpoints (~1 minute)
+const DEFAULT_MEMORY_CHECKPOINT_INTERVAL: u64 = 6_000;
+
+/// PIT ticks (~220 ms) a single agent `tick()` may take before the
+/// watchdog warns. The kernel can't preempt a runaway tick, only report it.
+const SLOW_TICK_PIT_TICKS: u64 = 4;
+
+/// Slow ticks after which the watchdog pauses an agent
+const SLOW_TICK_PAUSE_LIMIT: u64 = 3;
+
+/// Default real minutes between memory autosaves
+const DEFAULT_AUTOSAVE_MINUTES: u64 = 5;
+
+/// Past living ambitions kept by `breathe` (oldest dropped first)
+const MAX_AMBITION_HISTORY: usize = 32;
+
+/// Prefix of the memory entry `breathe` stores for each ambition
+const AMBITION_MEMORY_PREFIX: &str = "Living Ambition: ";
+
+/// Ticks an agent must wait between accepted prompt evolution proposals (~5 min)
+const PROMPT_EVOLUTION_COOLDOWN: u64 = 30_000;
+
+/// Trials each agent-proposed experiment runs before analysis
+const PROMPT_EVOLUTION_TRIALS: u64 = 20;
+
+/// Default cap on registered agents (protects the kernel heap)
+const DEFAULT_MAX_AGENTS: usize = 32;
+
+/// Dead letters kept for inspection (oldest are discarded first)
+const MAX_DEAD_LETTERS: usize = 64;
+
+/// Directed messages held for a paused agent (oldest are dropped first)
+const MAX_HELD_MAIL: usize = 64;
+
+/// Announcements kept for `announcements` (oldest are discarded first)
+const MAX_ANNOUNCEMENTS: usize = 32;
+
+/// Where `snapshot save` writes the supervisor state
+pub const SNAPSHOT_PATH: &str = "/storage/state/supervisor.snap";
+
+/// First line of a supervisor snapshot
+const SNAPSHOT_HEADER: &str = "#supervisor-snap v1";
+
+/// Ticks between RTC reads for the daily schedule (~5s)
+const SCHEDULE_CHECK_TICKS: u64 = 500;
+
+/// Consecutive idle ticks before the main loop drops into low-power mode
+pub const LOW_POWER_AFTER_IDLE_TICKS: u64 = 50;
+
+/// What one supervisor tick did; a tick with none of it is idle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickActivity {
+    /// Messages taken off the queue for routing
+    pub messages_routed: usize,
+    /// Timers that came due
+    pub timers_fired: usize,
+    /// Agents whose state changed during their tick
+    pub state_changes: usize,
+}
+
+impl TickActivity {
+    /// Nothing happened this tick
+    pub fn is_idle(&self) -> bool {
+        self.messages_routed == 0 && self.timers_fired == 0 && self.state_changes == 0
+    }
+}
+
+impl core::ops::AddAssign for TickActivity {
+    fn add_assign(&mut self, other: Self) {
+        self.messages_routed += other.messages_routed;
+        self.timers_fired += other.timers_fired;
+        self.state_changes += other.state_changes;
+    }
+}
+
+/// One agent's row in `roster()`
+#[derive(Debug, Clone)]
+pub struct RosterEntry {
+    pub name: String,
+    pub id: AgentId,
+    /// State as tracked by the supervisor
+    pub state: super::AgentState,
+    /// Role and certification of the agent's active library prompt, if any
+    pub role: Option<PromptRole>,
+    pub certification: Option<CertificationLevel>,
+    /// Highest-privilege tier the agent may write to
+    pub max_write_tier: ProtectionTier,
+    /// Topic the agent's memory searches are biased toward
+    pub focus: Option<String>,
+}
+
+/// An agent's status announcement, as kept by the supervisor
+#[derive(Debug, Clone)]
+pub struct Announcement {
+    /// Tick the supervisor received it
+    pub tick: u64,
+    pub agent_name: String,
+    pub text: String,
+    pub level: message::AnnounceLevel,
+}
+
+/// How `Supervisor::route_telegram` delivered a Telegram message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelegramRoute {
+    /// No wake word: broadcast to every agent subscribed to Text
+    Broadcast,
+    /// `@name` matched this agent, and only it received the message
+    Directed(String),
+    /// `@name` matched no agent; the sender was sent the available names
+    Unknown(String),
+}
+
+/// Per-agent message routing counters
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RouteStats {
+    /// Messages placed in the agent's inbox
+    pub delivered: u64,
+    /// Messages collected from the agent's outbox
+    pub sent: u64,
+    /// Held messages dropped while the agent was paused (see `MAX_HELD_MAIL`)
+    pub dropped: u64,
+}
+
+/// Per-agent `tick()` duration watchdog, in PIT ticks (~55 ms each)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TickTiming {
+    /// Longest single tick seen
+    pub slowest: u64,
+    /// Ticks that reached `SLOW_TICK_PIT_TICKS`
+    pub slow_ticks: u64,
+    /// Paused by the watchdog: skipped until `resume_agent`
+    pub paused: bool,
+}
+
+/// The four beats of the daily rhythm, in the order they fire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyPhase {
+    Morning,
+    Midday,
+    EndOfDay,
+    Reflection,
+}
+
+impl DailyPhase {
+    pub const ALL: [DailyPhase; 4] = [
+        DailyPhase::Morning,
+        DailyPhase::Midday,
+        DailyPhase::EndOfDay,
+        DailyPhase::Reflection,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DailyPhase::Morning => "morning ambition",
+            DailyPhase::Midday => "midday checkpoint",
+            DailyPhase::EndOfDay => "end-of-day report",
+            DailyPhase::Reflection => "nightly reflection",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Fires each `DailyPhase` once per real day, at its hour on the RTC clock
+#[derive(Debug, Clone)]
+pub struct DailyScheduler {
+    /// Hour (0-23) of each phase, indexed by `DailyPhase`
+    hours: [u8; 4],
+    /// Date the `fired` flags belong to (None until the first check)
+    date: Option<(u16, u8, u8)>,
+    /// Phases already run (or skipped at boot) on `date`
+    fired: [bool; 4],
+}
+
+impl DailyScheduler {
+    pub fn new() -> Self {
+        DailyScheduler {
+            hours: [8, 12, 18, 22],
+            date: None,
+            fired: [false; 4],
+        }
+    }
+
+    /// Hour a phase fires at
+    pub fn hour(&self, phase: DailyPhase) -> u8 {
+        self.hours[phase.index()]
+    }
+
+    /// Move a phase to another hour (0-23). Returns false if out of range.
+    pub fn set_hour(&mut self, phase: DailyPhase, hour: u8) -> bool {
+        if hour > 23 {
+            return false;
+        }
+        self.hours[phase.index()] = hour;
+        true
+    }
+
+    /// Whether a phase has run today
+    pub fn has_fired(&self, phase: DailyPhase) -> bool {
+        self.fired[phase.index()]
+    }
+
+    /// Phases due at `now`, marked as fired.
+    ///
+    /// The flags reset when the date changes. On the very first check (boot)
+    /// phases whose hour has already passed are marked without firing, so a
+    /// boot at 15:00 doesn't replay the whole morning at once.
+    pub fn due(&mut self, now: &crate::rtc::DateTime) -> Vec<DailyPhase> {
+        let booting = self.date.is_none();
+        if self.date != Some(now.date()) {
+            self.date = Some(now.date());
+            self.fired = [false; 4];
+        }
+
+        let mut due = Vec::new();
+        for phase in DailyPhase::ALL {
+            let i = phase.index();
+            if self.fired[i] || now.hour < self.hours[i] {
+                continue;
+            }
+            self.fired[i] = true;
+            // On boot, only a phase starting this very hour still fires
+            if !booting || now.hour == self.hours[i] {
+                due.push(phase);
+            }
+        }
+        due
+    }
+}
+
+/// Why `Supervisor::register` refused an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterError {
+    /// Already running `max_agents` agents
+    AtCapacity { max: usize },
+}
+
+/// Supervisor state published for the panic handler, which can't reach the
+/// Supervisor itself (it lives on `kernel_main`'s stack). Updated every tick.
+pub struct PanicContext {
+    /// Tick number at the last update
+    pub tick: AtomicU64,
+    /// Registered agents
+    pub agents: AtomicUsize,
+    /// Insights in the constellation: spark, connection, resource, feeling
+    pub insights: [AtomicUsize; 4],
+}
+
+/// Last published supervisor state (see `PanicContext`)
+pub static PANIC_CONTEXT: PanicContext = PanicContext {
+    tick: AtomicU64::new(0),
+    agents: AtomicUsize::new(0),
+    insights: [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)],
+};
 
 /// The Agent Supervisor - orchestrates all agents
 pub struct Supervisor {
     /// All registered agents
     agents: Vec<Box<dyn Agent>>,
+    /// IDs of `agents` in ascending order: agents are ticked in this order
+    /// however `agents` itself is arranged, so runs are reproducible
+    tick_order: Vec<AgentId>,
     /// Global message queue
     message_queue: Vec<Message>,
     /// Current tick number
@@ -55,19 +326,69 @@ pub struct Supervisor {
     id: AgentId,
     /// The living ambition - the soul of Genesis
     living_ambition: Option<String>,
+    /// Every ambition breathed in, oldest first: (tick, ambition)
+    ambition_history: Vec<(u64, String)>,
     /// Heartbeat counter (pulse every N ticks)
     heartbeat_counter: u64,
     /// Constellation of insights collected from agents
     /// Limited to prevent memory exhaustion (keep last 50 insights)
     constellation_of_insights: Vec<FeedbackType>,
+    /// Tick each insight arrived (parallel to `constellation_of_insights`)
+    insight_ticks: Vec<u64>,
     /// Serendipity check counter (scan for connections every N ticks)
     serendipity_counter: u64,
     /// Themes already broadcast by serendipity (prevent repeat notifications)
     serendipity_seen_themes: Vec<String>,
-    /// Rhythm counter for periodic checkpoint and report cycles
-    rhythm_counter: u64,
     /// Journal counter for periodic "As the Kernel Turns" entries
     journal_counter: u64,
+    /// Pending timers sorted by deadline: (deadline tick, target agent, payload)
+    timers: Vec<(u64, AgentId, String)>,
+    /// Tick of the last heartbeat ack received from each agent
+    heartbeat_acks: Vec<(AgentId, u64)>,
+    /// Round-trip ticks of the last answered ping, per agent
+    ping_latencies: Vec<(AgentId, u64)>,
+    /// Queries the supervisor sent and awaits: (query message id, agent name)
+    pending_queries: Vec<(u64, String)>,
+    /// Tick of the first heartbeat pulse (acks are only expected after this)
+    first_pulse_tick: Option<u64>,
+    /// Print per-test details when the next TestReport arrives
+    verbose_test_report: bool,
+    /// Max outbox messages accepted per agent per tick
+    outbox_cap: usize,
+    /// Messages dropped for exceeding the outbox cap
+    dead_letters: Vec<Message>,
+    /// Per-agent count of messages dropped for exceeding the outbox cap
+    outbox_overflows: Vec<(AgentId, u64)>,
+    /// Recent status announcements (most recent last)
+    announcements: Vec<Announcement>,
+    /// Tick of each agent's last accepted prompt evolution proposal
+    evolution_proposals: Vec<(AgentId, u64)>,
+    /// Routing counters, parallel to `agents`
+    route_stats: Vec<RouteStats>,
+    /// Messages delivered between two agents: (lower slot, higher slot, count)
+    agent_links: Vec<(usize, usize, u64)>,
+    /// Messages routed by kind (`MessageKind::kind_name`) since the last reset
+    kind_counts: BTreeMap<&'static str, u64>,
+    /// Tick duration watchdog, parallel to `agents`
+    tick_timings: Vec<TickTiming>,
+    /// Directed mail waiting for a watchdog-paused agent, parallel to `agents`
+    held_mail: Vec<Vec<Message>>,
+    /// Lifecycle state from each agent's last legal `tick()`, parallel to `agents`
+    agent_states: Vec<super::AgentState>,
+    /// Ambition each agent was last given, parallel to `agents`
+    agent_ambitions: Vec<Option<String>>,
+    /// Ticks between memory WAL checkpoints (0 = disabled)
+    memory_checkpoint_interval: u64,
+    /// Real minutes between memory autosaves (0 = disabled)
+    autosave_minutes: u64,
+    /// Uptime (ms) when the autosave interval last started
+    last_autosave_ms: u64,
+    /// Maximum number of registered agents
+    max_agents: usize,
+    /// Wall-clock daily rhythm (morning, midday, EOD, reflection)
+    daily_scheduler: DailyScheduler,
+    /// Consecutive ticks without activity (see `TickActivity`)
+    idle_ticks: u64,
 }
 
 impl Supervisor {
@@ -103,25 +424,60 @@ impl Supervisor {
         
         Supervisor {
             agents: Vec::new(),
+            tick_order: Vec::new(),
             message_queue: Vec::new(),
             tick: 0,
             next_agent_id: 1, // Reserve 0 for supervisor
             id: AgentId::new(0),
             living_ambition: None,
+            ambition_history: Vec::new(),
             heartbeat_counter: 0,
             constellation_of_insights: Vec::new(),
+            insight_ticks: Vec::new(),
             serendipity_counter: 0,
             serendipity_seen_themes: Vec::new(),
-            rhythm_counter: 0,
             journal_counter: 0,
+            timers: Vec::new(),
+            heartbeat_acks: Vec::new(),
+            ping_latencies: Vec::new(),
+            pending_queries: Vec::new(),
+            first_pulse_tick: None,
+            verbose_test_report: false,
+            outbox_cap: DEFAULT_OUTBOX_CAP,
+            dead_letters: Vec::new(),
+            outbox_overflows: Vec::new(),
+            announcements: Vec::new(),
+            evolution_proposals: Vec::new(),
+            route_stats: Vec::new(),
+            agent_links: Vec::new(),
+            kind_counts: BTreeMap::new(),
+            tick_timings: Vec::new(),
+            held_mail: Vec::new(),
+            agent_states: Vec::new(),
+            agent_ambitions: Vec::new(),
+            memory_checkpoint_interval: DEFAULT_MEMORY_CHECKPOINT_INTERVAL,
+            autosave_minutes: DEFAULT_AUTOSAVE_MINUTES,
+            last_autosave_ms: 0,
+            max_agents: DEFAULT_MAX_AGENTS,
+            daily_scheduler: DailyScheduler::new(),
+            idle_ticks: 0,
         }
     }
     
     /// Register a new agent with the supervisor (Genesis Protocol)
-    pub fn register(&mut self, mut agent: Box<dyn Agent>) {
+    ///
+    /// Refused (before the agent is initialized) once `max_agents` are registered.
+    pub fn register(&mut self, mut agent: Box<dyn Agent>) -> Result<(), RegisterError> {
         let name = String::from(agent.name());
         let id = agent.id();
         
+        if self.agents.len() >= self.max_agents {
+            serial_println!("[SUPERVISOR] Refusing agent {} (ID: {:?}): agent limit {} reached",
+                name, id, self.max_agents);
+            crate::event_log::record(&alloc::format!("Agent {name} refused: limit {} reached", self.max_agents));
+            return Err(RegisterError::AtCapacity { max: self.max_agents });
+        }
+        
         serial_println!("[SUPERVISOR] Registering agent: {} (ID: {:?})", name, id);
         
         // Initialize the agent
@@ -146,8 +502,17 @@ impl Supervisor {
             role: role.clone(),
         });
         
+        self.agent_states.push(agent.state());
         self.agents.push(agent);
+        self.tick_order.push(id);
+        self.tick_order.sort_by_key(|id| id.0);
+        self.route_stats.push(RouteStats::default());
+        self.tick_timings.push(TickTiming::default());
+        self.held_mail.push(Vec::new());
+        self.agent_ambitions.push(self.living_ambition.clone());
         serial_println!("[SUPERVISOR] Agent {} is now ONLINE (role: {})", name, role);
+        crate::event_log::record(&alloc::format!("Agent {name} registered (role: {role})"));
+        Ok(())
     }
     
     /// Generate a new unique agent ID
@@ -157,6 +522,28 @@ impl Supervisor {
         id
     }
     
+    /// Generate an ID for a new agent, or None if the agent limit is reached
+    /// (so a spawner doesn't burn IDs on agents that will be refused)
+    pub fn try_next_id(&mut self) -> Option<AgentId> {
+        if self.agents.len() >= self.max_agents {
+            None
+        } else {
+            Some(self.next_id())
+        }
+    }
+    
+    /// Maximum number of registered agents
+    pub fn max_agents(&self) -> usize {
+        self.max_agents
+    }
+    
+    /// Set the agent limit. It can't go below the current agent count or
+    /// zero; returns the limit actually applied.
+    pub fn set_max_agents(&mut self, max: usize) -> usize {
+        self.max_agents = max.max(self.agents.len()).max(1);
+        self.max_agents
+    }
+    
     /// Send a message (queues it for next tick)
     pub fn send(&mut self, mut msg: Message) {
         msg.timestamp = self.tick;
@@ -168,6 +555,114 @@ impl Supervisor {
         let msg = Message::broadcast(self.id, kind);
         self.send(msg);
     }
+
+    /// Ping every agent; each pong's round trip is reported as it arrives
+    pub fn ping_all(&mut self) {
+        self.broadcast(MessageKind::Ping { sent_tick: self.tick });
+    }
+    
+    /// Ping one agent by name (case-insensitive). Returns false if there is no such agent.
+    pub fn ping_agent(&mut self, name: &str) -> bool {
+        let Some(to) = self.agents.iter().find(|a| a.name().eq_ignore_ascii_case(name)).map(|a| a.id()) else {
+            return false;
+        };
+        let msg = Message::ping(self.id, to, self.tick);
+        self.send(msg);
+        true
+    }
+    
+    /// Route an incoming Telegram message as `Text("TELEGRAM: ...")`
+    ///
+    /// A leading `@name` wake word (case-insensitive, optionally followed by
+    /// `,` or `:`) sends the rest of the message to that agent alone. Without
+    /// one the message is broadcast. An unknown name is answered with the
+    /// list of agents instead of being delivered.
+    pub fn route_telegram(&mut self, text: &str) -> TelegramRoute {
+        let text = text.trim();
+        let Some(addressed) = text.strip_prefix('@') else {
+            self.broadcast(MessageKind::Text(alloc::format!("TELEGRAM: {text}")));
+            return TelegramRoute::Broadcast;
+        };
+        let (name, rest) = addressed.split_once(char::is_whitespace).unwrap_or((addressed, ""));
+        let name = name.trim_end_matches([',', ':']);
+
+        let Some(agent) = self.agents.iter().find(|a| a.name().eq_ignore_ascii_case(name)) else {
+            let available: Vec<String> = self.agents.iter().map(|a| alloc::format!("@{}", a.name())).collect();
+            crate::notify!("[TELEGRAM_REPLY] No agent named @{}. Available: {}", name, available.join(", "));
+            return TelegramRoute::Unknown(String::from(name));
+        };
+        let (to, agent_name) = (agent.id(), String::from(agent.name()));
+        let msg = Message::new(self.id, Some(to), MessageKind::Text(alloc::format!("TELEGRAM: {}", rest.trim())));
+        self.send(msg);
+        TelegramRoute::Directed(agent_name)
+    }
+
+    /// An agent's own activity ring, looked up by name (case-insensitive)
+    pub fn agent_activity(&self, name: &str) -> Option<(String, Vec<String>)> {
+        self.agents
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case(name))
+            .map(|a| (String::from(a.name()), a.recent_activity()))
+    }
+    
+    /// Each agent's estimated heap footprint, by name (see `Agent::estimated_memory_bytes`)
+    pub fn agent_memory(&self) -> Vec<(String, usize)> {
+        self.agents
+            .iter()
+            .map(|a| (String::from(a.name()), a.estimated_memory_bytes()))
+            .collect()
+    }
+    
+    /// Round-trip ticks of an agent's last answered ping, if any
+    pub fn ping_latency(&self, agent_id: AgentId) -> Option<u64> {
+        self.ping_latencies
+            .iter()
+            .find(|(id, _)| *id == agent_id)
+            .map(|(_, ticks)| *ticks)
+    }
+    
+    /// Report and remember the round trip of a pong addressed to the supervisor
+    fn record_pong(&mut self, from: AgentId, sent_tick: u64) {
+        let latency = self.tick.saturating_sub(sent_tick);
+        let name = self.agents
+            .iter()
+            .find(|a| a.id() == from)
+            .map(|a| String::from(a.name()))
+            .unwrap_or_else(|| alloc::format!("agent-{}", from.0));
+        serial_println!("[PING] {} answered in {} ticks", name, latency);
+        println!("  pong from {}: {} ticks", name, latency);
+        match self.ping_latencies.iter_mut().find(|(id, _)| *id == from) {
+            Some(entry) => entry.1 = latency,
+            None => self.ping_latencies.push((from, latency)),
+        }
+    }
+
+    /// Schedule a `MessageKind::Timer` for an agent, delivered `in_ticks` from now
+    pub fn schedule(&mut self, agent_id: AgentId, in_ticks: u64, payload: String) {
+        let deadline = self.tick + in_ticks;
+        // Insert after any timers with the same deadline so equal deadlines fire in FIFO order
+        let pos = self.timers.partition_point(|(d, _, _)| *d <= deadline);
+        self.timers.insert(pos, (deadline, agent_id, payload));
+    }
+
+    /// Number of timers waiting to fire
+    pub fn pending_timers(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Queue every timer whose deadline has been reached
+    fn fire_due_timers(&mut self) -> usize {
+        let due = self.timers.partition_point(|(d, _, _)| *d <= self.tick);
+        if due == 0 {
+            return 0;
+        }
+        let fired: Vec<(u64, AgentId, String)> = self.timers.drain(..due).collect();
+        for (_, agent_id, payload) in fired {
+            let msg = Message::new(self.id, Some(agent_id), MessageKind::Timer { payload });
+            self.send(msg);
+        }
+        due
+    }
     
     /// Agent-First Boot Sequence
     /// 
@@ -216,38 +711,82 @@ impl Supervisor {
         self.broadcast(MessageKind::SystemEvent(SystemEvent::EnvironmentSetup));
         
         // Also call handle_environment_setup directly for each agent
+        let mut requested_timers = Vec::new();
         for agent in self.agents.iter_mut() {
             // Create a context for environment setup
             let mut inbox = Vec::new();
             let mut outbox = Vec::new();
+            let mut timers = Vec::new();
             let mut ctx = AgentContext {
                 inbox: &mut inbox,
                 outbox: &mut outbox,
                 tick: self.tick,
+                agent_id: agent.id(),
+                timers: &mut timers,
             };
             
             agent.handle_environment_setup(&mut ctx);
+            requested_timers.extend(timers.into_iter().map(|(in_ticks, payload)| (agent.id(), in_ticks, payload)));
             
             // Collect any messages from environment setup
             for msg in outbox {
                 self.message_queue.push(msg);
             }
         }
+        for (agent_id, in_ticks, payload) in requested_timers {
+            self.schedule(agent_id, in_ticks, payload);
+        }
     }
     
     /// Run one tick of the supervisor loop
-    pub fn tick(&mut self) {
+    pub fn tick(&mut self) -> TickActivity {
+        let activity = self.step();
+        self.publish_panic_context();
+        activity
+    }
+
+    /// Run `count` ticks back to back; returns their combined activity
+    ///
+    /// Messages, timers and every rhythm counter advance exactly as they
+    /// would over `count` calls to `tick()`. Only the insight counts in the
+    /// crash-report snapshot (`PANIC_CONTEXT`), which walk every insight, are
+    /// published once at the end; its tick stays current. The memory store lock is deliberately
+    /// not held across the batch: the supervisor's memory handlers and the
+    /// agents themselves take it mid-tick, and spin locks don't nest.
+    pub fn tick_n(&mut self, count: u64) -> TickActivity {
+        let mut total = TickActivity::default();
+        for _ in 0..count {
+            total += self.step();
+        }
+        self.publish_panic_context();
+        total
+    }
+
+    /// One tick's work, minus publishing the rest of `PANIC_CONTEXT`
+    fn step(&mut self) -> TickActivity {
         self.tick += 1;
+        // The event and activity logs stamp entries with this, mid-batch too
+        PANIC_CONTEXT.tick.store(self.tick, Ordering::Relaxed);
+
+        // Fire due timers first so they are routed during this tick
+        let mut activity = TickActivity {
+            timers_fired: self.fire_due_timers(),
+            ..TickActivity::default()
+        };
         
         // Pulse the heartbeat (every 100 ticks = ~1 second at typical speeds)
         self.heartbeat_counter += 1;
-        if self.heartbeat_counter >= 100 {
+        if self.heartbeat_counter >= HEARTBEAT_INTERVAL {
             self.pulse();
             self.heartbeat_counter = 0;
         }
         
         // Route messages to agents
         let messages: Vec<Message> = self.message_queue.drain(..).collect();
+        activity.messages_routed = messages.len();
+        for msg in &messages {
+            *self.kind_counts.entry(msg.kind.kind_name()).or_default() += 1;
+        }
         
         // Collect Feedback messages before routing
         let mut feedback_messages = Vec::new();
@@ -269,47 +808,21 @@ impl Supervisor {
         for msg in feedback_messages {
             if let MessageKind::Feedback(feedback) = msg.kind.clone() {
                 // Auto-store feedback in persistent memory
-                let source = alloc::format!("agent-{}", msg.from.0);
-                match &feedback {
-                    FeedbackType::Spark { content, context } => {
-                        memory_store::store_with_timestamp(
-                            &alloc::format!("{} (context: {})", content, context),
-                            MemoryKind::Spark,
-                            &source,
-                            self.tick,
-                        );
-                    }
-                    FeedbackType::Connection { from, to, pattern } => {
-                        memory_store::store_with_timestamp(
-                            &alloc::format!("{} -> {}: {}", from, to, pattern),
-                            MemoryKind::Connection,
-                            &source,
-                            self.tick,
-                        );
-                    }
-                    FeedbackType::Resource { description, location } => {
-                        memory_store::store_with_timestamp(
-                            &alloc::format!("{} (at: {})", description, location),
-                            MemoryKind::Resource,
-                            &source,
-                            self.tick,
-                        );
-                    }
-                    FeedbackType::Feeling { tag, intensity } => {
-                        memory_store::store_with_timestamp(
-                            &alloc::format!("{} (intensity: {})", tag, intensity),
-                            MemoryKind::Feeling,
-                            &source,
-                            self.tick,
-                        );
-                    }
-                }
+                let source = if msg.from == AgentId::OPERATOR {
+                    String::from("operator")
+                } else {
+                    alloc::format!("agent-{}", msg.from.0)
+                };
+                let (kind, content) = MemoryKind::from_feedback(&feedback);
+                memory_store::store_with_timestamp(&content, kind, &source, self.tick);
 
                 self.constellation_of_insights.push(feedback);
+                self.insight_ticks.push(self.tick);
 
                 // Keep only the most recent insights
                 if self.constellation_of_insights.len() > MAX_INSIGHTS {
                     self.constellation_of_insights.remove(0);
+                    self.insight_ticks.remove(0);
                 }
             }
         }
@@ -337,7 +850,10 @@ impl Supervisor {
                     self.message_queue.push(reply);
                 }
                 MessageKind::MemorySearch { query } => {
-                    let search_results = memory_store::search(query);
+                    let focus = self.agents.iter()
+                        .find(|agent| agent.id() == msg.from)
+                        .and_then(|agent| agent.focus().map(String::from));
+                    let search_results = memory_store::search_focused(query, focus.as_deref());
                     let results: Vec<(u64, String)> = search_results.iter()
                         .take(10)
                         .filter_map(|(id, _score)| {
@@ -352,8 +868,8 @@ impl Supervisor {
                             })
                         })
                         .collect();
-                    serial_println!("[MEMORY_STORE] Agent {} searched '{}', {} results",
-                        msg.from.0, query, results.len());
+                    serial_println!("[MEMORY_STORE] Agent {} searched '{}' (focus: {}), {} results",
+                        msg.from.0, query, focus.as_deref().unwrap_or("none"), results.len());
                     let reply = Message::new(
                         self.id,
                         Some(msg.from),
@@ -361,38 +877,188 @@ impl Supervisor {
                     );
                     self.message_queue.push(reply);
                 }
+                MessageKind::HeartbeatAck { agent_id } => {
+                    self.record_heartbeat_ack(*agent_id);
+                }
+                MessageKind::FileOp { change, path, content } => {
+                    let (success, data) = self.apply_file_op(msg.from, *change, path, content.as_deref());
+                    let reply = Message::new(
+                        self.id,
+                        Some(msg.from),
+                        MessageKind::Response { success, data },
+                    );
+                    self.message_queue.push(reply);
+                }
+                MessageKind::Announce { agent_name, text, level } => {
+                    self.record_announcement(agent_name, text, *level);
+                }
+                MessageKind::PromptEvolution { system_prompt, hypothesis } => {
+                    let (success, data) = self.start_prompt_evolution(msg.from, system_prompt, hypothesis);
+                    let reply = Message::new(
+                        self.id,
+                        Some(msg.from),
+                        MessageKind::Response { success, data },
+                    );
+                    self.message_queue.push(reply);
+                }
+                MessageKind::Pong { sent_tick } if msg.to == Some(self.id) => {
+                    self.record_pong(msg.from, *sent_tick);
+                }
+                MessageKind::TestReport { suite, passed, failed, details } => {
+                    self.report_tests(msg.from, suite, *passed, *failed, details);
+                }
+                MessageKind::QueryResult { correlation_id, topic, rows } if msg.to == Some(self.id) => {
+                    self.report_query_result(*correlation_id, topic, rows);
+                }
+                MessageKind::Query { topic, args } => {
+                    let rows = self.answer_query(msg.to, topic, args);
+                    serial_println!("[QUERY] Agent {} asked '{}': {}", msg.from.0, topic,
+                        if rows.is_some() { "answered" } else { "no handler" });
+                    let reply = Message::new(
+                        self.id,
+                        Some(msg.from),
+                        MessageKind::QueryResult {
+                            correlation_id: msg.id,
+                            topic: topic.clone(),
+                            rows: rows.unwrap_or_default(),
+                        },
+                    );
+                    self.message_queue.push(reply);
+                }
                 _ => {
                     routable_messages.push(msg);
                 }
             }
         }
 
-        // Route other messages to agents
-        for agent in self.agents.iter_mut() {
-            // Collect messages for this agent
+        // Route other messages to agents, in ID order (see `tick_order`)
+        let agent_ids: Vec<AgentId> = self.agents.iter().map(|a| a.id()).collect();
+        let slots: Vec<usize> = self.tick_order
+            .iter()
+            .filter_map(|id| agent_ids.iter().position(|a| a == id))
+            .collect();
+        let mut requested_timers = Vec::new();
+        for slot in slots {
+            let agent = &mut self.agents[slot];
+            // Shutdown is final: a wound-down agent gets no more ticks or mail
+            if self.agent_states[slot] == super::AgentState::ShuttingDown {
+                continue;
+            }
+
+            // Collect messages for this agent (broadcasts only if subscribed)
+            let subscriptions = agent.subscriptions();
             let mut inbox: Vec<Message> = routable_messages
                 .iter()
-                .filter(|m| m.to.is_none() || m.to == Some(agent.id()))
+                .filter(|m| match m.to {
+                    None => subscriptions.accepts(&m.kind),
+                    Some(to) => to == agent.id(),
+                })
                 .cloned()
                 .collect();
+
+            // A paused agent's directed mail waits for it to resume (broadcasts
+            // pass it by); a Shutdown still gets through, with the held mail
+            let shutting_down = inbox.iter().any(|m| matches!(m.kind, MessageKind::Shutdown { .. }));
+            if self.tick_timings[slot].paused && !shutting_down {
+                let held = &mut self.held_mail[slot];
+                held.extend(inbox.into_iter().filter(|m| m.to.is_some()));
+                if held.len() > MAX_HELD_MAIL {
+                    let excess = held.len() - MAX_HELD_MAIL;
+                    held.drain(..excess);
+                    self.route_stats[slot].dropped += excess as u64;
+                }
+                continue;
+            }
+            if !self.held_mail[slot].is_empty() {
+                let mut held = core::mem::take(&mut self.held_mail[slot]);
+                held.append(&mut inbox);
+                inbox = held;
+            }
             
             let mut outbox: Vec<Message> = Vec::new();
+            let mut timers = Vec::new();
             
             // Create context and tick the agent
             let mut ctx = AgentContext {
                 inbox: &mut inbox,
                 outbox: &mut outbox,
                 tick: self.tick,
+                agent_id: agent.id(),
+                timers: &mut timers,
             };
             
-            let _new_state = agent.tick(&mut ctx);
+            let old_state = self.agent_states[slot];
+            let started = crate::interrupts::timer_ticks();
+            let mut new_state = if shutting_down {
+                agent.handle_shutdown(&mut ctx);
+                super::AgentState::ShuttingDown
+            } else {
+                agent.tick(&mut ctx)
+            };
+            let elapsed = crate::interrupts::timer_ticks().saturating_sub(started);
+            let timing = &mut self.tick_timings[slot];
+            if record_tick_duration(timing, elapsed) {
+                serial_println!("[WATCHDOG] {} tick took {} PIT ticks (~{} ms, limit {})", agent.name(),
+                    elapsed, crate::interrupts::ticks_to_ms(elapsed), SLOW_TICK_PIT_TICKS);
+                crate::event_log::record(&alloc::format!(
+                    "Slow tick by {}: ~{} ms", agent.name(), crate::interrupts::ticks_to_ms(elapsed)));
+                if timing.paused {
+                    serial_println!("[WATCHDOG] {} paused after {} slow ticks ('agent resume {}' resumes it)",
+                        agent.name(), timing.slow_ticks, agent.name());
+                    crate::event_log::record(&alloc::format!("Watchdog paused {}", agent.name()));
+                    if new_state != super::AgentState::ShuttingDown {
+                        new_state = super::AgentState::Paused;
+                    }
+                }
+            }
+            if !old_state.can_transition_to(new_state) {
+                serial_println!("[SUPERVISOR] WARNING: {} tried illegal transition {:?} -> {:?}; keeping {:?}",
+                    agent.name(), old_state, new_state, old_state);
+                crate::event_log::record(&alloc::format!(
+                    "Illegal state change {old_state:?} -> {new_state:?} by {}", agent.name()));
+            } else if new_state != old_state {
+                self.agent_states[slot] = new_state;
+                activity.state_changes += 1;
+            }
+            
+            let stats = &mut self.route_stats[slot];
+            stats.delivered += inbox.len() as u64;
+            stats.sent += outbox.len() as u64;
+            for msg in &inbox {
+                match agent_ids.iter().position(|id| *id == msg.from) {
+                    Some(from) if from != slot => record_link(&mut self.agent_links, from, slot),
+                    _ => {}
+                }
+            }
+            
+            requested_timers.extend(timers.into_iter().map(|(in_ticks, payload)| (agent.id(), in_ticks, payload)));
             
-            // Collect outgoing messages
-            for mut msg in outbox {
+            // Collect outgoing messages (capped so a runaway agent can't flood the queue)
+            let mut dropped = 0u64;
+            for (i, mut msg) in outbox.into_iter().enumerate() {
                 msg.timestamp = self.tick;
-                self.message_queue.push(msg);
+                if i < self.outbox_cap {
+                    self.message_queue.push(msg);
+                } else {
+                    dropped += 1;
+                    self.dead_letters.push(msg);
+                    if self.dead_letters.len() > MAX_DEAD_LETTERS {
+                        self.dead_letters.remove(0);
+                    }
+                }
+            }
+            if dropped > 0 {
+                serial_println!("[SUPERVISOR] {} exceeded outbox cap ({}): {} messages dead-lettered",
+                    agent.name(), self.outbox_cap, dropped);
+                match self.outbox_overflows.iter_mut().find(|(id, _)| *id == agent.id()) {
+                    Some(entry) => entry.1 += dropped,
+                    None => self.outbox_overflows.push((agent.id(), dropped)),
+                }
             }
         }
+        for (agent_id, in_ticks, payload) in requested_timers {
+            self.schedule(agent_id, in_ticks, payload);
+        }
         
         // Serendipity Engine: Check for connections (every 3000 ticks ~30s)
         self.serendipity_counter += 1;
@@ -401,21 +1067,9 @@ impl Supervisor {
             self.serendipity_counter = 0;
         }
 
-        // Daily rhythm: periodic checkpoint and status reports
-        self.rhythm_counter += 1;
-
-        // Midday checkpoint (every 60,000 ticks ~10 min)
-        if self.rhythm_counter % 60_000 == 0 {
-            serial_println!("[CHECKPOINT] Periodic checkpoint at tick {}", self.tick);
-            for agent in self.agents.iter() {
-                let progress = agent.checkpoint();
-                if !progress.is_empty() {
-                    serial_println!("[CHECKPOINT] [{}]:", agent.name());
-                    for item in &progress {
-                        serial_println!("[CHECKPOINT]   - {}", item);
-                    }
-                }
-            }
+        // Daily rhythm: the RTC decides when each phase is due
+        if self.tick % SCHEDULE_CHECK_TICKS == 0 {
+            self.run_daily_schedule();
         }
 
         // Journal: "As the Kernel Turns" entries (every 90,000 ticks ~15 min)
@@ -425,36 +1079,221 @@ impl Supervisor {
             self.journal_counter = 0;
         }
 
-        // Status report (every 120,000 ticks ~20 min)
-        if self.rhythm_counter % 120_000 == 0 {
-            serial_println!("[REPORT] Periodic status report at tick {}", self.tick);
-            for agent in self.agents.iter() {
-                let report = agent.eod_report();
-                if !report.is_empty() {
-                    serial_println!("[REPORT] [{}]:", agent.name());
-                    for item in &report {
-                        serial_println!("[REPORT]   - {}", item);
-                    }
-                }
+        self.prune_faded_feelings();
+
+        // Memory WAL checkpoint (crash resilience between full saves)
+        if self.memory_checkpoint_interval > 0 && self.tick % self.memory_checkpoint_interval == 0 {
+            let flushed = memory_store::checkpoint();
+            if flushed > 0 {
+                serial_println!("[MEMORY_STORE] Checkpoint at tick {}: {} ops", self.tick, flushed);
+            }
+        }
+
+        // Memory autosave on real time (the PIT), not ticks, so it keeps pace
+        // however fast the main loop runs
+        if self.autosave_due(crate::interrupts::uptime_ms()) && memory_store::is_dirty() {
+            memory_store::save();
+            serial_println!("[MEMORY_STORE] Autosaved at tick {} (every {} min)", self.tick, self.autosave_minutes);
+        }
+
+        if activity.is_idle() {
+            self.idle_ticks += 1;
+        } else {
+            self.idle_ticks = 0;
+        }
+        activity
+    }
+    
+    /// Consecutive ticks without activity
+    pub fn idle_ticks(&self) -> u64 {
+        self.idle_ticks
+    }
+    
+    /// Idle long enough that the main loop should back off
+    pub fn is_low_power(&self) -> bool {
+        self.idle_ticks >= LOW_POWER_AFTER_IDLE_TICKS
+    }
+    
+    /// Input arrived: leave low-power mode immediately
+    pub fn wake(&mut self) {
+        self.idle_ticks = 0;
+    }
+    
+    /// Drop feelings whose intensity has decayed to zero
+    ///
+    /// Sparks, connections and resources never decay.
+    fn prune_faded_feelings(&mut self) {
+        let mut i = 0;
+        while i < self.constellation_of_insights.len() {
+            let age = self.tick - self.insight_ticks[i];
+            let faded = matches!(&self.constellation_of_insights[i],
+                FeedbackType::Feeling { intensity, .. } if message::decayed_intensity(*intensity, age) == 0);
+            if faded {
+                self.constellation_of_insights.remove(i);
+                self.insight_ticks.remove(i);
+            } else {
+                i += 1;
             }
         }
     }
     
+    /// Refresh `PANIC_CONTEXT` so a crash report reflects this tick
+    fn publish_panic_context(&self) {
+        let mut counts = [0usize; 4];
+        for insight in &self.constellation_of_insights {
+            let slot = match insight {
+                FeedbackType::Spark { .. } => 0,
+                FeedbackType::Connection { .. } => 1,
+                FeedbackType::Resource { .. } => 2,
+                FeedbackType::Feeling { .. } => 3,
+            };
+            counts[slot] += 1;
+        }
+        PANIC_CONTEXT.tick.store(self.tick, Ordering::Relaxed);
+        PANIC_CONTEXT.agents.store(self.agents.len(), Ordering::Relaxed);
+        for (published, count) in PANIC_CONTEXT.insights.iter().zip(counts) {
+            published.store(count, Ordering::Relaxed);
+        }
+    }
+    
+    /// Set ticks between memory WAL checkpoints (0 disables them)
+    pub fn set_memory_checkpoint_interval(&mut self, ticks: u64) {
+        self.memory_checkpoint_interval = ticks;
+    }
+    
+    /// Ticks between memory WAL checkpoints (0 = disabled)
+    pub fn memory_checkpoint_interval(&self) -> u64 {
+        self.memory_checkpoint_interval
+    }
+
+    /// Set real minutes between memory autosaves (0 disables them)
+    pub fn set_autosave_minutes(&mut self, minutes: u64) {
+        self.autosave_minutes = minutes;
+    }
+
+    /// Real minutes between memory autosaves (0 = disabled)
+    pub fn autosave_minutes(&self) -> u64 {
+        self.autosave_minutes
+    }
+
+    /// Whether an autosave interval has elapsed by `now_ms` of uptime; starts
+    /// the next interval when it has
+    fn autosave_due(&mut self, now_ms: u64) -> bool {
+        if self.autosave_minutes == 0
+            || now_ms.saturating_sub(self.last_autosave_ms) < self.autosave_minutes * 60_000
+        {
+            return false;
+        }
+        self.last_autosave_ms = now_ms;
+        true
+    }
+    
+    /// Ask agents to run their tests, or only the named `suite`; `verbose`
+    /// prints each test line when the reports arrive
+    pub fn request_tests(&mut self, suite: Option<&str>, verbose: bool) {
+        self.verbose_test_report = verbose;
+        self.broadcast(MessageKind::Request {
+            action: String::from("run_tests"),
+            params: suite.map(String::from).into_iter().collect(),
+        });
+    }
+
+    /// Surface an agent's TestReport for one suite to the shell
+    fn report_tests(&mut self, from: AgentId, suite: &str, passed: u32, failed: u32, details: &[String]) {
+        use crate::vga_buffer::{println_marked, MARK_GREEN, MARK_RED, MARK_RESET};
+        serial_println!("[TEST_REPORT] Agent {} [{}]: {} passed, {} failed", from.0, suite, passed, failed);
+        let failed_mark = if failed > 0 { MARK_RED } else { MARK_RESET };
+        println_marked(&alloc::format!("Test report from agent {} [{}]: {}{} passed{}, {}{} failed{}",
+            from.0, suite, MARK_GREEN, passed, MARK_RESET, failed_mark, failed, MARK_RESET));
+        if self.verbose_test_report {
+            for line in details {
+                serial_println!("[TEST_REPORT]   {}", line);
+                let marked = line
+                    .replacen("PASSED", &alloc::format!("{MARK_GREEN}PASSED{MARK_RESET}"), 1)
+                    .replacen("FAILED", &alloc::format!("{MARK_RED}FAILED{MARK_RESET}"), 1);
+                println_marked(&alloc::format!("  {marked}"));
+            }
+        }
+    }
+
     /// Pulse the heartbeat - broadcast the living ambition DNA
     fn pulse(&mut self) {
+        self.sync_ambition();
         if let Some(ref ambition) = self.living_ambition {
             serial_println!("[HEARTBEAT] Pulsing ambition DNA to all agents...");
             self.broadcast(MessageKind::Heartbeat(ambition.clone()));
+            if self.first_pulse_tick.is_none() {
+                self.first_pulse_tick = Some(self.tick);
+            }
+        }
+    }
+
+    /// Remember when an agent last acknowledged the heartbeat
+    fn record_heartbeat_ack(&mut self, agent_id: AgentId) {
+        match self.heartbeat_acks.iter_mut().find(|(id, _)| *id == agent_id) {
+            Some(entry) => entry.1 = self.tick,
+            None => self.heartbeat_acks.push((agent_id, self.tick)),
+        }
+    }
+
+    /// Tick of the last heartbeat ack from an agent, if any
+    pub fn last_heartbeat_ack(&self, agent_id: AgentId) -> Option<u64> {
+        self.heartbeat_acks
+            .iter()
+            .find(|(id, _)| *id == agent_id)
+            .map(|(_, tick)| *tick)
+    }
+
+    /// True if heartbeats are pulsing but the agent hasn't acked for several cycles
+    pub fn is_unresponsive(&self, agent_id: AgentId) -> bool {
+        let first_pulse = match self.first_pulse_tick {
+            Some(tick) => tick,
+            None => return false,
+        };
+        let since = self.last_heartbeat_ack(agent_id).unwrap_or(0).max(first_pulse);
+        self.tick.saturating_sub(since) > HEARTBEAT_INTERVAL * UNRESPONSIVE_CYCLES
+    }
+
+    /// Names of agents flagged as unresponsive
+    pub fn unresponsive_agents(&self) -> Vec<String> {
+        self.agents
+            .iter()
+            .filter(|a| self.is_unresponsive(a.id()))
+            .map(|a| String::from(a.name()))
+            .collect()
+    }
+
+    /// Print each agent's last heartbeat ack age
+    pub fn print_heartbeat_status(&self) {
+        serial_println!("[HEARTBEAT] Ack status (tick {}):", self.tick);
+        println!("Heartbeat acks (tick {}):", self.tick);
+        for agent in &self.agents {
+            let age = match self.last_heartbeat_ack(agent.id()) {
+                Some(tick) => alloc::format!("{} ticks ago", self.tick - tick),
+                None => String::from("never"),
+            };
+            let flag = if self.is_unresponsive(agent.id()) { " [UNRESPONSIVE]" } else { "" };
+            serial_println!("  {:?} [{}]: last ack {}{}", agent.id(), agent.name(), age, flag);
+            println!("  {} - last ack {}{}", agent.name(), age, flag);
         }
     }
     
+    /// Run the serendipity engine now instead of waiting for its interval.
+    /// Returns true if it broadcast a connection.
+    pub fn force_serendipity(&mut self) -> bool {
+        self.serendipity_counter = 0;
+        self.check_serendipity()
+    }
+
     /// Serendipity Engine: Find overlapping themes using memory search
     /// Guard: skip themes already broadcast to prevent feedback loops where
     /// connection entries (which contain the theme keyword) amplify future searches.
-    fn check_serendipity(&mut self) {
+    ///
+    /// Returns true if a connection was broadcast.
+    fn check_serendipity(&mut self) -> bool {
         let stats = memory_store::stats();
         if stats.entry_count < 2 {
-            return;
+            return false;
         }
 
         // Cap seen themes list to prevent unbounded growth (keep last 20)
@@ -462,68 +1301,85 @@ impl Supervisor {
             self.serendipity_seen_themes.drain(..10);
         }
 
-        // Use the memory store's top keywords to find the strongest theme
-        // Only broadcast ONE connection per serendipity check
-        // Skip themes we've already broadcast (prevents feedback loops)
-        if !stats.top_keywords.is_empty() {
-            for (keyword, count) in stats.top_keywords.iter().take(5) {
-                if *count >= 2 {
-                    // Skip if we already broadcast this theme
-                    if self.serendipity_seen_themes.iter().any(|t| t == keyword) {
-                        continue;
-                    }
+        // Only broadcast ONE connection per serendipity check, and skip themes
+        // we've already broadcast (prevents feedback loops).
+        // Prefer keyword pairs that keep appearing together: a stronger signal
+        // than a single shared keyword.
+        for ((a, b), count) in memory_store::top_cooccurrences(5) {
+            if count < 2 {
+                break;
+            }
+            let theme = alloc::format!("{a} + {b}");
+            if self.serendipity_seen_themes.contains(&theme) {
+                continue;
+            }
+            let ids = memory_store::pair_entries(&a, &b);
+            if ids.len() >= 2 && self.broadcast_serendipity(&theme, ids[0], ids[1]) {
+                serial_println!("[SERENDIPITY] Pair '{}' ({} shared entries) — broadcasted connection (new theme)",
+                    theme, count);
+                return true;
+            }
+        }
 
-                    let results = memory_store::search(keyword);
-                    if results.len() >= 2 {
-                        let first_preview = memory_store::get(results[0].0)
-                            .map(|e| if e.content.len() > 60 {
-                                let s: String = e.content.chars().take(57).collect();
-                                alloc::format!("{}...", s)
-                            } else {
-                                e.content.clone()
-                            });
-                        let second_preview = memory_store::get(results[1].0)
-                            .map(|e| if e.content.len() > 60 {
-                                let s: String = e.content.chars().take(57).collect();
-                                alloc::format!("{}...", s)
-                            } else {
-                                e.content.clone()
-                            });
-
-                        if let (Some(from), Some(to)) = (first_preview, second_preview) {
-                            let connection_msg = Message::broadcast(
-                                self.id,
-                                MessageKind::Feedback(FeedbackType::Connection {
-                                    from: from.clone(),
-                                    to: to.clone(),
-                                    pattern: alloc::format!("Serendipity: theme '{}' links these insights", keyword),
-                                }),
-                            );
-                            self.message_queue.push(connection_msg);
-                            self.serendipity_seen_themes.push(keyword.clone());
-                            serial_println!("[SERENDIPITY] Theme '{}' ({} entries, {} hits) — broadcasted connection (new theme)",
-                                keyword, count, results.len());
-                            break; // Only one notification per cycle
-                        }
-                    }
-                }
+        // Fall back to the strongest single keyword
+        for (keyword, count) in stats.top_keywords.iter().take(5) {
+            if *count < 2 || self.serendipity_seen_themes.contains(keyword) {
+                continue;
+            }
+            let results = memory_store::search(keyword);
+            if results.len() >= 2 && self.broadcast_serendipity(keyword, results[0].0, results[1].0) {
+                serial_println!("[SERENDIPITY] Theme '{}' ({} entries, {} hits) — broadcasted connection (new theme)",
+                    keyword, count, results.len());
+                return true;
             }
         }
+        false
+    }
+
+    /// Broadcast a serendipity Connection between two memories and remember
+    /// the theme. Returns false if either memory is gone.
+    fn broadcast_serendipity(&mut self, theme: &str, first: u64, second: u64) -> bool {
+        let preview = |id: u64| memory_store::get(id).map(|e| if e.content.len() > 60 {
+            let s: String = e.content.chars().take(57).collect();
+            alloc::format!("{}...", s)
+        } else {
+            e.content
+        });
+        let (Some(from), Some(to)) = (preview(first), preview(second)) else {
+            return false;
+        };
+        let connection_msg = Message::broadcast(
+            self.id,
+            MessageKind::Feedback(FeedbackType::Connection {
+                from,
+                to,
+                pattern: alloc::format!("Serendipity: theme '{}' links these insights", theme),
+            }),
+        );
+        self.message_queue.push(connection_msg);
+        self.serendipity_seen_themes.push(String::from(theme));
+        true
     }
     
     // =========================================================================
     // Journal — "As the Kernel Turns"
     // =========================================================================
 
+    /// Each agent's journal entry for the current tick, as (agent name, entry)
+    pub fn journal_entries(&self) -> Vec<(String, String)> {
+        self.agents
+            .iter()
+            .filter_map(|agent| agent.journal_entry(self.tick).map(|entry| (String::from(agent.name()), entry)))
+            .collect()
+    }
+
     /// Emit journal entries from all agents via serial protocol
     fn emit_journal_entries(&mut self) {
         serial_println!("[JOURNAL_START]");
-        for agent in self.agents.iter() {
-            if let Some(entry) = agent.journal_entry(self.tick) {
-                // Escape newlines and pipes in entry text for single-line serial transport
-                let escaped = entry.replace('\n', " ").replace('|', "-");
-                serial_println!("[JOURNAL] {}|{}|{}", agent.name(), self.tick, escaped);
-            }
+        for (name, entry) in self.journal_entries() {
+            // Escape newlines and pipes in entry text for single-line serial transport
+            let escaped = entry.replace('\n', " ").replace('|', "-");
+            serial_println!("[JOURNAL] {}|{}|{}", name, self.tick, escaped);
         }
         serial_println!("[JOURNAL_DONE]");
     }
@@ -556,12 +1412,36 @@ impl Supervisor {
         serial_println!("[JOURNAL_DONE]");
     }
 
-    /// Trigger morning ambitions for all agents
-    pub fn morning_ambition(&mut self) {
-        serial_println!("[SUPERVISOR] === MORNING AMBITION ===");
-        println!();
-        println!("  === DAILY AMBITION ===");
-        
+    /// Run any daily phases the RTC says are due
+    fn run_daily_schedule(&mut self) {
+        let now = crate::rtc::now();
+        for phase in self.daily_scheduler.due(&now) {
+            serial_println!("[SCHEDULE] {:02}:{:02} - {}", now.hour, now.minute, phase.name());
+            match phase {
+                DailyPhase::Morning => self.morning_ambition(),
+                DailyPhase::Midday => self.midday_checkpoint(),
+                DailyPhase::EndOfDay => self.eod_report(),
+                DailyPhase::Reflection => self.nightly_reflection(),
+            }
+        }
+    }
+    
+    /// The wall-clock daily schedule
+    pub fn daily_schedule(&self) -> &DailyScheduler {
+        &self.daily_scheduler
+    }
+    
+    /// Set the hour (0-23) of the morning ambition. Returns false if out of range.
+    pub fn set_morning_hour(&mut self, hour: u8) -> bool {
+        self.daily_scheduler.set_hour(DailyPhase::Morning, hour)
+    }
+    
+    /// Trigger morning ambitions for all agents
+    pub fn morning_ambition(&mut self) {
+        serial_println!("[SUPERVISOR] === MORNING AMBITION ===");
+        println!();
+        println!("  === DAILY AMBITION ===");
+        
         self.broadcast(MessageKind::SystemEvent(SystemEvent::MorningAmbition));
         
         for agent in self.agents.iter_mut() {
@@ -580,6 +1460,8 @@ impl Supervisor {
     /// Trigger midday checkpoint for all agents
     pub fn midday_checkpoint(&mut self) {
         serial_println!("[SUPERVISOR] === MIDDAY CHECKPOINT ===");
+        println!();
+        println!("  === MIDDAY CHECKPOINT ===");
         
         self.broadcast(MessageKind::SystemEvent(SystemEvent::MiddayCheckpoint));
         
@@ -587,8 +1469,10 @@ impl Supervisor {
             let progress = agent.checkpoint();
             if !progress.is_empty() {
                 serial_println!("[{}] Progress:", agent.name());
+                println!("  [{}]", agent.name());
                 for item in &progress {
                     serial_println!("  - {}", item);
+                    println!("    - {}", item);
                 }
             }
         }
@@ -615,6 +1499,131 @@ impl Supervisor {
         }
     }
     
+    /// Trigger nightly reflection for all agents
+    pub fn nightly_reflection(&mut self) {
+        serial_println!("[SUPERVISOR] === NIGHTLY REFLECTION ===");
+        println!();
+        println!("  === NIGHTLY REFLECTION ===");
+        
+        self.broadcast(MessageKind::SystemEvent(SystemEvent::NightReflection));
+        
+        let mut reflected = 0;
+        for agent in self.agents.iter_mut() {
+            let lessons = agent.reflect();
+            if !lessons.is_empty() {
+                reflected += 1;
+                serial_println!("[{}] Reflected:", agent.name());
+                println!("  [{}]", agent.name());
+                for lesson in &lessons {
+                    serial_println!("  - {}", lesson);
+                    println!("    ~ {}", lesson);
+                }
+            }
+        }
+        
+        serial_println!("[SUPERVISOR] {} of {} agents reflected", reflected, self.agents.len());
+        println!("  {} of {} agents reflected", reflected, self.agents.len());
+    }
+    
+    /// Capabilities of the agent with the given name (case-insensitive)
+    pub fn agent_capabilities(&self, name: &str) -> Option<Vec<String>> {
+        self.agents
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case(name))
+            .map(|a| a.capabilities())
+    }
+    
+    /// Broadcast filter of the agent with the given name (case-insensitive)
+    pub fn agent_subscriptions(&self, name: &str) -> Option<super::message::MessageFilter> {
+        self.agents
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case(name))
+            .map(|a| a.subscriptions())
+    }
+    
+    /// Ask an agent (by name, case-insensitive) a structured query
+    ///
+    /// Outer None: no such agent. Inner None: the agent doesn't serve the topic.
+    pub fn query(&self, name: &str, topic: &str, args: &[String]) -> Option<Option<Vec<String>>> {
+        self.agents
+            .iter()
+            .find(|a| a.name().eq_ignore_ascii_case(name))
+            .map(|a| a.handle_query(topic, args))
+    }
+    
+    /// Send a `Query` to an agent (by name, case-insensitive); the answer is
+    /// printed when its `QueryResult` comes back, matched by correlation id.
+    /// Returns the query's message id, or None if there is no such agent.
+    pub fn send_query(&mut self, name: &str, topic: &str, args: Vec<String>) -> Option<u64> {
+        let agent = self.agents.iter().find(|a| a.name().eq_ignore_ascii_case(name))?;
+        let (to, agent_name) = (agent.id(), String::from(agent.name()));
+        let msg = Message::new(self.id, Some(to), MessageKind::Query { topic: String::from(topic), args });
+        let id = msg.id;
+        self.pending_queries.push((id, agent_name));
+        self.send(msg);
+        Some(id)
+    }
+    
+    /// Print the answer to a query sent by `send_query`
+    fn report_query_result(&mut self, correlation_id: u64, topic: &str, rows: &[String]) {
+        let Some(pos) = self.pending_queries.iter().position(|(id, _)| *id == correlation_id) else {
+            serial_println!("[QUERY] Unmatched result #{} for '{}'", correlation_id, topic);
+            return;
+        };
+        let (_, agent) = self.pending_queries.remove(pos);
+        serial_println!("[QUERY] #{} {} / {}: {} rows", correlation_id, agent, topic, rows.len());
+        if rows.is_empty() {
+            println!("  {} does not answer '{}'", agent, topic);
+            return;
+        }
+        println!("  === {} / {} ({} rows) ===", agent.to_uppercase(), topic, rows.len());
+        for row in rows {
+            println!("    {}", row);
+        }
+    }
+    
+    /// Serve a routed query: the addressed agent answers, or for a broadcast
+    /// the first agent that serves the topic
+    fn answer_query(&self, to: Option<AgentId>, topic: &str, args: &[String]) -> Option<Vec<String>> {
+        self.agents
+            .iter()
+            .filter(|a| to.is_none() || to == Some(a.id()))
+            .find_map(|a| a.handle_query(topic, args))
+    }
+    
+    /// Shut down every agent in registration order and print a final report
+    pub fn shutdown_all(&mut self) {
+        serial_println!("[SUPERVISOR] === SHUTDOWN ===");
+        println!();
+        println!("  === SHUTDOWN ===");
+        
+        // Each agent finds a Shutdown in its inbox and gets one tick to push
+        // final messages; a second tick lets the supervisor handle them
+        // (memory stores, announcements) before anyone is stopped
+        let ids: Vec<AgentId> = self.agents.iter().map(|a| a.id()).collect();
+        for id in ids {
+            let reason = String::from("system shutdown");
+            self.send(Message::new(self.id, Some(id), MessageKind::Shutdown { reason }));
+        }
+        self.step();
+        self.step();
+        
+        for agent in self.agents.iter_mut() {
+            serial_println!("[SUPERVISOR] Shutting down {}...", agent.name());
+            println!("  Stopping {}...", agent.name());
+            agent.shutdown();
+        }
+        
+        let stats = memory_store::stats();
+        serial_println!("[SUPERVISOR] Final report: tick {}, {} agents, {} insights, {} memories",
+            self.tick, self.agents.len(), self.constellation_of_insights.len(), stats.entry_count);
+        println!();
+        println!("  Final tick:      {}", self.tick);
+        println!("  Agents stopped:  {}", self.agents.len());
+        println!("  Insights:        {}", self.constellation_of_insights.len());
+        println!("  Memories:        {}", stats.entry_count);
+    }
+    
     /// Get agent count
     pub fn agent_count(&self) -> usize {
         self.agents.len()
@@ -625,11 +1634,167 @@ impl Supervisor {
         self.tick
     }
     
+    /// Set the max outbox messages accepted per agent per tick
+    pub fn set_outbox_cap(&mut self, cap: usize) {
+        self.outbox_cap = cap;
+    }
+    
+    /// Messages an agent has had dead-lettered for exceeding the outbox cap
+    pub fn overflow_count(&self, agent_id: AgentId) -> u64 {
+        self.outbox_overflows
+            .iter()
+            .find(|(id, _)| *id == agent_id)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+    
+    /// "Name: count" for every agent that has overflowed its outbox
+    pub fn overflowing_agents(&self) -> Vec<String> {
+        self.agents
+            .iter()
+            .filter(|a| self.overflow_count(a.id()) > 0)
+            .map(|a| alloc::format!("{}: {}", a.name(), self.overflow_count(a.id())))
+            .collect()
+    }
+    
+    /// Keep an agent's announcement and echo it to the event log
+    /// (and to the console when it's a warning or error)
+    fn record_announcement(&mut self, agent_name: &str, text: &str, level: message::AnnounceLevel) {
+        serial_println!("[ANNOUNCE] {} ({}): {}", agent_name, level.as_str(), text);
+        crate::event_log::record(&alloc::format!("{agent_name} ({}): {text}", level.as_str()));
+        if level != message::AnnounceLevel::Info {
+            println!("[{}] {}: {}", level.as_str(), agent_name, text);
+        }
+        self.announcements.push(Announcement {
+            tick: self.tick,
+            agent_name: String::from(agent_name),
+            text: String::from(text),
+            level,
+        });
+        if self.announcements.len() > MAX_ANNOUNCEMENTS {
+            self.announcements.remove(0);
+        }
+    }
+
+    /// Recent status announcements (most recent last)
+    pub fn announcements(&self) -> &[Announcement] {
+        &self.announcements
+    }
+    
+    /// Messages dropped for exceeding the outbox cap (most recent last)
+    pub fn dead_letters(&self) -> &[Message] {
+        &self.dead_letters
+    }
+    
+    /// Routing counters for every agent, by name
+    pub fn route_stats(&self) -> Vec<(String, RouteStats)> {
+        self.agents
+            .iter()
+            .zip(self.route_stats.iter())
+            .map(|(a, s)| (String::from(a.name()), *s))
+            .collect()
+    }
+    
+    /// Name, state and messages delivered for each agent (for live views)
+    pub fn agent_overview(&self) -> Vec<(String, super::AgentState, u64)> {
+        self.agents
+            .iter()
+            .zip(self.route_stats.iter().zip(self.agent_states.iter()))
+            .map(|(a, (s, state))| (String::from(a.name()), *state, s.delivered))
+            .collect()
+    }
+    
+    /// Messages delivered between pairs of agents since the last reset, as
+    /// (slot, slot, count) with slots in `agent_overview()` order
+    pub fn agent_links(&self) -> &[(usize, usize, u64)] {
+        &self.agent_links
+    }
+    
+    /// Zero all routing counters
+    pub fn reset_route_stats(&mut self) {
+        for stats in self.route_stats.iter_mut() {
+            *stats = RouteStats::default();
+        }
+        self.agent_links.clear();
+    }
+    
+    /// Messages routed per kind since the last reset, by kind name
+    pub fn kind_counts(&self) -> &BTreeMap<&'static str, u64> {
+        &self.kind_counts
+    }
+    
+    /// Resume an agent the watchdog paused, looked up by name
+    /// (case-insensitive). Its slow-tick count starts over; the slowest
+    /// tick is kept. Returns the agent's name, or None if no agent was paused.
+    pub fn resume_agent(&mut self, name: &str) -> Option<String> {
+        let slot = self.agents.iter().position(|a| a.name().eq_ignore_ascii_case(name))?;
+        let timing = &mut self.tick_timings[slot];
+        if !timing.paused {
+            return None;
+        }
+        timing.paused = false;
+        timing.slow_ticks = 0;
+        self.agent_states[slot] = super::AgentState::Ready;
+        let name = String::from(self.agents[slot].name());
+        crate::event_log::record(&alloc::format!("Agent {name} resumed"));
+        Some(name)
+    }
+    
+    /// Zero the per-kind message counts
+    pub fn reset_kind_counts(&mut self) {
+        self.kind_counts.clear();
+    }
+    
     /// Print status of all agents
     pub fn print_status(&self) {
         serial_println!("[SUPERVISOR] Agent Status (tick {}):", self.tick);
         for agent in &self.agents {
-            serial_println!("  {:?} [{}]: {:?}", agent.id(), agent.name(), agent.state());
+            let unresponsive = if self.is_unresponsive(agent.id()) { " (unresponsive)" } else { "" };
+            serial_println!("  {:?} [{}]: {:?}{} overflows={}", agent.id(), agent.name(), agent.state(),
+                unresponsive, self.overflow_count(agent.id()));
+        }
+        serial_println!("  Outbox cap: {}/tick, dead letters: {}", self.outbox_cap, self.dead_letters.len());
+    }
+    
+    /// Print a health table for all agents
+    pub fn print_health(&self) {
+        serial_println!("[SUPERVISOR] === AGENT HEALTH (tick {}) ===", self.tick);
+        println!();
+        println!("  {:<12} {:<12} {:>8} {:>6} {:>6} {:>8}", "AGENT", "STATE", "LAST", "ERRS", "MSGS", "SLOWEST");
+        for (agent, timing) in self.agents.iter().zip(self.tick_timings.iter()) {
+            let slowest_ms = crate::interrupts::ticks_to_ms(timing.slowest);
+            match agent.health() {
+                Some(h) => {
+                    let state = alloc::format!("{:?}", h.state);
+                    serial_println!("  [{}] state={} last_tick={} errors={} messages={} slowest_tick_ms={}",
+                        agent.name(), state, h.last_tick, h.errors, h.messages_processed, slowest_ms);
+                    println!("  {:<12} {:<12} {:>8} {:>6} {:>6} {:>6}ms",
+                        agent.name(), state, h.last_tick, h.errors, h.messages_processed, slowest_ms);
+                    for note in &h.notes {
+                        serial_println!("  [{}]   {}", agent.name(), note);
+                        println!("      {}", note);
+                    }
+                    if let Some(focus) = agent.focus() {
+                        serial_println!("  [{}]   focus: {}", agent.name(), focus);
+                        println!("      focus: {}", focus);
+                    }
+                }
+                None => {
+                    serial_println!("  [{}] no health report (state={:?}) slowest_tick_ms={}",
+                        agent.name(), agent.state(), slowest_ms);
+                    println!("  {:<12} {:<12} (no health report) slowest {}ms", agent.name(),
+                        alloc::format!("{:?}", agent.state()), slowest_ms);
+                }
+            }
+            if timing.slow_ticks > 0 {
+                let paused = if timing.paused { ", paused by watchdog" } else { "" };
+                serial_println!("  [{}]   {} slow ticks{}", agent.name(), timing.slow_ticks, paused);
+                println!("      {} slow ticks{}", timing.slow_ticks, paused);
+            }
+            if let Some(latency) = self.ping_latency(agent.id()) {
+                serial_println!("  [{}]   last ping: {} ticks", agent.name(), latency);
+                println!("      last ping: {} ticks", latency);
+            }
         }
     }
     
@@ -639,31 +1804,66 @@ impl Supervisor {
         println!();
         println!("  === AGENT ALLIANCE ACADEMY ===");
         
-        // Show each character's certification
-        let certifications = [
-            (character_ids::SAM, "Sam"),
-            (character_ids::ARCHIMEDES_VOICE, "Archimedes"),
-            (character_ids::THOMAS, "Thomas"),
-            (character_ids::PETE, "Pete"),
-            (character_ids::SENTINEL, "Sentinel"),
-            (character_ids::SCOUT, "Scout"),
-            (character_ids::SCRIBE, "Scribe"),
-        ];
+        // Running agents describe themselves
+        let mut embodied = Vec::new();
+        for agent in &self.agents {
+            let level = agent.character_id().and_then(|id| {
+                embodied.push(id);
+                library::with_library(|lib| lib.get_active(id).map(|p| p.certification)).flatten()
+            });
+            let marker = level.map_or("", |l| l.color_marker());
+            let description = agent.describe();
+            serial_println!("  {}", description);
+            crate::vga_buffer::println_marked(&alloc::format!("  {}{}{}",
+                marker, description, crate::vga_buffer::MARK_RESET));
+        }
         
-        for (char_id, name) in certifications {
-            if let Some((badge, level_name)) = library::with_library(|lib| {
-                lib.get_active(char_id)
-                    .map(|p| (p.certification.badge(), p.certification.name()))
-            }).flatten() {
-                serial_println!("  {} {} - {}", badge, name, level_name);
-                println!("  {} {} - {}", badge, name, level_name);
-            }
+        // The rest of the cast, straight from the prompt library
+        let others: Vec<(CertificationLevel, String)> = library::with_library(|lib| {
+            lib.active()
+                .into_iter()
+                .filter(|p| !embodied.contains(&p.id.character_id))
+                .map(|p| (p.certification, p.name.clone()))
+                .collect()
+        }).unwrap_or_default();
+        for (level, name) in others {
+            serial_println!("  {} {} - {}", level.badge(), name, level.name());
+            crate::vga_buffer::println_marked(&alloc::format!("  {}{} {} - {}{}",
+                level.color_marker(), level.badge(), name, level.name(),
+                crate::vga_buffer::MARK_RESET));
         }
         
         println!();
         println!("  https://as-the-cloud-turns-web.onrender.com/#academy");
     }
     
+    /// Each agent's one-line self-description, in registration order
+    pub fn agent_descriptions(&self) -> Vec<String> {
+        self.agents.iter().map(|agent| agent.describe()).collect()
+    }
+    
+    /// Every registered agent with its state, prompt role/certification and write tier
+    pub fn roster(&self) -> Vec<RosterEntry> {
+        self.agents
+            .iter()
+            .zip(self.agent_states.iter())
+            .map(|(agent, state)| {
+                let prompt = agent.character_id().and_then(|id| {
+                    library::with_library(|lib| lib.get_active(id).map(|p| (p.role, p.certification))).flatten()
+                });
+                RosterEntry {
+                    name: String::from(agent.name()),
+                    id: agent.id(),
+                    state: *state,
+                    role: prompt.map(|(role, _)| role),
+                    certification: prompt.map(|(_, level)| level),
+                    max_write_tier: agent.max_write_tier(),
+                    focus: agent.focus().map(String::from),
+                }
+            })
+            .collect()
+    }
+    
     /// Get prompt for a character
     pub fn get_prompt(&self, character_id: u32) -> Option<String> {
         library::with_library(|lib| {
@@ -676,8 +1876,10 @@ impl Supervisor {
     pub fn breathe(&mut self, ambition: String) {
         serial_println!("[SUPERVISOR] Setting living ambition (the soul)...");
         serial_println!("[SUPERVISOR] \"{}\"", ambition);
+        crate::event_log::record(&alloc::format!("Ambition set: {ambition}"));
 
         self.living_ambition = Some(ambition.clone());
+        push_ambition(&mut self.ambition_history, self.tick, &ambition);
 
         // Persist ambition to host disk via bridge (daily file)
         // Escape pipes and newlines for serial transport
@@ -686,7 +1888,7 @@ impl Supervisor {
 
         // Also persist to memory store for in-session search
         memory_store::store_with_timestamp(
-            &alloc::format!("Living Ambition: {}", ambition),
+            &alloc::format!("{AMBITION_MEMORY_PREFIX}{ambition}"),
             MemoryKind::Observation,
             "supervisor",
             self.tick,
@@ -694,12 +1896,8 @@ impl Supervisor {
         // Trigger a serial persist so it reaches host disk
         memory_store::persist_to_serial();
 
-        // Imprint all existing agents with the new ambition
-        for agent in self.agents.iter_mut() {
-            agent.imprint(&ambition);
-        }
-
-        // Broadcast the first heartbeat immediately
+        // Notify agents whose ambition changed, then broadcast the first
+        // heartbeat immediately
         self.pulse();
 
         println!();
@@ -708,6 +1906,41 @@ impl Supervisor {
         println!();
     }
     
+    /// Past ambitions, oldest first: (tick breathed, ambition). The last one
+    /// is the living ambition.
+    pub fn ambition_history(&self) -> &[(u64, String)] {
+        &self.ambition_history
+    }
+
+    /// Breathe in history entry `n` (1-based, as listed by `breathe --history`)
+    /// again. Returns the ambition, or None if there is no such entry.
+    pub fn restore_ambition(&mut self, n: usize) -> Option<String> {
+        let ambition = self.ambition_history.get(n.checked_sub(1)?)?.1.clone();
+        self.breathe(ambition.clone());
+        Some(ambition)
+    }
+
+    /// Rebuild the ambition history from the memory entries `breathe` stored,
+    /// so it survives reboots. Call once persisted memories are loaded.
+    pub fn load_ambition_history(&mut self) {
+        self.ambition_history = ambition_history_from(&memory_store::by_source("supervisor"));
+        serial_println!("[SUPERVISOR] Ambition history: {} entries", self.ambition_history.len());
+    }
+
+    /// Call `on_ambition_change` on every agent whose last-given ambition
+    /// differs from the living one
+    fn sync_ambition(&mut self) {
+        let Some(ambition) = self.living_ambition.clone() else {
+            return;
+        };
+        for (agent, known) in self.agents.iter_mut().zip(self.agent_ambitions.iter_mut()) {
+            if known.as_deref() != Some(ambition.as_str()) {
+                agent.on_ambition_change(known.as_deref(), &ambition);
+                *known = Some(ambition.clone());
+            }
+        }
+    }
+    
     /// Get the current living ambition
     pub fn get_ambition(&self) -> Option<&String> {
         self.living_ambition.as_ref()
@@ -717,6 +1950,117 @@ impl Supervisor {
     pub fn get_insights(&self) -> &[FeedbackType] {
         &self.constellation_of_insights
     }
+    
+    /// Tick each insight arrived, parallel to `get_insights()`
+    pub fn insight_ticks(&self) -> &[u64] {
+        &self.insight_ticks
+    }
+    
+    /// Serialize scalar state and each agent's `snapshot()` (one `key=value` per line)
+    ///
+    /// Insights are recorded as counts only; timers and message queues aren't saved.
+    pub fn snapshot(&self) -> String {
+        use memory_store::escape_field;
+        
+        let mut insights = [0usize; 4];
+        for insight in &self.constellation_of_insights {
+            insights[match insight {
+                FeedbackType::Spark { .. } => 0,
+                FeedbackType::Connection { .. } => 1,
+                FeedbackType::Resource { .. } => 2,
+                FeedbackType::Feeling { .. } => 3,
+            }] += 1;
+        }
+        
+        let mut out = String::from(SNAPSHOT_HEADER);
+        out.push('\n');
+        let fields = [
+            ("tick", alloc::format!("{}", self.tick)),
+            ("ambition", escape_field(self.living_ambition.as_deref().unwrap_or(""))),
+            ("heartbeat_counter", alloc::format!("{}", self.heartbeat_counter)),
+            ("serendipity_counter", alloc::format!("{}", self.serendipity_counter)),
+            ("journal_counter", alloc::format!("{}", self.journal_counter)),
+            ("outbox_cap", alloc::format!("{}", self.outbox_cap)),
+            ("memory_checkpoint_interval", alloc::format!("{}", self.memory_checkpoint_interval)),
+            ("max_agents", alloc::format!("{}", self.max_agents)),
+            ("insights", alloc::format!("{},{},{},{}", insights[0], insights[1], insights[2], insights[3])),
+        ];
+        for (key, value) in fields {
+            out.push_str(&alloc::format!("{key}={value}\n"));
+        }
+        for (agent, stats) in self.agents.iter().zip(self.route_stats.iter()) {
+            out.push_str(&alloc::format!("agent={}|{}|{}|{}\n",
+                escape_field(agent.name()), stats.delivered, stats.sent, escape_field(&agent.snapshot())));
+        }
+        out
+    }
+    
+    /// Reload state written by `snapshot()`. Agents are matched by name;
+    /// unknown keys and agents not running now are skipped.
+    ///
+    /// Returns the number of agents restored.
+    pub fn restore_from(&mut self, data: &str) -> Result<usize, &'static str> {
+        use memory_store::unescape_field;
+        
+        let mut lines = data.lines();
+        if lines.next().map(str::trim) != Some(SNAPSHOT_HEADER) {
+            return Err("not a supervisor snapshot");
+        }
+        
+        let old_tick = self.tick;
+        let mut restored = 0;
+        for line in lines {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let number = value.trim().parse::<u64>().ok();
+            match (key, number) {
+                ("tick", Some(n)) => self.tick = n,
+                ("ambition", _) => {
+                    let ambition = unescape_field(value);
+                    self.living_ambition = if ambition.is_empty() { None } else { Some(ambition) };
+                }
+                ("heartbeat_counter", Some(n)) => self.heartbeat_counter = n,
+                ("serendipity_counter", Some(n)) => self.serendipity_counter = n,
+                ("journal_counter", Some(n)) => self.journal_counter = n,
+                ("outbox_cap", Some(n)) => self.outbox_cap = n as usize,
+                ("memory_checkpoint_interval", Some(n)) => self.memory_checkpoint_interval = n,
+                ("max_agents", Some(n)) => {
+                    self.set_max_agents(n as usize);
+                }
+                ("agent", _) => {
+                    let parts: Vec<&str> = value.splitn(4, '|').collect();
+                    if parts.len() < 4 {
+                        continue;
+                    }
+                    let name = unescape_field(parts[0]);
+                    let Some(slot) = self.agents.iter().position(|a| a.name() == name) else {
+                        serial_println!("[SNAPSHOT] Skipping {}: not running", name);
+                        continue;
+                    };
+                    self.route_stats[slot] = RouteStats {
+                        delivered: parts[1].parse().unwrap_or(0),
+                        sent: parts[2].parse().unwrap_or(0),
+                        ..RouteStats::default()
+                    };
+                    self.agents[slot].restore(&unescape_field(parts[3]));
+                    restored += 1;
+                }
+                _ => {}
+            }
+        }
+        
+        // Keep pending timers the same distance away, and restart heartbeat
+        // tracking so the tick jump doesn't look like missed acks
+        for timer in self.timers.iter_mut() {
+            timer.0 = (timer.0 + self.tick).saturating_sub(old_tick);
+        }
+        self.heartbeat_acks.clear();
+        self.first_pulse_tick = None;
+        
+        serial_println!("[SNAPSHOT] Restored tick {} and {} agents", self.tick, restored);
+        Ok(restored)
+    }
 
     /// Check the ceremony required for a change to a file.
     /// Returns the TierCheck with proceed flag and required ceremony.
@@ -727,6 +2071,7 @@ impl Supervisor {
         if !result.proceed {
             serial_println!("[PROTECTION] BLOCKED: Agent '{}' — {} on '{}' ({})",
                 agent_name, result.ceremony, path, result.tier);
+            crate::event_log::record(&alloc::format!("Denied {agent_name} on {path} ({})", result.tier));
         } else if result.tier.requires_discussion() {
             serial_println!("[PROTECTION] NOTICE: Agent '{}' changing {} ({}) — {}",
                 agent_name, path, result.tier, result.ceremony);
@@ -734,6 +2079,78 @@ impl Supervisor {
         result
     }
 
+    /// Apply an agent's `FileOp` if its protection tier allows it.
+    /// Returns the ack: (applied, path or the reason it was refused).
+    fn apply_file_op(&self, from: AgentId, change: protection::ChangeKind, path: &str, content: Option<&str>) -> (bool, String) {
+        use crate::storage::filesystem;
+        use protection::ChangeKind;
+
+        let name = self.agents
+            .iter()
+            .find(|a| a.id() == from)
+            .map(|a| String::from(a.name()))
+            .unwrap_or_else(|| alloc::format!("agent-{}", from.0));
+        let check = self.check_tier(&name, path, change);
+        if !check.proceed {
+            return (false, alloc::format!("{path}: {}", check.ceremony));
+        }
+
+        let result = match (change, content) {
+            (ChangeKind::Create, None) if filesystem::dir_exists(path) => Ok(()),
+            (ChangeKind::Create, None) => filesystem::create_dir(path),
+            (ChangeKind::Create | ChangeKind::Modify, Some(text)) => filesystem::write_file_string(path, text),
+            _ => {
+                serial_println!("[PROTECTION] {} sent unsupported file op {:?} on '{}'", name, change, path);
+                return (false, alloc::format!("{path}: {} is not supported as a file op", change.description()));
+            }
+        };
+        match result {
+            Ok(()) => {
+                serial_println!("[PROTECTION] {} applied: {} '{}'", name, change.description(), path);
+                (true, String::from(path))
+            }
+            Err(e) => (false, alloc::format!("{path}: {e:?}")),
+        }
+    }
+
+    /// Start an A/B experiment for an agent's proposed prompt.
+    /// Returns the ack: (started, experiment number or the reason it was refused).
+    fn start_prompt_evolution(&mut self, from: AgentId, system_prompt: &str, hypothesis: &str) -> (bool, String) {
+        let Some(agent) = self.agents.iter().find(|a| a.id() == from) else {
+            return (false, String::from("unknown agent"));
+        };
+        let name = String::from(agent.name());
+        let Some(character_id) = agent.character_id() else {
+            return (false, alloc::format!("{name} has no Academy character to evolve"));
+        };
+        if let Some((_, last)) = self.evolution_proposals.iter().find(|(id, _)| *id == from) {
+            let wait = (last + PROMPT_EVOLUTION_COOLDOWN).saturating_sub(self.tick);
+            if wait > 0 {
+                serial_println!("[EVOLUTION] {} proposal refused: rate limited for {} more ticks", name, wait);
+                return (false, alloc::format!("rate limited: next proposal in {wait} ticks"));
+            }
+        }
+
+        let started = evolution::with_engine_mut(|engine| {
+            library::with_library_mut(|lib| {
+                let baseline_id = lib.get_active(character_id)?.id;
+                engine.start_experiment(lib, baseline_id, system_prompt, hypothesis, PROMPT_EVOLUTION_TRIALS)
+            }).flatten()
+        }).flatten();
+        let Some(experiment_id) = started else {
+            return (false, alloc::format!("no active prompt for character {character_id}"));
+        };
+
+        match self.evolution_proposals.iter_mut().find(|(id, _)| *id == from) {
+            Some(entry) => entry.1 = self.tick,
+            None => self.evolution_proposals.push((from, self.tick)),
+        }
+        serial_println!("[EVOLUTION] {} proposed experiment #{} for character {}: '{}'",
+            name, experiment_id, character_id, hypothesis);
+        crate::event_log::record(&alloc::format!("{name} started prompt experiment #{experiment_id}"));
+        (true, alloc::format!("experiment #{experiment_id}"))
+    }
+
     /// Print protection tier status for all registered paths.
     pub fn print_protection_status(&self) {
         protection::print_tier_summary();
@@ -760,3 +2177,905 @@ impl Default for Supervisor {
     }
 }
 
+/// Append an ambition to the history, unless it repeats the latest one,
+/// dropping the oldest beyond `MAX_AMBITION_HISTORY`
+fn push_ambition(history: &mut Vec<(u64, String)>, tick: u64, ambition: &str) {
+    if history.last().is_some_and(|(_, last)| last == ambition) {
+        return;
+    }
+    history.push((tick, String::from(ambition)));
+    if history.len() > MAX_AMBITION_HISTORY {
+        history.remove(0);
+    }
+}
+
+/// The ambition history recorded in `breathe`'s memory entries, oldest first
+fn ambition_history_from<'a>(entries: impl IntoIterator<Item = &'a MemoryEntry>) -> Vec<(u64, String)> {
+    let mut entries: Vec<&MemoryEntry> = entries
+        .into_iter()
+        .filter(|e| e.content.starts_with(AMBITION_MEMORY_PREFIX))
+        .collect();
+    // Ticks restart each boot, IDs don't
+    entries.sort_by_key(|e| e.id);
+
+    let mut history = Vec::new();
+    for entry in entries {
+        push_ambition(&mut history, entry.timestamp, &entry.content[AMBITION_MEMORY_PREFIX.len()..]);
+    }
+    history
+}
+
+/// Record one agent tick that took `elapsed` PIT ticks. Returns true if it
+/// was slow; the agent is paused on reaching `SLOW_TICK_PAUSE_LIMIT` slow ticks.
+fn record_tick_duration(timing: &mut TickTiming, elapsed: u64) -> bool {
+    timing.slowest = timing.slowest.max(elapsed);
+    if elapsed < SLOW_TICK_PIT_TICKS {
+        return false;
+    }
+    timing.slow_ticks += 1;
+    if timing.slow_ticks >= SLOW_TICK_PAUSE_LIMIT {
+        timing.paused = true;
+    }
+    true
+}
+
+/// Count one message between the agents in slots `a` and `b` (either direction)
+fn record_link(links: &mut Vec<(usize, usize, u64)>, a: usize, b: usize) {
+    let key = (a.min(b), a.max(b));
+    match links.iter_mut().find(|(x, y, _)| (*x, *y) == key) {
+        Some(link) => link.2 += 1,
+        None => links.push((key.0, key.1, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::{ActivityLog, AgentState};
+    use alloc::sync::Arc;
+    use spin::Mutex;
+
+    /// Minimal agent that records the tick at which each Timer arrived, and
+    /// reschedules "every <n>" timers through its context
+    #[derive(Debug)]
+    struct TimerProbe {
+        id: AgentId,
+        fired_at: Arc<Mutex<Vec<u64>>>,
+        activity: ActivityLog,
+    }
+
+    impl Agent for TimerProbe {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Probe" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for payload in ctx.fired_timers() {
+                self.fired_at.lock().push(ctx.tick);
+                self.activity.record(&alloc::format!("timer '{payload}'"));
+                if let Some(every) = payload.strip_prefix("every ").and_then(|n| n.parse().ok()) {
+                    ctx.schedule(every, &payload);
+                }
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+        fn recent_activity(&self) -> Vec<String> { self.activity.to_vec() }
+    }
+
+    #[test]
+    fn timer_fires_exactly_at_deadline() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let fired_at = Arc::new(Mutex::new(Vec::new()));
+        sup.register(Box::new(TimerProbe { id, fired_at: fired_at.clone(), activity: ActivityLog::default() })).unwrap();
+
+        let start = sup.current_tick();
+        sup.schedule(id, 5, String::from("wake"));
+
+        for _ in 0..4 {
+            sup.tick();
+        }
+        assert!(fired_at.lock().is_empty(), "timer must not fire before its deadline");
+        assert_eq!(sup.pending_timers(), 1);
+
+        sup.tick();
+        assert_eq!(*fired_at.lock(), alloc::vec![start + 5]);
+        assert_eq!(sup.pending_timers(), 0);
+    }
+
+    #[test]
+    fn agent_reschedules_its_own_timer_from_the_payload() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let fired_at = Arc::new(Mutex::new(Vec::new()));
+        sup.register(Box::new(TimerProbe { id, fired_at: fired_at.clone(), activity: ActivityLog::default() })).unwrap();
+
+        let start = sup.current_tick();
+        sup.schedule(id, 2, String::from("every 3"));
+        for _ in 0..8 {
+            sup.tick();
+        }
+        assert_eq!(*fired_at.lock(), alloc::vec![start + 2, start + 5, start + 8]);
+        assert_eq!(sup.pending_timers(), 1);
+    }
+
+    /// Agent that sends a fixed number of messages every tick
+    #[derive(Debug)]
+    struct Flooder {
+        id: AgentId,
+        per_tick: usize,
+    }
+
+    impl Agent for Flooder {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Flooder" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for _ in 0..self.per_tick {
+                ctx.outbox.push(Message::new(self.id, Some(self.id), MessageKind::Text(String::from("spam"))));
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn outbox_excess_is_dead_lettered() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        sup.register(Box::new(Flooder { id, per_tick: DEFAULT_OUTBOX_CAP + 8 })).unwrap();
+
+        sup.tick();
+        assert_eq!(sup.overflow_count(id), 8);
+        assert_eq!(sup.dead_letters().len(), 8);
+
+        sup.set_outbox_cap(DEFAULT_OUTBOX_CAP + 8);
+        sup.tick();
+        assert_eq!(sup.overflow_count(id), 8, "no new overflow once the cap fits");
+    }
+
+    /// Agent that asks a peer one query and records the answer
+    #[derive(Debug)]
+    struct Asker {
+        id: AgentId,
+        peer: AgentId,
+        asked: Option<u64>,
+        answer: Arc<Mutex<Option<(u64, Vec<String>)>>>,
+    }
+
+    impl Agent for Asker {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Asker" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::QueryResult { correlation_id, rows, .. } = &msg.kind {
+                    *self.answer.lock() = Some((*correlation_id, rows.clone()));
+                }
+            }
+            if self.asked.is_none() {
+                let query = Message::new(self.id, Some(self.peer), MessageKind::Query {
+                    topic: String::from("colors"),
+                    args: Vec::new(),
+                });
+                self.asked = Some(query.id);
+                ctx.outbox.push(query);
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    /// Agent that serves the "colors" topic
+    #[derive(Debug)]
+    struct Oracle {
+        id: AgentId,
+    }
+
+    impl Agent for Oracle {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Oracle" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, _ctx: &mut AgentContext) -> AgentState { AgentState::Ready }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+        fn handle_query(&self, topic: &str, _args: &[String]) -> Option<Vec<String>> {
+            (topic == "colors").then(|| alloc::vec![String::from("red"), String::from("blue")])
+        }
+    }
+
+    #[test]
+    fn query_is_answered_with_correlation_id() {
+        let mut sup = Supervisor::new();
+        let asker_id = sup.next_id();
+        let oracle_id = sup.next_id();
+        let answer = Arc::new(Mutex::new(None));
+        sup.register(Box::new(Asker { id: asker_id, peer: oracle_id, asked: None, answer: answer.clone() })).unwrap();
+        sup.register(Box::new(Oracle { id: oracle_id })).unwrap();
+
+        for _ in 0..3 {
+            sup.tick();
+        }
+        let (correlation_id, rows) = answer.lock().clone().expect("query should be answered");
+        assert!(correlation_id > 0);
+        assert_eq!(rows, alloc::vec![String::from("red"), String::from("blue")]);
+        assert_eq!(sup.query("oracle", "colors", &[]).flatten().map(|r| r.len()), Some(2));
+        assert_eq!(sup.query("oracle", "weather", &[]), Some(None));
+
+        // The supervisor's own queries are routed too and matched on return
+        assert_eq!(sup.send_query("nobody", "colors", Vec::new()), None);
+        let id = sup.send_query("oracle", "colors", Vec::new()).expect("oracle is running");
+        assert_eq!(sup.pending_queries.len(), 1);
+        sup.tick();
+        sup.tick();
+        assert!(sup.pending_queries.is_empty());
+        assert!(id > 0);
+    }
+
+    #[test]
+    fn register_refuses_past_agent_limit() {
+        let mut sup = Supervisor::new();
+        sup.set_max_agents(3);
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let id = sup.try_next_id().expect("below the limit");
+            sup.register(Box::new(Flooder { id, per_tick: 0 })).unwrap();
+            ids.push(id);
+        }
+
+        assert!(sup.try_next_id().is_none());
+        let extra = sup.next_id();
+        assert_eq!(
+            sup.register(Box::new(Flooder { id: extra, per_tick: 0 })),
+            Err(RegisterError::AtCapacity { max: 3 })
+        );
+        assert_eq!(sup.agent_count(), 3);
+        assert_eq!(sup.route_stats().len(), 3);
+        let registered: Vec<AgentId> = sup.agents.iter().map(|a| a.id()).collect();
+        assert_eq!(registered, ids);
+
+        assert_eq!(sup.set_max_agents(1), 3, "limit can't drop below the agent count");
+    }
+
+    fn at(day: u8, hour: u8) -> crate::rtc::DateTime {
+        crate::rtc::DateTime { year: 2026, month: 3, day, hour, minute: 0, second: 0 }
+    }
+
+    #[test]
+    fn daily_phases_fire_once_per_day() {
+        let mut sched = DailyScheduler::new();
+
+        // Boot at 12:30: morning already passed and is skipped, midday fires
+        assert_eq!(sched.due(&at(1, 12)), alloc::vec![DailyPhase::Midday]);
+        assert!(sched.has_fired(DailyPhase::Morning));
+        assert!(sched.due(&at(1, 13)).is_empty());
+        assert_eq!(sched.due(&at(1, 18)), alloc::vec![DailyPhase::EndOfDay]);
+        assert!(sched.due(&at(1, 19)).is_empty());
+
+        // Next day starts fresh
+        assert!(sched.due(&at(2, 7)).is_empty());
+        assert_eq!(sched.due(&at(2, 8)), alloc::vec![DailyPhase::Morning]);
+        assert!(sched.due(&at(2, 9)).is_empty());
+    }
+
+    /// Agent whose only state is a counter it snapshots
+    #[derive(Debug)]
+    struct Counter {
+        id: AgentId,
+        count: u64,
+    }
+
+    impl Agent for Counter {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Counter" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, _ctx: &mut AgentContext) -> AgentState { AgentState::Ready }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+        fn snapshot(&self) -> String { alloc::format!("{}", self.count) }
+        fn restore(&mut self, data: &str) { self.count = data.parse().unwrap_or(0); }
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_restore() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        sup.register(Box::new(Counter { id, count: 7 })).unwrap();
+        sup.living_ambition = Some(String::from("ship it | today"));
+        for _ in 0..3 {
+            sup.tick();
+        }
+        let snap = sup.snapshot();
+
+        let mut fresh = Supervisor::new();
+        let id = fresh.next_id();
+        fresh.register(Box::new(Counter { id, count: 0 })).unwrap();
+        assert_eq!(fresh.restore_from(&snap), Ok(1));
+        assert_eq!(fresh.current_tick(), 3);
+        assert_eq!(fresh.get_ambition().map(String::as_str), Some("ship it | today"));
+        assert_eq!(fresh.snapshot(), snap);
+        assert!(fresh.restore_from("garbage").is_err());
+    }
+
+    /// Agent that only wants Ping broadcasts and records what it receives
+    #[derive(Debug)]
+    struct PingOnly {
+        id: AgentId,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Agent for PingOnly {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "PingOnly" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                let kind = msg.kind.filter_bit().names()[0];
+                self.received.lock().push(String::from(kind));
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+        fn subscriptions(&self) -> crate::agents::message::MessageFilter {
+            crate::agents::message::MessageFilter::PING
+        }
+    }
+
+    #[test]
+    fn unsubscribed_broadcasts_are_not_delivered() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        sup.register(Box::new(PingOnly { id, received: received.clone() })).unwrap();
+        received.lock().clear();
+
+        sup.broadcast(MessageKind::Text(String::from("noise")));
+        sup.ping_all();
+        sup.send(Message::new(AgentId::new(0), Some(id), MessageKind::Text(String::from("direct"))));
+        sup.tick();
+
+        assert_eq!(*received.lock(), alloc::vec![String::from("Ping"), String::from("Text")]);
+    }
+
+    /// Agent that answers every ping with a pong
+    #[derive(Debug)]
+    struct Ponger {
+        id: AgentId,
+    }
+
+    impl Agent for Ponger {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Ponger" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::Ping { sent_tick } = msg.kind {
+                    ctx.outbox.push(Message::pong(self.id, msg.from, sent_tick));
+                }
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn directed_ping_measures_round_trip() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        sup.register(Box::new(Ponger { id })).unwrap();
+
+        assert!(!sup.ping_agent("nobody"));
+        assert!(sup.ping_agent("ponger"));
+        assert_eq!(sup.ping_latency(id), None);
+
+        // Ping delivered on the first tick, pong collected on the second
+        sup.tick();
+        sup.tick();
+        assert_eq!(sup.ping_latency(id), Some(2));
+    }
+
+    #[test]
+    fn idle_ticks_reset_on_activity() {
+        let mut sup = Supervisor::new();
+        sup.tick();
+        for _ in 0..LOW_POWER_AFTER_IDLE_TICKS {
+            assert!(sup.tick().is_idle());
+        }
+        assert!(sup.is_low_power());
+
+        sup.schedule(AgentId::new(99), 1, String::from("wake up"));
+        let activity = sup.tick();
+        assert_eq!(activity.timers_fired, 1);
+        assert_eq!(sup.idle_ticks(), 0);
+
+        sup.tick();
+        sup.wake();
+        assert!(!sup.is_low_power());
+    }
+
+    /// Agent that records every `Response` it gets
+    #[derive(Debug)]
+    struct Filer {
+        id: AgentId,
+        acks: Arc<Mutex<Vec<(bool, String)>>>,
+    }
+
+    impl Agent for Filer {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Filer" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::Response { success, data } = &msg.kind {
+                    self.acks.lock().push((*success, data.clone()));
+                }
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn sandbox_file_ops_are_refused() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let acks = Arc::new(Mutex::new(Vec::new()));
+        sup.register(Box::new(Filer { id, acks: acks.clone() })).unwrap();
+
+        sup.send(Message::new(id, None, MessageKind::FileOp {
+            change: protection::ChangeKind::Modify,
+            path: String::from("/memory/secrets/token.key"),
+            content: Some(String::from("leaked")),
+        }));
+        sup.tick();
+        sup.tick();
+
+        let acks = acks.lock();
+        assert_eq!(acks.len(), 1);
+        assert!(!acks[0].0);
+        assert!(acks[0].1.starts_with("/memory/secrets/token.key"));
+    }
+
+    /// Agent that returns a scripted sequence of states from `tick()`
+    #[derive(Debug)]
+    struct Scripted {
+        id: AgentId,
+        script: Vec<AgentState>,
+    }
+
+    impl Agent for Scripted {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Scripted" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, _ctx: &mut AgentContext) -> AgentState {
+            if self.script.is_empty() { AgentState::Ready } else { self.script.remove(0) }
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn illegal_state_transitions_are_ignored() {
+        assert!(AgentState::Ready.can_transition_to(AgentState::Paused));
+        assert!(AgentState::Error.can_transition_to(AgentState::Initializing));
+        assert!(!AgentState::Running.can_transition_to(AgentState::Initializing));
+        assert!(!AgentState::ShuttingDown.can_transition_to(AgentState::Running));
+
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let script = alloc::vec![AgentState::Paused, AgentState::ShuttingDown, AgentState::Running];
+        sup.register(Box::new(Scripted { id, script })).unwrap();
+
+        sup.tick();
+        assert_eq!(sup.agent_overview()[0].1, AgentState::Paused);
+        sup.tick();
+        sup.tick();
+        assert_eq!(sup.agent_overview()[0].1, AgentState::ShuttingDown);
+    }
+
+    #[test]
+    fn feelings_fade_and_are_pruned() {
+        use crate::agents::message::{decayed_intensity, FEELING_DECAY_INTERVAL};
+        assert_eq!(decayed_intensity(30, FEELING_DECAY_INTERVAL - 1), 30);
+        assert_eq!(decayed_intensity(30, FEELING_DECAY_INTERVAL), 20);
+        assert_eq!(decayed_intensity(30, u64::MAX), 0);
+
+        let mut sup = Supervisor::new();
+        sup.send(Message::new(AgentId::OPERATOR, None, MessageKind::Feedback(FeedbackType::Feeling {
+            tag: String::from("relieved"),
+            intensity: 10,
+        })));
+        sup.send(Message::new(AgentId::OPERATOR, None, MessageKind::Feedback(FeedbackType::Spark {
+            content: String::from("idea"),
+            context: String::from("test"),
+        })));
+        sup.tick();
+        assert_eq!(sup.get_insights().len(), 2);
+
+        for _ in 0..FEELING_DECAY_INTERVAL {
+            sup.tick();
+        }
+        assert_eq!(sup.get_insights().len(), 1);
+        assert!(matches!(sup.get_insights()[0], FeedbackType::Spark { .. }));
+    }
+
+    #[test]
+    fn activity_log_keeps_the_most_recent_actions() {
+        let mut log = crate::agents::ActivityLog::default();
+        for n in 0..25 {
+            log.record(&alloc::format!("action {n}"));
+        }
+        let kept = log.to_vec();
+        assert_eq!(kept.len(), 20);
+        assert!(kept[0].ends_with("action 5"));
+        assert!(kept[19].ends_with("action 24"));
+    }
+
+    /// Agent that proposes two prompt evolutions on its first tick
+    #[derive(Debug)]
+    struct Evolver {
+        id: AgentId,
+        proposed: bool,
+        acks: Arc<Mutex<Vec<(bool, String)>>>,
+    }
+
+    impl Agent for Evolver {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { "Evolver" }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::Response { success, data } = &msg.kind {
+                    self.acks.lock().push((*success, data.clone()));
+                }
+            }
+            if !self.proposed {
+                self.proposed = true;
+                ctx.propose_prompt_evolution("Test twice.", "evolver hypothesis one");
+                ctx.propose_prompt_evolution("Test thrice.", "evolver hypothesis two");
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+        fn character_id(&self) -> Option<u32> { Some(character_ids::THOMAS) }
+    }
+
+    #[test]
+    fn prompt_evolution_proposals_are_rate_limited() {
+        let mut sup = Supervisor::new();
+        let id = sup.next_id();
+        let acks = Arc::new(Mutex::new(Vec::new()));
+        sup.register(Box::new(Evolver { id, proposed: false, acks: acks.clone() })).unwrap();
+
+        // Tick 1 proposes, tick 2 answers, tick 3 routes the acks back
+        sup.tick();
+        sup.tick();
+        sup.tick();
+
+        let acks = acks.lock();
+        assert_eq!(acks.len(), 2);
+        assert!(acks[0].0, "first proposal should start an experiment");
+        assert!(!acks[1].0);
+        assert!(acks[1].1.starts_with("rate limited"));
+        let started = evolution::with_engine(|engine| {
+            engine.experiments().iter().any(|e| e.hypothesis == "evolver hypothesis one")
+        });
+        assert_eq!(started, Some(true));
+    }
+
+    #[test]
+    fn announcements_stay_out_of_insights_and_memory() {
+        let mut sup = Supervisor::new();
+        let stored_before = memory_store::stats().entry_count;
+        sup.send(Message::new(AgentId::new(7), None, MessageKind::Announce {
+            agent_name: String::from("Thomas"),
+            text: String::from("All 3 tests passed"),
+            level: message::AnnounceLevel::Info,
+        }));
+        sup.tick();
+
+        assert!(sup.get_insights().is_empty());
+        assert_eq!(memory_store::stats().entry_count, stored_before);
+        assert_eq!(sup.announcements().len(), 1);
+        assert_eq!(sup.announcements()[0].text, "All 3 tests passed");
+    }
+
+    #[test]
+    fn tick_n_matches_repeated_tick() {
+        let run = |batched: bool| {
+            let mut sup = Supervisor::new();
+            let id = sup.next_id();
+            let fired_at = Arc::new(Mutex::new(Vec::new()));
+            sup.register(Box::new(TimerProbe { id, fired_at: fired_at.clone(), activity: ActivityLog::default() })).unwrap();
+            sup.schedule(id, 2, String::from("early"));
+            sup.schedule(id, 4, String::from("late"));
+            sup.schedule(id, 9, String::from("after the batch"));
+            sup.ping_all();
+
+            let activity = if batched {
+                sup.tick_n(5)
+            } else {
+                let mut total = TickActivity::default();
+                for _ in 0..5 {
+                    total += sup.tick();
+                }
+                total
+            };
+            let fired = fired_at.lock().clone();
+            let log = sup.agents[0].recent_activity();
+            (activity, fired, log, sup.pending_timers(), sup.idle_ticks(), sup.snapshot())
+        };
+
+        let batched = run(true);
+        assert_eq!(batched, run(false));
+        assert_eq!(batched.1, [2, 4]);
+        assert_eq!(batched.2, ["[t=2] timer 'early'", "[t=4] timer 'late'"]);
+        assert_eq!(batched.3, 1);
+    }
+
+    /// Agent that records each Telegram text it receives and whether it was addressed to it
+    #[derive(Debug)]
+    struct Listener {
+        id: AgentId,
+        name: &'static str,
+        heard: Arc<Mutex<Vec<(bool, String)>>>,
+    }
+
+    impl Agent for Listener {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { self.name }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::Text(text) = &msg.kind {
+                    if let Some(body) = text.strip_prefix("TELEGRAM: ") {
+                        self.heard.lock().push((msg.to == Some(self.id), String::from(body)));
+                    }
+                }
+            }
+            AgentState::Ready
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn telegram_wake_word_directs_message_to_one_agent() {
+        let mut sup = Supervisor::new();
+        let alpha_heard = Arc::new(Mutex::new(Vec::new()));
+        let beta_heard = Arc::new(Mutex::new(Vec::new()));
+        let id = sup.next_id();
+        sup.register(Box::new(Listener { id, name: "Alpha", heard: alpha_heard.clone() })).unwrap();
+        let id = sup.next_id();
+        sup.register(Box::new(Listener { id, name: "Beta", heard: beta_heard.clone() })).unwrap();
+
+        assert_eq!(sup.route_telegram("@beta, run the tests"), TelegramRoute::Directed(String::from("Beta")));
+        assert_eq!(sup.route_telegram("@gamma hello"), TelegramRoute::Unknown(String::from("gamma")));
+        sup.tick();
+        assert!(alpha_heard.lock().is_empty());
+        assert_eq!(*beta_heard.lock(), [(true, String::from("run the tests"))]);
+
+        assert_eq!(sup.route_telegram("hello everyone"), TelegramRoute::Broadcast);
+        sup.tick();
+        assert_eq!(*alpha_heard.lock(), [(false, String::from("hello everyone"))]);
+        assert_eq!(beta_heard.lock().len(), 2);
+    }
+
+    #[test]
+    fn autosave_fires_once_per_real_interval() {
+        let mut sup = Supervisor::new();
+        sup.set_autosave_minutes(2);
+        assert!(!sup.autosave_due(119_999));
+        assert!(sup.autosave_due(120_000));
+        assert!(!sup.autosave_due(200_000));
+        assert!(sup.autosave_due(240_000));
+
+        sup.set_autosave_minutes(0);
+        assert!(!sup.autosave_due(10_000_000));
+    }
+
+    #[test]
+    fn agent_links_count_messages_between_agents() {
+        let mut sup = Supervisor::new();
+        let alpha = sup.next_id();
+        sup.register(Box::new(Listener { id: alpha, name: "Alpha", heard: Arc::new(Mutex::new(Vec::new())) })).unwrap();
+        let beta = sup.next_id();
+        sup.register(Box::new(Listener { id: beta, name: "Beta", heard: Arc::new(Mutex::new(Vec::new())) })).unwrap();
+
+        sup.send(Message::new(alpha, Some(beta), MessageKind::Text(String::from("hi"))));
+        sup.send(Message::new(beta, Some(alpha), MessageKind::Text(String::from("hello"))));
+        sup.broadcast(MessageKind::Text(String::from("from the supervisor")));
+        sup.tick();
+        assert_eq!(sup.agent_links(), [(0, 1, 2)]);
+
+        sup.reset_route_stats();
+        assert!(sup.agent_links().is_empty());
+    }
+
+    #[test]
+    fn kind_counts_tally_routed_messages_by_kind() {
+        let mut sup = Supervisor::new();
+        let alpha = sup.next_id();
+        sup.register(Box::new(Listener { id: alpha, name: "Alpha", heard: Arc::new(Mutex::new(Vec::new())) })).unwrap();
+
+        sup.send(Message::new(AgentId::OPERATOR, Some(alpha), MessageKind::Text(String::from("hi"))));
+        sup.send(Message::new(AgentId::OPERATOR, Some(alpha), MessageKind::Text(String::from("again"))));
+        sup.send(Message::new(AgentId::OPERATOR, Some(alpha), MessageKind::Ping { sent_tick: 1 }));
+        sup.tick();
+        assert_eq!(sup.kind_counts().get("Text"), Some(&2));
+        assert_eq!(sup.kind_counts().get("Ping"), Some(&1));
+        assert_eq!(MessageKind::Ping { sent_tick: 1 }.kind_name(), "Ping");
+
+        sup.reset_kind_counts();
+        assert!(sup.kind_counts().is_empty());
+    }
+
+    #[test]
+    fn watchdog_pauses_an_agent_after_repeated_slow_ticks() {
+        let mut timing = TickTiming::default();
+        assert!(!record_tick_duration(&mut timing, 1));
+        for _ in 1..SLOW_TICK_PAUSE_LIMIT {
+            assert!(record_tick_duration(&mut timing, SLOW_TICK_PIT_TICKS));
+            assert!(!timing.paused);
+        }
+        assert!(record_tick_duration(&mut timing, SLOW_TICK_PIT_TICKS + 5));
+        assert!(timing.paused);
+        assert_eq!(timing.slowest, SLOW_TICK_PIT_TICKS + 5);
+
+        // A paused agent gets no ticks until resumed, and its mail waits
+        let mut sup = Supervisor::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let id = sup.next_id();
+        sup.register(Box::new(Winder { id, name: "A", peer: None, log: log.clone() })).unwrap();
+        sup.tick_timings[0] = timing;
+        sup.send(Message::new(AgentId::OPERATOR, Some(id), MessageKind::Text(String::from("hello"))));
+        sup.tick();
+        assert!(log.lock().is_empty());
+        assert_eq!(sup.resume_agent("b"), None);
+        assert_eq!(sup.resume_agent("a").as_deref(), Some("A"));
+        assert_eq!(sup.resume_agent("a"), None);
+        sup.tick();
+        assert_eq!(*log.lock(), ["A: heard hello", "A: tick"]);
+
+        // Held mail is capped, keeping the newest
+        log.lock().clear();
+        sup.tick_timings[0].paused = true;
+        for n in 0..MAX_HELD_MAIL + 2 {
+            sup.send(Message::new(AgentId::OPERATOR, Some(id), MessageKind::Text(alloc::format!("{n}"))));
+        }
+        sup.tick();
+        assert_eq!(sup.held_mail[0].len(), MAX_HELD_MAIL);
+        assert!(matches!(&sup.held_mail[0][0].kind, MessageKind::Text(text) if text == "2"));
+        assert_eq!(sup.route_stats[0].dropped, 2);
+
+        // Shutdown reaches a paused agent
+        sup.send(Message::new(sup.id, Some(id), MessageKind::Shutdown { reason: String::from("test") }));
+        sup.tick();
+        assert_eq!(*log.lock(), ["A: wind down (test)"]);
+    }
+
+    /// Agent that logs its ticks and the texts it hears, and says goodbye
+    /// to `peer` when told to shut down
+    #[derive(Debug)]
+    struct Winder {
+        id: AgentId,
+        name: &'static str,
+        peer: Option<AgentId>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Agent for Winder {
+        fn id(&self) -> AgentId { self.id }
+        fn name(&self) -> &str { self.name }
+        fn state(&self) -> AgentState { AgentState::Ready }
+        fn init(&mut self) {}
+        fn tick(&mut self, ctx: &mut AgentContext) -> AgentState {
+            for msg in ctx.inbox.iter() {
+                if let MessageKind::Text(text) = &msg.kind {
+                    self.log.lock().push(alloc::format!("{}: heard {}", self.name, text));
+                }
+            }
+            self.log.lock().push(alloc::format!("{}: tick", self.name));
+            AgentState::Ready
+        }
+        fn handle_shutdown(&mut self, ctx: &mut AgentContext) {
+            self.log.lock().push(alloc::format!("{}: wind down ({})", self.name, ctx.shutdown_reason().unwrap()));
+            if let Some(peer) = self.peer {
+                ctx.outbox.push(Message::new(self.id, Some(peer), MessageKind::Text(String::from("goodbye"))));
+            }
+        }
+        fn receive(&mut self, _msg: &Message) {}
+        fn shutdown(&mut self) {}
+    }
+
+    #[test]
+    fn shutdown_message_gets_one_wind_down_tick_whose_outbox_is_routed() {
+        let mut sup = Supervisor::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let a = sup.next_id();
+        let b = sup.next_id();
+        sup.register(Box::new(Winder { id: a, name: "A", peer: Some(b), log: log.clone() })).unwrap();
+        sup.register(Box::new(Winder { id: b, name: "B", peer: None, log: log.clone() })).unwrap();
+
+        let reason = String::from("test");
+        sup.send(Message::new(sup.id, Some(a), MessageKind::Shutdown { reason }));
+        sup.tick();
+        sup.tick();
+        assert_eq!(*log.lock(), ["A: wind down (test)", "B: tick", "B: heard goodbye", "B: tick"]);
+        assert_eq!(sup.agent_overview()[0].1, AgentState::ShuttingDown);
+    }
+
+    #[test]
+    fn agents_tick_in_id_order_however_they_are_stored() {
+        let mut sup = Supervisor::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (a, b, c) = (sup.next_id(), sup.next_id(), sup.next_id());
+        for (id, name) in [(c, "C"), (a, "A"), (b, "B")] {
+            sup.register(Box::new(Winder { id, name, peer: None, log: log.clone() })).unwrap();
+        }
+
+        sup.tick();
+        assert_eq!(*log.lock(), ["A: tick", "B: tick", "C: tick"]);
+
+        // Rearrange the backing vec (and its parallel vecs) as a scheduler might
+        sup.agents.swap(0, 2);
+        sup.route_stats.swap(0, 2);
+        sup.tick_timings.swap(0, 2);
+        sup.agent_states.swap(0, 2);
+        sup.agent_ambitions.swap(0, 2);
+        log.lock().clear();
+        sup.tick();
+        assert_eq!(*log.lock(), ["A: tick", "B: tick", "C: tick"]);
+    }
+
+    #[test]
+    fn ambition_history_skips_repeats_and_drops_oldest() {
+        let mut history = Vec::new();
+        push_ambition(&mut history, 1, "build graphics");
+        push_ambition(&mut history, 2, "build graphics");
+        push_ambition(&mut history, 3, "rest");
+        assert_eq!(history, [(1, String::from("build graphics")), (3, String::from("rest"))]);
+
+        for tick in 0..MAX_AMBITION_HISTORY as u64 {
+            push_ambition(&mut history, 10 + tick, &alloc::format!("ambition {tick}"));
+        }
+        assert_eq!(history.len(), MAX_AMBITION_HISTORY);
+        assert_eq!(history[0].0, 10);
+
+        let mut sup = Supervisor::new();
+        assert_eq!(sup.restore_ambition(0), None);
+        assert_eq!(sup.restore_ambition(1), None);
+        assert!(sup.get_ambition().is_none());
+    }
+
+    #[test]
+    fn ambition_history_rebuilds_from_stored_entries_in_id_order() {
+        let mut store = memory_store::MemoryStore::new(16);
+        store.store_with_timestamp("Living Ambition: ship the kernel", MemoryKind::Observation, "supervisor", 900);
+        store.store_with_timestamp("Checkpoint reached", MemoryKind::Observation, "supervisor", 950);
+        // After a reboot ticks start over, so a later entry can have a smaller tick
+        store.store_with_timestamp("Living Ambition: rest", MemoryKind::Observation, "supervisor", 40);
+        store.store_with_timestamp("Living Ambition: rest", MemoryKind::Observation, "supervisor", 60);
+
+        let history = ambition_history_from(store.by_source("supervisor"));
+        assert_eq!(history, [(900, String::from("ship the kernel")), (40, String::from("rest"))]);
+    }
+}
@@ -0,0 +1,213 @@
+//! CPU Feature Detection
+//!
+//! Reads CPUID once (on first use) for the vendor string, the brand string
+//! and the feature bits Genesis cares about, and logs them at boot. The
+//! strings are heap-allocated, so the first query must come after the heap
+//! is up.
+//!
+//! CPUID itself always exists on x86_64, but a leaf above the highest one
+//! the CPU reports returns unrelated data, so every leaf is checked against
+//! the reported maximum first; features on an unsupported leaf read as
+//! absent.
+//!
+//! Under QEMU the CPU vendor is whatever CPU model is emulated
+//! ("GenuineIntel", "AuthenticAMD"); the hypervisor leaf (0x4000_0000)
+//! names the emulator itself: "TCGTCGTCGTCG" for plain TCG emulation,
+//! "KVMKVMKVM" under KVM.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::arch::x86_64::{CpuidResult, __cpuid, __cpuid_count};
+use spin::Lazy;
+
+/// Highest extended leaf is read from here; brand string lives above it
+const EXTENDED_BASE: u32 = 0x8000_0000;
+
+/// Hypervisor vendor leaf (valid only when `Feature::Hypervisor` is set)
+const HYPERVISOR_LEAF: u32 = 0x4000_0000;
+
+/// A CPU feature Genesis can check for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    Fpu,
+    Tsc,
+    Apic,
+    Sse,
+    Sse2,
+    Sse3,
+    Ssse3,
+    Sse41,
+    Sse42,
+    Avx,
+    Avx2,
+    X2Apic,
+    Rdrand,
+    Rdseed,
+    Hypervisor,
+}
+
+/// Register of a CPUID result a feature bit lives in
+#[derive(Debug, Clone, Copy)]
+enum Reg {
+    Ebx,
+    Ecx,
+    Edx,
+}
+
+impl Feature {
+    /// Every feature, in display order
+    pub const ALL: [Feature; 15] = [
+        Feature::Fpu, Feature::Tsc, Feature::Apic, Feature::X2Apic,
+        Feature::Sse, Feature::Sse2, Feature::Sse3, Feature::Ssse3,
+        Feature::Sse41, Feature::Sse42, Feature::Avx, Feature::Avx2,
+        Feature::Rdrand, Feature::Rdseed, Feature::Hypervisor,
+    ];
+
+    /// Lowercase name, as in /proc/cpuinfo
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::Fpu => "fpu",
+            Feature::Tsc => "tsc",
+            Feature::Apic => "apic",
+            Feature::Sse => "sse",
+            Feature::Sse2 => "sse2",
+            Feature::Sse3 => "sse3",
+            Feature::Ssse3 => "ssse3",
+            Feature::Sse41 => "sse4_1",
+            Feature::Sse42 => "sse4_2",
+            Feature::Avx => "avx",
+            Feature::Avx2 => "avx2",
+            Feature::X2Apic => "x2apic",
+            Feature::Rdrand => "rdrand",
+            Feature::Rdseed => "rdseed",
+            Feature::Hypervisor => "hypervisor",
+        }
+    }
+
+    /// (leaf, register, bit) reporting this feature
+    fn location(self) -> (u32, Reg, u32) {
+        match self {
+            Feature::Fpu => (1, Reg::Edx, 0),
+            Feature::Tsc => (1, Reg::Edx, 4),
+            Feature::Apic => (1, Reg::Edx, 9),
+            Feature::Sse => (1, Reg::Edx, 25),
+            Feature::Sse2 => (1, Reg::Edx, 26),
+            Feature::Sse3 => (1, Reg::Ecx, 0),
+            Feature::Ssse3 => (1, Reg::Ecx, 9),
+            Feature::Sse41 => (1, Reg::Ecx, 19),
+            Feature::Sse42 => (1, Reg::Ecx, 20),
+            Feature::X2Apic => (1, Reg::Ecx, 21),
+            Feature::Avx => (1, Reg::Ecx, 28),
+            Feature::Rdrand => (1, Reg::Ecx, 30),
+            Feature::Hypervisor => (1, Reg::Ecx, 31),
+            Feature::Avx2 => (7, Reg::Ebx, 5),
+            Feature::Rdseed => (7, Reg::Ebx, 18),
+        }
+    }
+}
+
+/// What CPUID reported, read once
+struct CpuInfo {
+    /// Highest basic leaf
+    max_leaf: u32,
+    /// Highest extended leaf (0 if the extended range isn't reported)
+    max_extended_leaf: u32,
+    vendor: String,
+    brand: Option<String>,
+    hypervisor_vendor: Option<String>,
+    /// Leaf 1 result (zeroed if unsupported)
+    leaf1: CpuidResult,
+    /// Leaf 7 sub-leaf 0 result (zeroed if unsupported)
+    leaf7: CpuidResult,
+}
+
+const NO_LEAF: CpuidResult = CpuidResult { eax: 0, ebx: 0, ecx: 0, edx: 0 };
+
+static INFO: Lazy<CpuInfo> = Lazy::new(detect);
+
+/// Text packed little-endian into CPUID registers, without trailing NULs/spaces
+fn registers_text(regs: &[u32]) -> String {
+    let bytes: Vec<u8> = regs.iter().flat_map(|r| r.to_le_bytes()).collect();
+    String::from(String::from_utf8_lossy(&bytes).trim_matches(|c: char| c == '\0' || c == ' '))
+}
+
+fn detect() -> CpuInfo {
+    // SAFETY: CPUID is available on every x86_64 CPU and has no side effects
+    let leaf0 = unsafe { __cpuid(0) };
+    let max_leaf = leaf0.eax;
+    let leaf1 = if max_leaf >= 1 { unsafe { __cpuid(1) } } else { NO_LEAF };
+    let leaf7 = if max_leaf >= 7 { unsafe { __cpuid_count(7, 0) } } else { NO_LEAF };
+
+    let extended = unsafe { __cpuid(EXTENDED_BASE) }.eax;
+    let max_extended_leaf = if extended >= EXTENDED_BASE { extended } else { 0 };
+    let brand = (max_extended_leaf >= EXTENDED_BASE + 4).then(|| {
+        let regs: Vec<u32> = (2..=4)
+            .map(|i| unsafe { __cpuid(EXTENDED_BASE + i) })
+            .flat_map(|r| [r.eax, r.ebx, r.ecx, r.edx])
+            .collect();
+        registers_text(&regs)
+    });
+
+    let hypervisor_vendor = (leaf1.ecx & (1 << 31) != 0).then(|| {
+        let leaf = unsafe { __cpuid(HYPERVISOR_LEAF) };
+        registers_text(&[leaf.ebx, leaf.ecx, leaf.edx])
+    });
+
+    CpuInfo {
+        max_leaf,
+        max_extended_leaf,
+        vendor: registers_text(&[leaf0.ebx, leaf0.edx, leaf0.ecx]),
+        brand,
+        hypervisor_vendor,
+        leaf1,
+        leaf7,
+    }
+}
+
+/// Whether the CPU reports `feature` (false if its leaf isn't supported)
+pub fn has_feature(feature: Feature) -> bool {
+    let (leaf, reg, bit) = feature.location();
+    let result = match leaf {
+        1 => &INFO.leaf1,
+        _ => &INFO.leaf7,
+    };
+    let value = match reg {
+        Reg::Ebx => result.ebx,
+        Reg::Ecx => result.ecx,
+        Reg::Edx => result.edx,
+    };
+    value & (1 << bit) != 0
+}
+
+/// CPU vendor string ("GenuineIntel", "AuthenticAMD", ...)
+pub fn vendor() -> &'static str {
+    &INFO.vendor
+}
+
+/// Processor brand string, if the extended leaves report one
+pub fn brand() -> Option<&'static str> {
+    INFO.brand.as_deref()
+}
+
+/// Hypervisor vendor ("TCGTCGTCGTCG" for QEMU TCG, "KVMKVMKVM" for KVM),
+/// if running under one
+pub fn hypervisor_vendor() -> Option<&'static str> {
+    INFO.hypervisor_vendor.as_deref()
+}
+
+/// (highest basic leaf, highest extended leaf)
+pub fn max_leaves() -> (u32, u32) {
+    (INFO.max_leaf, INFO.max_extended_leaf)
+}
+
+/// Names of the features present, in `Feature::ALL` order
+pub fn present_features() -> Vec<&'static str> {
+    Feature::ALL.iter().filter(|f| has_feature(**f)).map(|f| f.name()).collect()
+}
+
+/// Log the vendor and features to serial (boot)
+pub fn log_summary() {
+    crate::serial_println!("[CPU] {} ({}), hypervisor: {}",
+        vendor(), brand().unwrap_or("no brand string"), hypervisor_vendor().unwrap_or("none"));
+    crate::serial_println!("[CPU] Features: {}", present_features().join(" "));
+}
@@ -0,0 +1,52 @@
+//! In-OS Event Log
+//!
+//! A small ring of notable events (agent registration, ambition changes,
+//! certification upgrades, protection denials) so they can be read inside
+//! Genesis with the `log` shell command instead of only on the serial port.
+//!
+//! `record` never waits: if the log is busy (e.g. an interrupt arrived while
+//! it was being read) the event is counted as dropped instead.
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use spin::Mutex;
+
+/// Events kept (oldest are discarded first)
+const EVENT_LOG_CAP: usize = 100;
+
+/// Recorded events, each prefixed with the tick it happened at
+static EVENT_LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Events lost because the log was locked
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Append an event, stamped with the current supervisor tick
+pub fn record(event: &str) {
+    let tick = crate::agents::supervisor::PANIC_CONTEXT.tick.load(Ordering::Relaxed);
+    let line = format!("[t={tick}] {event}");
+    match EVENT_LOG.try_lock() {
+        Some(mut log) => {
+            if log.len() >= EVENT_LOG_CAP {
+                log.pop_front();
+            }
+            log.push_back(line);
+        }
+        None => {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The last `count` events, oldest first
+pub fn recent(count: usize) -> Vec<String> {
+    let log = EVENT_LOG.lock();
+    log.iter().skip(log.len().saturating_sub(count)).cloned().collect()
+}
+
+/// Events lost to lock contention since boot
+pub fn dropped() -> u64 {
+    DROPPED.load(Ordering::Relaxed)
+}
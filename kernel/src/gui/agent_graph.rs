@@ -0,0 +1,208 @@
+//! Agent Message Graph
+//!
+//! The `agents graph` view: the Supervisor sits at the center with every
+//! agent on a ring around it. A spoke joins each agent to the Supervisor
+//! (every message is routed through it) and a chord joins two agents that
+//! messaged each other. Lines thicken with traffic volume and turn yellow
+//! while their count is still climbing. The main loop refreshes the view
+//! periodically; a frame is only redrawn when the traffic changed. Any key
+//! press ends the view and hands the screen back to the desktop.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+
+use super::dashboard::AgentRow;
+use super::graphics::{self, Color, GraphicsContext, TEXT_SCALE};
+
+/// Traffic between two agents: (slot, slot, messages), slots index the rows
+pub type Link = (usize, usize, u64);
+
+/// Space kept for the title line
+const HEADER_HEIGHT: u32 = 24;
+/// Space kept for the footer hint
+const FOOTER_HEIGHT: u32 = 20;
+/// Gap between the ring and the screen edge, for the labels
+const RING_MARGIN: i32 = 28;
+/// Radius of an agent node
+const NODE_RADIUS: i32 = 6;
+/// Radius of the Supervisor node
+const HUB_RADIUS: i32 = 9;
+/// Characters of an agent name shown under its node
+const LABEL_CHARS: usize = 6;
+
+/// Set while the graph owns the screen
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Set when a key ended the graph, until the main loop restores the desktop
+static EXITED: AtomicBool = AtomicBool::new(false);
+
+/// What is currently painted (None = nothing yet)
+struct Painted {
+    rows: Vec<AgentRow>,
+    links: Vec<Link>,
+    /// Some line was drawn as active, so the next refresh must fade it
+    any_active: bool,
+}
+
+static PAINTED: Mutex<Option<Painted>> = Mutex::new(None);
+
+/// Take over the screen with the graph (drawn on the next `update`)
+pub fn start() {
+    *PAINTED.lock() = None;
+    EXITED.store(false, Ordering::SeqCst);
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Whether the graph owns the screen
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// End the graph because a key was pressed. Returns true if it was active,
+/// in which case the key should be swallowed.
+///
+/// Called from the keyboard interrupt handler, so it only touches atomics.
+pub fn exit_on_key() -> bool {
+    let was_active = ACTIVE.swap(false, Ordering::SeqCst);
+    if was_active {
+        EXITED.store(true, Ordering::SeqCst);
+    }
+    was_active
+}
+
+/// True once after a key ended the graph
+pub fn take_exited() -> bool {
+    EXITED.swap(false, Ordering::SeqCst)
+}
+
+/// Redraw the graph if traffic changed since the last frame
+///
+/// `rows` are the agents (messages delivered drive the spokes) and `links`
+/// the agent-to-agent counts; a line is active when its count grew since
+/// the previous frame.
+pub fn update(rows: &[AgentRow], links: &[Link]) {
+    if !is_active() {
+        return;
+    }
+
+    let mut painted = PAINTED.lock();
+    if painted.as_ref().is_some_and(|p| !p.any_active && p.rows == rows && p.links == links) {
+        return;
+    }
+
+    let previous = painted.take();
+    let grew_spoke = |slot: usize, count: u64| {
+        previous.as_ref().is_some_and(|p| p.rows.get(slot).is_some_and(|r| r.2 < count))
+    };
+    let grew_link = |&(a, b, count): &Link| {
+        previous.as_ref().is_some_and(|p| {
+            p.links.iter().find(|l| (l.0, l.1) == (a, b)).map_or(0, |l| l.2) < count
+        })
+    };
+    let spoke_active: Vec<bool> = rows.iter().enumerate().map(|(slot, r)| grew_spoke(slot, r.2)).collect();
+    let link_active: Vec<bool> = links.iter().map(grew_link).collect();
+
+    graphics::with_graphics(|gfx| {
+        draw(gfx, rows, links, &spoke_active, &link_active);
+        gfx.swap_buffers();
+    });
+
+    *painted = Some(Painted {
+        rows: rows.to_vec(),
+        links: links.to_vec(),
+        any_active: spoke_active.iter().chain(link_active.iter()).any(|&a| a),
+    });
+}
+
+/// sin(degrees) x 1000, by Bhaskara I's approximation (the kernel has no libm)
+fn sin_milli(degrees: i32) -> i32 {
+    let d = degrees.rem_euclid(360);
+    let (d, sign) = if d < 180 { (d, 1) } else { (d - 180, -1) };
+    let p = d * (180 - d);
+    sign * 4000 * p / (40_500 - p)
+}
+
+/// Position of node `index` of `count` on a ring, starting at the top
+fn ring_point(center: (i32, i32), radius: i32, index: usize, count: usize) -> (i32, i32) {
+    let angle = -90 + (360 * index / count.max(1)) as i32;
+    (center.0 + radius * sin_milli(angle + 90) / 1000,
+     center.1 + radius * sin_milli(angle) / 1000)
+}
+
+/// Line width for a traffic volume (1 to 3 pixels, one per order of magnitude)
+fn thickness(count: u64) -> i32 {
+    match count {
+        0..=9 => 1,
+        10..=99 => 2,
+        _ => 3,
+    }
+}
+
+/// Draw a line `width` pixels thick, widening across its main direction
+fn draw_thick_line(gfx: &mut GraphicsContext, from: (i32, i32), to: (i32, i32), width: i32, color: u8) {
+    let steep = (to.1 - from.1).abs() > (to.0 - from.0).abs();
+    for offset in 0..width {
+        let (dx, dy) = if steep { (offset, 0) } else { (0, offset) };
+        gfx.draw_line(from.0 + dx, from.1 + dy, to.0 + dx, to.1 + dy, color);
+    }
+}
+
+/// Color of a line: yellow while active, blue once it carried traffic, gray before
+fn line_color(count: u64, active: bool) -> u8 {
+    if active {
+        Color::Yellow as u8
+    } else if count == 0 {
+        Color::DarkGray as u8
+    } else {
+        Color::LightBlue as u8
+    }
+}
+
+fn draw(gfx: &mut GraphicsContext, rows: &[AgentRow], links: &[Link], spoke_active: &[bool], link_active: &[bool]) {
+    let (width, height) = (gfx.width() as i32, gfx.height() as i32);
+    let top = HEADER_HEIGHT as i32;
+    let bottom = height - FOOTER_HEIGHT as i32;
+    let center = (width / 2, (top + bottom) / 2);
+    let radius = (width.min(bottom - top) / 2 - RING_MARGIN).max(HUB_RADIUS * 2);
+    let points: Vec<(i32, i32)> = (0..rows.len()).map(|i| ring_point(center, radius, i, rows.len())).collect();
+
+    gfx.clear(Color::Black);
+    gfx.draw_text(5, 4, "AGENT GRAPH", Color::Yellow as u8);
+    gfx.draw_rect(0, bottom as u32, width as u32, 1, Color::White as u8);
+    gfx.draw_text(5, bottom as u32 + 3, "any key: exit", Color::DarkGray as u8);
+
+    // Lines first so the nodes sit on top of them
+    for (slot, (_, _, delivered)) in rows.iter().enumerate() {
+        draw_thick_line(gfx, center, points[slot], thickness(*delivered),
+            line_color(*delivered, spoke_active[slot]));
+    }
+    for (&(a, b, count), &active) in links.iter().zip(link_active) {
+        if let (Some(&from), Some(&to)) = (points.get(a), points.get(b)) {
+            draw_thick_line(gfx, from, to, thickness(count), line_color(count, active));
+        }
+    }
+
+    gfx.fill_circle(center.0, center.1, HUB_RADIUS, Color::LightCyan as u8);
+    gfx.draw_circle(center.0, center.1, HUB_RADIUS, Color::White as u8);
+
+    let font = super::fonts::get_font();
+    let advance = ((font.char_width + font.spacing) * TEXT_SCALE) as i32;
+    let text_height = (font.char_height * TEXT_SCALE) as i32;
+    for ((name, state, _), &(x, y)) in rows.iter().zip(&points) {
+        gfx.fill_circle(x, y, NODE_RADIUS, state.color() as u8);
+        gfx.draw_circle(x, y, NODE_RADIUS, Color::White as u8);
+
+        // Label below nodes in the lower half, above the rest
+        let label: String = name.chars().take(LABEL_CHARS).collect();
+        let label_width = label.chars().count() as i32 * advance;
+        let label_x = (x - label_width / 2).clamp(0, (width - label_width).max(0));
+        let label_y = if y > center.1 {
+            y + NODE_RADIUS + 2
+        } else {
+            y - NODE_RADIUS - 2 - text_height
+        };
+        gfx.draw_text(label_x as u32, label_y.max(top) as u32, &label, Color::White as u8);
+    }
+}
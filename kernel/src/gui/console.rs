@@ -13,13 +13,21 @@ use lazy_static::lazy_static;
 const MAX_OUTPUT_LINES: usize = 10;
 /// Maximum input length (should match shell command length)
 const MAX_INPUT_LEN: usize = 128;
+/// Tab stop used when expanding tabs in output lines
+const DEFAULT_TAB_STOP: usize = 4;
+/// Left and right margin around console text, in pixels
+const TEXT_MARGIN: u32 = 5;
 
 /// Console state for graphics mode overlay
 pub struct GraphicsConsole {
     /// Current input buffer (what user is typing)
     input_buffer: String,
-    /// Output history (last N lines of command output)
+    /// Output history (last N physical lines, already wrapped)
     output_lines: Vec<String>,
+    /// Column at which output lines are word-wrapped
+    wrap_width: usize,
+    /// Tabs expand to the next multiple of this many columns
+    tab_stop: usize,
     /// Prompt string
     prompt: String,
     /// Console height in pixels
@@ -34,6 +42,8 @@ impl GraphicsConsole {
         GraphicsConsole {
             input_buffer: String::with_capacity(MAX_INPUT_LEN),
             output_lines: Vec::new(),
+            wrap_width: usize::MAX,
+            tab_stop: DEFAULT_TAB_STOP,
             prompt: String::from("genesis> "),
             height,
             y_position: 0, // Will be set based on screen height
@@ -61,12 +71,23 @@ impl GraphicsConsole {
         self.input_buffer.push_str(buffer);
     }
 
-    /// Add a line to output history
+    /// Set the wrap width in columns (applies to lines added afterwards)
+    pub fn set_wrap_width(&mut self, cols: usize) {
+        self.wrap_width = cols.max(1);
+    }
+
+    /// Set the tab stop in columns (applies to lines added afterwards)
+    pub fn set_tab_stop(&mut self, cols: usize) {
+        self.tab_stop = cols.max(1);
+    }
+
+    /// Add a line to output history, wrapped into physical lines
     pub fn add_output_line(&mut self, line: String) {
-        self.output_lines.push(line);
-        // Keep only last N lines
+        self.output_lines.extend(wrap_line(&line, self.wrap_width, self.tab_stop));
+        // Keep only last N physical lines
         if self.output_lines.len() > MAX_OUTPUT_LINES {
-            self.output_lines.remove(0);
+            let excess = self.output_lines.len() - MAX_OUTPUT_LINES;
+            self.output_lines.drain(..excess);
         }
     }
 
@@ -119,7 +140,7 @@ impl GraphicsConsole {
             if y_offset + line_height > console_y + self.height - (char_h + 6) {
                 break; // Don't overlap input line
             }
-            gfx.draw_text(5, y_offset, line, 15); // White text
+            gfx.draw_text(TEXT_MARGIN, y_offset, line, 15); // White text
             y_offset += line_height;
         }
         
@@ -168,17 +189,84 @@ impl GraphicsConsole {
     }
 }
 
+/// Split a logical line into physical lines of at most `width` chars.
+///
+/// Tabs expand to the next multiple of `tab_stop`. Lines break at spaces;
+/// only a word longer than a whole line is broken mid-word.
+pub fn wrap_line(line: &str, width: usize, tab_stop: usize) -> Vec<String> {
+    let width = width.max(1);
+    let tab_stop = tab_stop.max(1);
+
+    let mut expanded = String::with_capacity(line.len());
+    let mut col = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let pad = tab_stop - col % tab_stop;
+            for _ in 0..pad {
+                expanded.push(' ');
+            }
+            col += pad;
+        } else {
+            expanded.push(c);
+            col += 1;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    // Whether `current` has taken a word yet (runs of spaces split into
+    // empty words, which keeps indentation intact)
+    let mut started = false;
+    for word in expanded.split(' ') {
+        let word_len = word.chars().count();
+        if started && current_len + 1 + word_len <= width {
+            current.push(' ');
+            current.push_str(word);
+            current_len += 1 + word_len;
+            continue;
+        }
+        if started {
+            lines.push(core::mem::take(&mut current));
+        }
+        let mut rest = word;
+        while let Some((split, _)) = rest.char_indices().nth(width) {
+            lines.push(String::from(&rest[..split]));
+            rest = &rest[split..];
+        }
+        current.push_str(rest);
+        current_len = rest.chars().count();
+        started = true;
+    }
+    lines.push(current);
+    lines
+}
+
 /// Global graphics console instance
 lazy_static! {
     pub static ref GRAPHICS_CONSOLE: Mutex<Option<GraphicsConsole>> = Mutex::new(None);
 }
 
 /// Initialize graphics console
-pub fn init(_screen_width: u32, screen_height: u32) {
+pub fn init(screen_width: u32, screen_height: u32) {
     let console_height = 80; // 80 pixels tall (fits 320x200 with 2x text)
     let mut console = GraphicsConsole::new(console_height);
     console.set_y_position(screen_height - console_height);
     *GRAPHICS_CONSOLE.lock() = Some(console);
+    fit_to_width(screen_width);
+}
+
+/// Columns of current-font text that fit across a panel `panel_width` pixels wide
+fn columns_for(panel_width: u32) -> usize {
+    let font = super::fonts::get_font();
+    let advance = (font.char_width + font.spacing) * super::graphics::TEXT_SCALE;
+    (panel_width.saturating_sub(2 * TEXT_MARGIN) / advance.max(1)) as usize
+}
+
+/// Wrap output at as many columns as fit across the panel with the current
+/// font. Called at init and again whenever the font changes.
+pub fn fit_to_width(panel_width: u32) {
+    set_wrap_width(columns_for(panel_width));
 }
 
 /// Update console input buffer
@@ -191,11 +279,26 @@ pub fn update_input_buffer(buffer: &str) {
     render_overlay(super::graphics::WIDTH, super::graphics::HEIGHT);
 }
 
+/// Set the column count output lines wrap at (defaults to the panel width)
+pub fn set_wrap_width(cols: usize) {
+    if let Some(ref mut console) = GRAPHICS_CONSOLE.lock().as_mut() {
+        console.set_wrap_width(cols);
+    }
+}
+
+/// Set the tab stop used when expanding tabs in output lines
+pub fn set_tab_stop(cols: usize) {
+    if let Some(ref mut console) = GRAPHICS_CONSOLE.lock().as_mut() {
+        console.set_tab_stop(cols);
+    }
+}
+
 /// Add output line to console
 pub fn add_output_line(line: String) {
     if let Some(ref mut console) = GRAPHICS_CONSOLE.lock().as_mut() {
         console.add_output_line(line);
     }
+    super::desktop::mark_dirty();
 }
 
 /// Clear console output
@@ -203,6 +306,7 @@ pub fn clear_output() {
     if let Some(ref mut console) = GRAPHICS_CONSOLE.lock().as_mut() {
         console.clear_output();
     }
+    super::desktop::mark_dirty();
 }
 
 /// Render the console overlay (internal - use render_to_graphics from within graphics context)
@@ -241,3 +345,25 @@ pub fn render_to_graphics(gfx: &mut super::graphics::GraphicsContext, screen_wid
 pub fn is_initialized() -> bool {
     GRAPHICS_CONSOLE.lock().is_some()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_line_breaks_at_word_boundaries() {
+        assert_eq!(wrap_line("the quick brown fox", 10, 4), alloc::vec!["the quick", "brown fox"]);
+        assert_eq!(wrap_line("", 10, 4), alloc::vec![""]);
+    }
+
+    #[test]
+    fn wrap_line_splits_only_overlong_words() {
+        assert_eq!(wrap_line("ab abcdefghij", 4, 4), alloc::vec!["ab", "abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn wrap_line_expands_tabs_to_stops() {
+        assert_eq!(wrap_line("a\tb", 20, 4), alloc::vec!["a   b"]);
+        assert_eq!(wrap_line("  indented", 20, 4), alloc::vec!["  indented"]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Live Agent Dashboard
+//!
+//! The `status --watch` view: one row per agent (state dot, name, messages
+//! delivered), refreshed by the main loop while graphics mode is active.
+//! Only the header and rows whose values changed are repainted, so the
+//! continuous updates never redraw the whole screen. Any key press ends the
+//! view and hands the screen back to the desktop.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+
+use super::graphics::{self, Color, GraphicsContext, TEXT_SCALE};
+use crate::agents::AgentState;
+
+/// One dashboard row: agent name, state, messages delivered
+pub type AgentRow = (String, AgentState, u64);
+
+/// Top of the header line
+const HEADER_Y: u32 = 4;
+/// Top of the first agent row
+const FIRST_ROW_Y: u32 = 28;
+/// Height of each agent row
+const ROW_HEIGHT: u32 = 20;
+/// Space kept for the footer hint
+const FOOTER_HEIGHT: u32 = 20;
+/// The header tick is repainted at most this often
+const HEADER_REFRESH_TICKS: u64 = 50;
+
+/// Set while the dashboard owns the screen
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Set when a key ended the dashboard, until the main loop restores the desktop
+static EXITED: AtomicBool = AtomicBool::new(false);
+
+/// What is currently painted (None = the frame needs a full draw)
+struct Painted {
+    rows: Vec<AgentRow>,
+    header_tick: Option<u64>,
+}
+
+static PAINTED: Mutex<Option<Painted>> = Mutex::new(None);
+
+/// Take over the screen with the dashboard (drawn on the next `update`)
+pub fn start() {
+    *PAINTED.lock() = None;
+    EXITED.store(false, Ordering::SeqCst);
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Whether the dashboard owns the screen
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// End the dashboard because a key was pressed. Returns true if it was
+/// active, in which case the key should be swallowed.
+///
+/// Called from the keyboard interrupt handler, so it only touches atomics.
+pub fn exit_on_key() -> bool {
+    let was_active = ACTIVE.swap(false, Ordering::SeqCst);
+    if was_active {
+        EXITED.store(true, Ordering::SeqCst);
+    }
+    was_active
+}
+
+/// True once after a key ended the dashboard
+pub fn take_exited() -> bool {
+    EXITED.swap(false, Ordering::SeqCst)
+}
+
+/// Repaint whatever changed since the last update
+pub fn update(rows: &[AgentRow], tick: u64) {
+    if !is_active() {
+        return;
+    }
+
+    let mut painted = PAINTED.lock();
+    graphics::with_graphics(|gfx| {
+        let shown = painted.get_or_insert_with(|| {
+            draw_frame(gfx);
+            Painted { rows: Vec::new(), header_tick: None }
+        });
+        let mut changed = false;
+
+        if shown.header_tick.is_none_or(|t| t / HEADER_REFRESH_TICKS != tick / HEADER_REFRESH_TICKS) {
+            draw_header(gfx, tick);
+            shown.header_tick = Some(tick);
+            changed = true;
+        }
+
+        let max_rows = ((graphics::HEIGHT - FOOTER_HEIGHT - FIRST_ROW_Y) / ROW_HEIGHT) as usize;
+        let visible = &rows[..rows.len().min(max_rows)];
+        for (slot, row) in visible.iter().enumerate() {
+            if shown.rows.get(slot) != Some(row) {
+                draw_row(gfx, slot, Some(row));
+                changed = true;
+            }
+        }
+        // Blank rows for agents that went away
+        for slot in visible.len()..shown.rows.len() {
+            draw_row(gfx, slot, None);
+            changed = true;
+        }
+        shown.rows = visible.to_vec();
+
+        if changed {
+            gfx.swap_buffers();
+        }
+    });
+}
+
+/// Horizontal advance of one character in the current font
+fn char_advance() -> u32 {
+    let font = super::fonts::get_font();
+    (font.char_width + font.spacing) * TEXT_SCALE
+}
+
+fn draw_frame(gfx: &mut GraphicsContext) {
+    gfx.clear(Color::Black);
+    gfx.draw_rect(0, FIRST_ROW_Y - 4, graphics::WIDTH, 1, Color::White as u8);
+    gfx.draw_rect(0, graphics::HEIGHT - FOOTER_HEIGHT, graphics::WIDTH, 1, Color::White as u8);
+    gfx.draw_text(5, graphics::HEIGHT - FOOTER_HEIGHT + 3, "any key: exit", Color::DarkGray as u8);
+}
+
+fn draw_header(gfx: &mut GraphicsContext, tick: u64) {
+    gfx.draw_rect(0, HEADER_Y, graphics::WIDTH, FIRST_ROW_Y - 4 - HEADER_Y, Color::Black as u8);
+    // Keep the tick short enough to fit on one line
+    gfx.draw_text(5, HEADER_Y, &format!("LIVE t={}", tick % 1_000_000), Color::Yellow as u8);
+}
+
+/// Paint (or blank, for None) one agent row in place
+fn draw_row(gfx: &mut GraphicsContext, slot: usize, row: Option<&AgentRow>) {
+    let y = FIRST_ROW_Y + slot as u32 * ROW_HEIGHT;
+    gfx.draw_rect(0, y, graphics::WIDTH, ROW_HEIGHT, Color::Black as u8);
+    let Some((name, state, messages)) = row else {
+        return;
+    };
+
+    gfx.draw_rect(5, y + 4, 8, 8, state.color() as u8);
+
+    let advance = char_advance();
+    let count = format!("{}", (*messages).min(9999));
+    let count_x = graphics::WIDTH.saturating_sub(5 + count.len() as u32 * advance);
+    let name_x = 18;
+    let name_chars = (count_x.saturating_sub(name_x + advance) / advance) as usize;
+    let name: String = name.chars().take(name_chars).collect();
+
+    gfx.draw_text(name_x, y, &name, Color::White as u8);
+    gfx.draw_text(count_x, y, &count, Color::LightCyan as u8);
+}
+
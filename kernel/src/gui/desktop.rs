@@ -5,10 +5,23 @@
 //! - Left: Conversation/transcript (Voice Archimedes)
 //! - Right: Ambition statement (Silent Archimedes)
 //! - Bottom: Agent zones (Focus, Resources, etc.)
+//!
+//! That split screen is one of several named arrangements (`LAYOUTS`); each
+//! `create_*_layout` builds the zones from data it is handed, and the render
+//! path is the same for all of them.
 
 use alloc::string::String;
 use alloc::vec::Vec;
 
+/// Name of the right-hand ambition zone
+const AMBITION_ZONE: &str = "Ambition Statement";
+
+/// Built-in arrangements selectable with `desktop layout <name>`
+pub const LAYOUTS: [&str; 4] = ["split", "focus", "dashboard", "journal"];
+
+/// Height of one agent cell in the dashboard grid (pixels)
+const DASHBOARD_CELL_HEIGHT: u32 = 40;
+
 /// A zone on the desktop defined by an agent
 #[derive(Debug, Clone)]
 pub struct Zone {
@@ -41,6 +54,12 @@ pub struct DesktopLayout {
     width: u32,
     /// Screen height
     height: u32,
+    /// Ambition pane inputs, kept so the theme can be refreshed in place
+    ambition: Option<String>,
+    commitments: Vec<String>,
+    theme: Option<String>,
+    /// Which of `LAYOUTS` the zones were built as
+    layout: &'static str,
 }
 
 impl DesktopLayout {
@@ -50,6 +69,10 @@ impl DesktopLayout {
             zones: Vec::new(),
             width,
             height,
+            ambition: None,
+            commitments: Vec::new(),
+            theme: None,
+            layout: LAYOUTS[0],
         }
     }
     
@@ -59,7 +82,13 @@ impl DesktopLayout {
     }
     
     /// Create default ambition layout (split screen)
-    pub fn create_ambition_layout(&mut self, ambition: Option<&str>, commitments: &[String]) {
+    ///
+    /// `theme` is today's top memory keyword; the line is left out when None.
+    pub fn create_ambition_layout(&mut self, ambition: Option<&str>, commitments: &[String], theme: Option<&str>) {
+        self.set_ambition(ambition, commitments, theme);
+        self.zones.clear();
+        self.layout = "split";
+        
         // Left zone: Conversation area (for Voice Archimedes)
         let left_zone = Zone {
             name: String::from("Conversation"),
@@ -75,40 +104,151 @@ impl DesktopLayout {
         self.add_zone(left_zone);
         
         // Right zone: Ambition statement (Silent Archimedes)
-        let mut right_content = String::from("TODAY'S AMBITION\n\n");
-        
-        if let Some(ambition) = ambition {
-            right_content.push_str("Today's Ambition Statement:\n");
-            right_content.push_str("\"");
-            right_content.push_str(ambition);
-            right_content.push_str("\"\n\n");
-        } else {
-            right_content.push_str("No ambition set yet.\n\n");
-        }
-        
-        if !commitments.is_empty() {
-            right_content.push_str("Key Commitments:\n");
-            for commitment in commitments {
-                right_content.push_str("- ");
-                right_content.push_str(commitment);
-                right_content.push_str("\n");
-            }
-        }
-        
         let right_zone = Zone {
-            name: String::from("Ambition Statement"),
+            name: String::from(AMBITION_ZONE),
             agent: String::from("Silent Archimedes"),
             x: self.width / 2,
             y: 0,
             width: self.width / 2,
             height: self.height,
-            content: right_content,
+            content: self.ambition_content(),
             bg_color: 1, // Blue
             text_color: 14, // Yellow
         };
         self.add_zone(right_zone);
     }
     
+    /// "focus": the ambition pane alone, full screen
+    pub fn create_focus_layout(&mut self, ambition: Option<&str>, commitments: &[String], theme: Option<&str>) {
+        self.set_ambition(ambition, commitments, theme);
+        self.zones.clear();
+        self.layout = "focus";
+        let zone = Zone {
+            name: String::from(AMBITION_ZONE),
+            agent: String::from("Silent Archimedes"),
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+            content: self.ambition_content(),
+            bg_color: 1, // Blue
+            text_color: 14, // Yellow
+        };
+        self.add_zone(zone);
+    }
+
+    /// "dashboard": one cell per agent, two columns, from (name, status) pairs
+    pub fn create_dashboard_layout(&mut self, agents: &[(String, String)]) {
+        self.zones.clear();
+        self.layout = "dashboard";
+        if agents.is_empty() {
+            self.add_text_zone("Dashboard", "AGENTS\n\nNo agents registered.");
+            return;
+        }
+        let cell_width = self.width / 2;
+        for (i, (name, status)) in agents.iter().enumerate() {
+            let y = (i as u32 / 2) * DASHBOARD_CELL_HEIGHT;
+            if y + DASHBOARD_CELL_HEIGHT > self.height {
+                break; // More agents than fit on screen
+            }
+            self.add_zone(Zone {
+                name: name.clone(),
+                agent: name.clone(),
+                x: (i as u32 % 2) * cell_width,
+                y,
+                width: cell_width,
+                height: DASHBOARD_CELL_HEIGHT,
+                content: alloc::format!("{name}\n{status}"),
+                bg_color: 0, // Black
+                text_color: 10, // Light Green
+            });
+        }
+    }
+
+    /// "journal": the agents' journal entries, from (agent, entry) pairs
+    pub fn create_journal_layout(&mut self, entries: &[(String, String)]) {
+        self.zones.clear();
+        self.layout = "journal";
+        let mut content = String::from("AS THE KERNEL TURNS\n\n");
+        if entries.is_empty() {
+            content.push_str("No journal entries yet.");
+        }
+        for (agent, entry) in entries {
+            content.push_str(&alloc::format!("{agent}: {entry}\n"));
+        }
+        self.add_text_zone("Journal", &content);
+    }
+
+    /// One full-screen zone of plain text
+    fn add_text_zone(&mut self, name: &str, content: &str) {
+        let zone = Zone {
+            name: String::from(name),
+            agent: String::from("Supervisor"),
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+            content: String::from(content),
+            bg_color: 0, // Black
+            text_color: 15, // White
+        };
+        self.add_zone(zone);
+    }
+
+    /// Remember the ambition pane inputs
+    fn set_ambition(&mut self, ambition: Option<&str>, commitments: &[String], theme: Option<&str>) {
+        self.ambition = ambition.map(String::from);
+        self.commitments = commitments.to_vec();
+        self.theme = theme.map(String::from);
+    }
+
+    /// Which of `LAYOUTS` is showing
+    pub fn layout(&self) -> &'static str {
+        self.layout
+    }
+    
+    /// Text of the ambition pane: statement, theme of the day, commitments
+    fn ambition_content(&self) -> String {
+        let mut content = String::from("TODAY'S AMBITION\n\n");
+        
+        if let Some(ambition) = &self.ambition {
+            content.push_str("Today's Ambition Statement:\n");
+            content.push('"');
+            content.push_str(ambition);
+            content.push_str("\"\n\n");
+        } else {
+            content.push_str("No ambition set yet.\n\n");
+        }
+        
+        if let Some(theme) = &self.theme {
+            content.push_str("Today's theme: ");
+            content.push_str(theme);
+            content.push_str("\n\n");
+        }
+        
+        if !self.commitments.is_empty() {
+            content.push_str("Key Commitments:\n");
+            for commitment in &self.commitments {
+                content.push_str("- ");
+                content.push_str(commitment);
+                content.push('\n');
+            }
+        }
+        content
+    }
+    
+    /// Update the theme line of the ambition pane (no-op if unchanged)
+    pub fn set_theme(&mut self, theme: Option<&str>) {
+        if self.theme.as_deref() == theme {
+            return;
+        }
+        self.theme = theme.map(String::from);
+        let content = self.ambition_content();
+        if let Some(zone) = self.zones.iter_mut().find(|z| z.name == AMBITION_ZONE) {
+            zone.content = content;
+        }
+    }
+    
     /// Get all zones
     pub fn zones(&self) -> &[Zone] {
         &self.zones
@@ -173,6 +313,7 @@ impl DesktopLayout {
 /// Global desktop layout
 use spin::Mutex;
 use lazy_static::lazy_static;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 lazy_static! {
     pub static ref DESKTOP: Mutex<Option<DesktopLayout>> = Mutex::new(None);
@@ -183,16 +324,63 @@ pub fn init(width: u32, height: u32) {
     *DESKTOP.lock() = Some(DesktopLayout::new(width, height));
 }
 
+/// Set when visible content changes, cleared by `render()`
+///
+/// Atomic rather than a Mutex because mode toggles mark it from the keyboard
+/// interrupt handler.
+static DIRTY: AtomicBool = AtomicBool::new(true);
+
+/// When true, the main loop only re-renders when the desktop is dirty
+static AUTO_RENDER: AtomicBool = AtomicBool::new(false);
+
 /// Get mutable reference to desktop layout
 pub fn with_desktop<F, R>(f: F) -> Option<R>
 where
     F: FnOnce(&mut DesktopLayout) -> R,
 {
+    mark_dirty();
     DESKTOP.lock().as_mut().map(f)
 }
 
-/// Render the desktop
+/// Today's theme: the most frequent memory keyword, if any memories exist
+pub fn theme_of_the_day() -> Option<String> {
+    crate::storage::memory_store::top_keywords(1)
+        .into_iter()
+        .next()
+        .map(|(keyword, _)| keyword)
+}
+
+/// Render the desktop, refreshing the theme of the day first
 pub fn render() {
-    DESKTOP.lock().as_ref().map(|desktop| desktop.render());
+    DIRTY.store(false, Ordering::SeqCst);
+    let theme = theme_of_the_day();
+    if let Some(desktop) = DESKTOP.lock().as_mut() {
+        desktop.set_theme(theme.as_deref());
+        desktop.render();
+    }
+}
+
+/// Flag the desktop for re-render
+pub fn mark_dirty() {
+    DIRTY.store(true, Ordering::SeqCst);
+}
+
+/// Check whether anything changed since the last render
+pub fn needs_render() -> bool {
+    DIRTY.load(Ordering::SeqCst)
+}
+
+/// Switch between "always render" and "render only when dirty"
+pub fn set_auto_render(enabled: bool) {
+    AUTO_RENDER.store(enabled, Ordering::SeqCst);
+    if enabled {
+        // Repaint once so auto mode starts from a fresh frame
+        mark_dirty();
+    }
+}
+
+/// Check whether auto (dirty-only) rendering is on
+pub fn auto_render() -> bool {
+    AUTO_RENDER.load(Ordering::SeqCst)
 }
 
@@ -6,6 +6,7 @@
 
 use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// Font character bitmap (8x8 pixels)
 /// Each byte represents one row, with bits representing pixels
@@ -207,6 +208,45 @@ pub fn set_font(font: Font) {
     *CURRENT_FONT.lock() = font;
 }
 
+/// A built-in font in the registry
+struct BuiltinFont {
+    /// Registry name (what `fonts set` takes)
+    name: &'static str,
+    /// `Font::name` of the font it builds
+    display: &'static str,
+    create: fn() -> Font,
+}
+
+/// Built-in fonts by registry name
+const BUILTIN_FONTS: [BuiltinFont; 3] = [
+    BuiltinFont { name: "academy", display: "Agent Alliance Academy", create: create_academy_font },
+    BuiltinFont { name: "academy-bold", display: "Agent Alliance Academy Bold", create: create_academy_bold_font },
+    BuiltinFont { name: "genesis", display: "Genesis AI", create: create_genesis_font },
+];
+
+/// Registry names of the built-in fonts
+pub fn list_names() -> Vec<&'static str> {
+    BUILTIN_FONTS.iter().map(|f| f.name).collect()
+}
+
+/// Registry name of the active font (None for a custom font)
+pub fn current_name() -> Option<&'static str> {
+    let display = CURRENT_FONT.lock().name;
+    BUILTIN_FONTS.iter().find(|f| f.display == display).map(|f| f.name)
+}
+
+/// Switch to a built-in font by registry name (case-insensitive).
+/// Returns false if there's no such font.
+pub fn set_by_name(name: &str) -> bool {
+    match BUILTIN_FONTS.iter().find(|f| f.name.eq_ignore_ascii_case(name.trim())) {
+        Some(font) => {
+            set_font((font.create)());
+            true
+        }
+        None => false,
+    }
+}
+
 /// Get glyph for a character using the current font
 pub fn get_glyph(ch: char) -> CharBitmap {
     CURRENT_FONT.lock().get_glyph_or_default(ch).clone()
@@ -316,3 +356,16 @@ pub fn create_academy_font() -> Font {
     
     font
 }
+
+/// Agent Alliance Academy Bold
+///
+/// The Academy font with every stroke thickened one pixel to the right -
+/// easier to read on a busy desktop or a blurry projector.
+pub fn create_academy_bold_font() -> Font {
+    let academy = create_academy_font();
+    let mut font = Font::new("Agent Alliance Academy Bold", 8, 8, 4);
+    for (ch, bitmap) in &academy.glyphs {
+        font.add_glyph(*ch, bitmap.map(|row| row | (row >> 1)));
+    }
+    font
+}
@@ -8,6 +8,7 @@
 extern crate alloc;
 
 use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use spin::Mutex;
 use lazy_static::lazy_static;
 
@@ -19,6 +20,12 @@ pub const WIDTH: u32 = 320;
 pub const HEIGHT: u32 = 200;
 pub const FRAMEBUFFER_ADDR: usize = 0xA0000;
 
+/// VGA text buffer (Mode 3)
+pub const TEXT_BUFFER_ADDR: usize = 0xB8000;
+
+/// BIOS number of the graphics mode last programmed (0x12 or 0x13)
+static HARDWARE_MODE: AtomicU8 = AtomicU8::new(0x13);
+
 /// Text rendering scale factor (2x for readability)
 pub const TEXT_SCALE: u32 = 2;
 
@@ -104,6 +111,7 @@ impl GraphicsContext {
     pub unsafe fn init_mode_12h(&mut self) {
         // Actually switch to VGA Mode 12h by programming hardware registers
         switch_to_mode_12h();
+        HARDWARE_MODE.store(0x12, Ordering::SeqCst);
         
         // Clear the framebuffer
         self.clear(Color::Black);
@@ -118,6 +126,7 @@ impl GraphicsContext {
     /// Direct hardware access - must be called during kernel init.
     pub unsafe fn init_mode_13h(&mut self) {
         switch_to_mode_13h();
+        HARDWARE_MODE.store(0x13, Ordering::SeqCst);
         self.clear(Color::Black);
         self.draw_test_pattern();
     }
@@ -144,6 +153,57 @@ impl GraphicsContext {
         self.draw_text(10, 140, "Mode 13h: 320x200", Color::LightCyan as u8);
         self.draw_text(10, 160, "2x Text Scaling", Color::LightGreen as u8);
     }
+
+    /// Draw rows of stipple swatches for picking UI fill patterns: plain
+    /// stripes on top, diagonal hatches below
+    pub fn draw_stipple_samples(&mut self) {
+        const PATTERNS: [u8; 5] = [0xFF, 0xAA, 0x88, 0x80, 0xCC];
+
+        self.clear(Color::Black);
+
+        let swatch = 48u32;
+        let gap = 12u32;
+        for (i, pattern) in PATTERNS.iter().enumerate() {
+            let x = gap + i as u32 * (swatch + gap);
+            self.draw_rect_pattern(x, 20, swatch, swatch, Color::LightCyan as u8, *pattern);
+            self.draw_rect_outline(x, 20, swatch, swatch, Color::White as u8);
+            self.draw_rect_hatch(x, 100, swatch, swatch, Color::LightCyan as u8, *pattern);
+            self.draw_rect_outline(x, 100, swatch, swatch, Color::White as u8);
+        }
+        self.draw_text(10, 76, "FF AA 88 80 CC", Color::White as u8);
+        self.draw_text(10, 156, "HATCHED", Color::White as u8);
+    }
+    
+    /// Blit the Genesis logo over a striped background to check alignment and transparency
+    pub fn draw_logo_sample(&mut self) {
+        const T: u8 = 0xFF; // Transparent key
+        const C: u8 = Color::LightCyan as u8;
+        const Y: u8 = Color::Yellow as u8;
+        #[rustfmt::skip]
+        const LOGO: [u8; 12 * 12] = [
+            T, T, T, C, C, C, C, C, C, T, T, T,
+            T, T, C, C, C, C, C, C, C, C, T, T,
+            T, C, C, T, T, T, T, T, T, C, C, T,
+            C, C, T, T, T, T, T, T, T, T, T, T,
+            C, C, T, T, T, T, T, T, T, T, T, T,
+            C, C, T, T, T, Y, Y, Y, Y, Y, T, T,
+            C, C, T, T, T, Y, Y, Y, Y, C, C, T,
+            C, C, T, T, T, T, T, T, T, C, C, T,
+            T, C, C, T, T, T, T, T, T, C, C, T,
+            T, T, C, C, C, C, C, C, C, C, T, T,
+            T, T, T, C, C, C, C, C, C, T, T, T,
+            T, T, T, T, T, T, T, T, T, T, T, T,
+        ];
+
+        self.clear(Color::Black);
+        self.draw_rect_pattern(0, 0, self.width, 80, Color::Blue as u8, 0xCC);
+
+        // Opaque on the left, keyed on the right, plus one clipped at the screen edge
+        self.blit(40, 30, 12, 12, &LOGO);
+        self.blit_keyed(80, 30, 12, 12, &LOGO, T);
+        self.blit_keyed(self.width - 6, 30, 12, 12, &LOGO, T);
+        self.draw_text(10, 100, "blit / keyed", Color::White as u8);
+    }
     
     /// Draw a single pixel
     /// 
@@ -173,6 +233,67 @@ impl GraphicsContext {
         }
     }
     
+    /// Draw a rectangle filled with an 8-bit stipple pattern
+    ///
+    /// Each bit of `pattern` covers one column of an 8-pixel cell (bit 7 is the
+    /// leftmost column); set bits are drawn, clear bits are skipped. Every row
+    /// uses the same mask, so the pattern repeats as vertical stripes.
+    pub fn draw_rect_pattern(&mut self, x: u32, y: u32, w: u32, h: u32, color: u8, pattern: u8) {
+        self.fill_masked_rows(x, y, w, h, color, |_| pattern);
+    }
+
+    /// Like `draw_rect_pattern`, but the mask is rotated by one bit per row,
+    /// so sparse patterns become diagonal hatches and dots
+    pub fn draw_rect_hatch(&mut self, x: u32, y: u32, w: u32, h: u32, color: u8, pattern: u8) {
+        self.fill_masked_rows(x, y, w, h, color, |py| pattern.rotate_right(py % 8));
+    }
+
+    fn fill_masked_rows(&mut self, x: u32, y: u32, w: u32, h: u32, color: u8, mask_for_row: impl Fn(u32) -> u8) {
+        for py in y..(y + h).min(self.height) {
+            let row_mask = mask_for_row(py);
+            for px in x..(x + w).min(self.width) {
+                if (row_mask >> (7 - (px % 8))) & 1 != 0 {
+                    self.draw_pixel(px, py, color);
+                }
+            }
+        }
+    }
+    
+    /// Copy a `w`x`h` buffer of palette indices into the framebuffer at (x, y)
+    ///
+    /// Pixels falling outside the screen are clipped. A buffer shorter than
+    /// `w * h` simply stops early.
+    pub fn blit(&mut self, x: u32, y: u32, w: u32, h: u32, pixels: &[u8]) {
+        self.blit_inner(x, y, w, h, pixels, None);
+    }
+
+    /// Like `blit`, but pixels equal to `transparent` are skipped
+    pub fn blit_keyed(&mut self, x: u32, y: u32, w: u32, h: u32, pixels: &[u8], transparent: u8) {
+        self.blit_inner(x, y, w, h, pixels, Some(transparent));
+    }
+
+    fn blit_inner(&mut self, x: u32, y: u32, w: u32, h: u32, pixels: &[u8], key: Option<u8>) {
+        for row in 0..h {
+            let py = y + row;
+            if py >= self.height {
+                break;
+            }
+            for col in 0..w {
+                let px = x + col;
+                if px >= self.width {
+                    break;
+                }
+                let color = match pixels.get((row * w + col) as usize) {
+                    Some(&c) => c,
+                    None => return,
+                };
+                if Some(color) != key {
+                    self.draw_pixel(px, py, color);
+                }
+            }
+        }
+    }
+    
     /// Draw a rectangle outline
     pub fn draw_rect_outline(&mut self, x: u32, y: u32, w: u32, h: u32, color: u8) {
         // Top and bottom lines
@@ -192,6 +313,67 @@ impl GraphicsContext {
         }
     }
     
+    /// Plot a pixel at signed coordinates, skipping ones off screen
+    fn plot(&mut self, x: i32, y: i32, color: u8) {
+        if x >= 0 && y >= 0 {
+            self.draw_pixel(x as u32, y as u32, color);
+        }
+    }
+
+    /// Draw a line between two points (Bresenham); off-screen pixels are clipped
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u8) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.plot(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draw a circle outline (midpoint algorithm)
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, r: i32, color: u8) {
+        let (mut x, mut y, mut err) = (r, 0, 1 - r);
+        while x >= y {
+            for (px, py) in [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)] {
+                self.plot(cx + px, cy + py, color);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Draw a filled circle
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, r: i32, color: u8) {
+        for dy in -r..=r {
+            let mut half = 0;
+            while (half + 1) * (half + 1) + dy * dy <= r * r {
+                half += 1;
+            }
+            for x in cx - half..=cx + half {
+                self.plot(x, cy + dy, color);
+            }
+        }
+    }
+
     /// Clear the screen with a color
     pub fn clear(&mut self, color: Color) {
         self.clear_color = color as u8;
@@ -223,9 +405,13 @@ impl GraphicsContext {
     
     /// Enable double buffering
     /// 
-    /// Allocates a back buffer for smooth rendering.
-    /// Requires heap allocation to be initialized.
+    /// Allocates a back buffer for smooth rendering, starting as a copy of
+    /// what's on screen. Requires heap allocation to be initialized.
     pub fn enable_double_buffering(&mut self) -> Result<(), &'static str> {
+        if !self.back_buffer.is_null() {
+            return Ok(());
+        }
+        
         use alloc::alloc::{alloc, Layout};
         
         let size = self.buffer_len_bytes();
@@ -237,12 +423,38 @@ impl GraphicsContext {
             if buffer.is_null() {
                 return Err("Failed to allocate back buffer");
             }
+            ptr::copy_nonoverlapping(self.front_buffer, buffer, size);
             self.back_buffer = buffer;
         }
         
         Ok(())
     }
     
+    /// Try to enable double buffering; if the back buffer can't be
+    /// allocated, log it and keep drawing straight to VGA memory
+    fn double_buffer_or_fallback(&mut self) {
+        if let Err(e) = self.enable_double_buffering() {
+            serial_println!("[GRAPHICS] WARNING: {} - falling back to single-buffered rendering", e);
+        }
+    }
+    
+    /// Check that the framebuffer actually holds what we write to it
+    ///
+    /// If the mode switch didn't take, 0xA0000 isn't mapped to VGA memory and
+    /// reads come back as open bus (0xFF) regardless of what was written.
+    fn framebuffer_responds(&self) -> bool {
+        let probe = unsafe { self.front_buffer.add(self.buffer_len_bytes() - 1) };
+        unsafe {
+            let saved = ptr::read_volatile(probe);
+            let ok = [0x5Au8, 0xA5].iter().all(|&pattern| {
+                ptr::write_volatile(probe, pattern);
+                ptr::read_volatile(probe) == pattern
+            });
+            ptr::write_volatile(probe, saved);
+            ok
+        }
+    }
+    
     /// Get width
     pub fn width(&self) -> u32 {
         self.width
@@ -627,16 +839,34 @@ unsafe fn switch_to_mode_13h() {
 
 /// Set up the standard VGA 16-color palette for Mode 12h
 unsafe fn setup_palette_16color() {
+    serial_println!("[PALETTE] Setting up 16-color palette...");
+    let (name, colors) = &THEMES[CURRENT_THEME.load(Ordering::Relaxed)];
+    write_palette_16(colors);
+    serial_println!("[PALETTE] 16-color palette configured ({})", name);
+}
+
+/// 16 DAC entries as 6-bit (r, g, b)
+pub type Palette16 = [(u8, u8, u8); 16];
+
+/// Program DAC entries 0-15 with 6-bit RGB values
+unsafe fn write_palette_16(colors: &Palette16) {
     use x86_64::instructions::port::Port;
     
-    serial_println!("[PALETTE] Setting up 16-color palette...");
     let mut palette_index: Port<u8> = Port::new(0x3C8);
     let mut palette_data: Port<u8> = Port::new(0x3C9);
     
     palette_index.write(0); // Start at color 0
-    
-    // Standard VGA 16 colors
-    let standard_colors: [(u8, u8, u8); 16] = [
+    for (r, g, b) in colors.iter() {
+        palette_data.write(*r);
+        palette_data.write(*g);
+        palette_data.write(*b);
+    }
+}
+
+/// Desktop color themes (6-bit DAC values, indexed like `Color`).
+/// Both Mode 12h and Mode 13h take their first 16 palette entries from here.
+pub const THEMES: [(&str, Palette16); 3] = [
+    ("classic", [
         (0x00, 0x00, 0x00), // 0: Black
         (0x00, 0x00, 0x2A), // 1: Blue
         (0x00, 0x2A, 0x00), // 2: Green
@@ -653,14 +883,44 @@ unsafe fn setup_palette_16color() {
         (0x3F, 0x15, 0x3F), // 13: Pink
         (0x3F, 0x3F, 0x15), // 14: Yellow
         (0x3F, 0x3F, 0x3F), // 15: White
-    ];
-    
-    for (r, g, b) in standard_colors.iter() {
-        palette_data.write(*r);
-        palette_data.write(*g);
-        palette_data.write(*b);
+    ]),
+    ("amber", [
+        (0x00, 0x00, 0x00), (0x0A, 0x05, 0x00), (0x15, 0x0A, 0x00), (0x1A, 0x0D, 0x00),
+        (0x20, 0x10, 0x00), (0x24, 0x12, 0x00), (0x28, 0x14, 0x00), (0x2A, 0x1C, 0x04),
+        (0x12, 0x09, 0x00), (0x2E, 0x18, 0x00), (0x32, 0x1E, 0x00), (0x36, 0x22, 0x04),
+        (0x3A, 0x26, 0x08), (0x3C, 0x2A, 0x0C), (0x3F, 0x30, 0x10), (0x3F, 0x38, 0x20),
+    ]),
+    ("matrix-green", [
+        (0x00, 0x00, 0x00), (0x00, 0x08, 0x02), (0x00, 0x14, 0x04), (0x00, 0x18, 0x08),
+        (0x02, 0x1C, 0x02), (0x02, 0x20, 0x06), (0x04, 0x22, 0x04), (0x08, 0x2A, 0x08),
+        (0x00, 0x0E, 0x02), (0x04, 0x2C, 0x0A), (0x08, 0x32, 0x08), (0x0C, 0x36, 0x10),
+        (0x10, 0x38, 0x10), (0x14, 0x3A, 0x18), (0x20, 0x3F, 0x20), (0x30, 0x3F, 0x30),
+    ]),
+];
+
+/// Index into `THEMES` of the active palette
+static CURRENT_THEME: AtomicUsize = AtomicUsize::new(0);
+
+/// Name of the active desktop theme
+pub fn current_theme() -> &'static str {
+    THEMES[CURRENT_THEME.load(Ordering::Relaxed)].0
+}
+
+/// Select a desktop theme by name. Returns false for unknown names.
+///
+/// In graphics mode the DAC is reprogrammed immediately, so the screen
+/// changes color without a redraw. In text mode the choice is applied
+/// the next time Mode 12h is set up.
+pub fn set_theme(name: &str) -> bool {
+    let Some(index) = THEMES.iter().position(|(n, _)| *n == name) else {
+        return false;
+    };
+    CURRENT_THEME.store(index, Ordering::Relaxed);
+    if current_mode() == VgaMode::Graphics {
+        unsafe { write_palette_16(&THEMES[index].1) };
     }
-    serial_println!("[PALETTE] 16-color palette configured");
+    serial_println!("[PALETTE] Theme set to {}", name);
+    true
 }
 
 /// Set up the standard VGA 256-color palette (Mode 13h)
@@ -670,33 +930,9 @@ unsafe fn setup_palette() {
     let mut palette_index: Port<u8> = Port::new(0x3C8);
     let mut palette_data: Port<u8> = Port::new(0x3C9);
     
-    palette_index.write(0); // Start at color 0
-    
-    // Standard VGA colors (first 16)
-    let standard_colors: [(u8, u8, u8); 16] = [
-        (0x00, 0x00, 0x00), // 0: Black
-        (0x00, 0x00, 0x2A), // 1: Blue
-        (0x00, 0x2A, 0x00), // 2: Green
-        (0x00, 0x2A, 0x2A), // 3: Cyan
-        (0x2A, 0x00, 0x00), // 4: Red
-        (0x2A, 0x00, 0x2A), // 5: Magenta
-        (0x2A, 0x15, 0x00), // 6: Brown
-        (0x2A, 0x2A, 0x2A), // 7: Light Gray
-        (0x15, 0x15, 0x15), // 8: Dark Gray
-        (0x15, 0x15, 0x3F), // 9: Light Blue
-        (0x15, 0x3F, 0x15), // 10: Light Green
-        (0x15, 0x3F, 0x3F), // 11: Light Cyan
-        (0x3F, 0x15, 0x15), // 12: Light Red
-        (0x3F, 0x15, 0x3F), // 13: Pink
-        (0x3F, 0x3F, 0x15), // 14: Yellow
-        (0x3F, 0x3F, 0x3F), // 15: White
-    ];
-    
-    for (r, g, b) in standard_colors.iter() {
-        palette_data.write(*r);
-        palette_data.write(*g);
-        palette_data.write(*b);
-    }
+    // First 16 colors come from the active theme
+    write_palette_16(&THEMES[CURRENT_THEME.load(Ordering::Relaxed)].1);
+    palette_index.write(16);
     
     // Fill remaining colors with a grayscale ramp
     for i in 16u8..=255 {
@@ -725,7 +961,7 @@ lazy_static! {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VgaMode {
     Text,      // Mode 3: 80x25 text mode
-    Graphics,  // Mode 13h: 320x200 graphics mode
+    Graphics,  // Mode 13h (320x200x256) or Mode 12h (640x480x16) - see `geometry()`
 }
 
 lazy_static! {
@@ -733,21 +969,58 @@ lazy_static! {
     pub static ref CURRENT_MODE: Mutex<VgaMode> = Mutex::new(VgaMode::Text);
 }
 
+/// Whether `GRAPHICS` holds a context whose mode switch was verified
+///
+/// Kept outside the `GRAPHICS` lock so `current_mode()` can be called from
+/// inside `with_graphics` without deadlocking.
+static CONTEXT_READY: AtomicBool = AtomicBool::new(false);
+
 /// Initialize graphics system
 /// 
+/// On failure the screen is put back in text mode and the error is returned,
+/// so callers can keep going with the text console.
+/// 
 /// # Safety
 /// Must be called during kernel initialization, after heap is initialized.
-pub unsafe fn init() {
-    serial_println!("[GRAPHICS] Creating graphics context...");
-    let mut graphics = GraphicsContext::new();
-    
+pub unsafe fn init() -> Result<(), &'static str> {
     serial_println!("[GRAPHICS] Initializing Mode 13h...");
-    graphics.init_mode_13h(); // Mode 13h (320x200) with 2x text scaling
+    match enter_graphics_mode() {
+        Ok(()) => {
+            serial_println!("[GRAPHICS] Graphics system ready!");
+            Ok(())
+        }
+        Err(e) => {
+            serial_println!("[GRAPHICS] ERROR: {} - staying in text mode", e);
+            Err(e)
+        }
+    }
+}
+
+/// Program Mode 13h, creating the context if needed, and verify it took
+///
+/// A missing back buffer only costs smooth updates, so that still counts as
+/// success; a framebuffer that doesn't respond does not.
+///
+/// # Safety
+/// Direct hardware access - switches VGA registers.
+unsafe fn enter_graphics_mode() -> Result<(), &'static str> {
+    let mut slot = GRAPHICS.lock();
+    let gfx = slot.get_or_insert_with(|| GraphicsContext::new());
+    gfx.init_mode_13h(); // Mode 13h (320x200) with 2x text scaling
+    if !gfx.framebuffer_responds() {
+        *slot = None;
+        drop(slot);
+        CONTEXT_READY.store(false, Ordering::SeqCst);
+        switch_to_text_mode();
+        return Err("VGA framebuffer did not respond after mode switch");
+    }
+    gfx.double_buffer_or_fallback();
+    drop(slot);
     
-    serial_println!("[GRAPHICS] Storing graphics context...");
-    *GRAPHICS.lock() = Some(graphics);
+    CONTEXT_READY.store(true, Ordering::SeqCst);
     *CURRENT_MODE.lock() = VgaMode::Graphics;
-    serial_println!("[GRAPHICS] Graphics system ready!");
+    super::desktop::mark_dirty();
+    Ok(())
 }
 
 /// Switch VGA to text mode (Mode 3: 80x25)
@@ -755,6 +1028,34 @@ pub unsafe fn init() {
 /// # Safety
 /// Direct hardware access - switches VGA registers.
 pub unsafe fn switch_to_text_mode() {
+    program_text_mode_registers();
+    
+    // Update mode state
+    *CURRENT_MODE.lock() = VgaMode::Text;
+}
+
+/// Put the screen back in text mode for the panic handler
+///
+/// Only port writes and atomics: the code that panicked may hold `GRAPHICS`
+/// or `CURRENT_MODE` forever, so neither is waited on. Clearing
+/// `CONTEXT_READY` is enough for `current_mode()` to report text even when
+/// `CURRENT_MODE` can't be updated.
+///
+/// # Safety
+/// Direct hardware access - switches VGA registers.
+pub unsafe fn force_text_mode() {
+    CONTEXT_READY.store(false, Ordering::SeqCst);
+    program_text_mode_registers();
+    if let Some(mut mode) = CURRENT_MODE.try_lock() {
+        *mode = VgaMode::Text;
+    }
+}
+
+/// Write the Mode 3 register set (no locks taken)
+///
+/// # Safety
+/// Direct hardware access - switches VGA registers.
+unsafe fn program_text_mode_registers() {
     use x86_64::instructions::port::Port;
     
     // VGA Mode 3 (80x25 text mode) register values
@@ -863,59 +1164,72 @@ pub unsafe fn switch_to_text_mode() {
     attr_port.write(0x14); attr_port.write(0x00); // Color select
     
     attr_port.write(0x20); // Enable video
-    
-    // Update mode state
-    *CURRENT_MODE.lock() = VgaMode::Text;
 }
 
 /// Toggle between text and graphics mode
 /// 
 /// # Safety
 /// Direct hardware access - switches VGA registers.
-pub unsafe fn toggle_mode() {
-    let current = *CURRENT_MODE.lock();
-    match current {
-        VgaMode::Text => {
-            // Switch to graphics mode (Mode 12h)
-            // Ensure graphics context exists
-            if GRAPHICS.lock().is_none() {
-                let mut graphics = GraphicsContext::new();
-                graphics.init_mode_13h();
-                *GRAPHICS.lock() = Some(graphics);
-            } else if let Some(ref mut gfx) = GRAPHICS.lock().as_mut() {
-                gfx.init_mode_13h();
-            }
-            // Update mode state
-            *CURRENT_MODE.lock() = VgaMode::Graphics;
-        }
+pub unsafe fn toggle_mode() -> Result<(), &'static str> {
+    match current_mode() {
+        VgaMode::Text => enter_graphics_mode(),
         VgaMode::Graphics => {
-            // Switch to text mode
-            switch_to_text_mode();
             // Mode state is updated in switch_to_text_mode()
+            switch_to_text_mode();
+            Ok(())
         }
     }
 }
 
-/// Switch to graphics mode (Mode 12h)
+/// Switch to graphics mode (Mode 13h)
 /// 
 /// # Safety
 /// Direct hardware access - switches VGA registers.
-pub unsafe fn switch_to_graphics_mode() {
-    // Ensure graphics context exists
-    if GRAPHICS.lock().is_none() {
-        let mut graphics = GraphicsContext::new();
-        graphics.init_mode_13h();
-        *GRAPHICS.lock() = Some(graphics);
-    } else if let Some(ref mut gfx) = GRAPHICS.lock().as_mut() {
-        gfx.init_mode_13h();
-    }
-    // Update mode state
-    *CURRENT_MODE.lock() = VgaMode::Graphics;
+pub unsafe fn switch_to_graphics_mode() -> Result<(), &'static str> {
+    enter_graphics_mode()
 }
 
 /// Get current VGA mode
+///
+/// Never reports `Graphics` unless a verified graphics context exists.
 pub fn current_mode() -> VgaMode {
-    *CURRENT_MODE.lock()
+    match *CURRENT_MODE.lock() {
+        VgaMode::Graphics if CONTEXT_READY.load(Ordering::SeqCst) => VgaMode::Graphics,
+        _ => VgaMode::Text,
+    }
+}
+
+/// Current screen geometry: (width, height, bits per pixel).
+///
+/// In text mode this is the character grid (80x25) at 16 bits per cell.
+pub fn geometry() -> (u32, u32, u8) {
+    match current_mode() {
+        VgaMode::Text => (80, 25, 16),
+        VgaMode::Graphics => match HARDWARE_MODE.load(Ordering::SeqCst) {
+            0x12 => (640, 480, 4),
+            _ => (WIDTH, HEIGHT, 8),
+        },
+    }
+}
+
+/// BIOS mode number of the current screen mode (0x03, 0x12 or 0x13)
+pub fn hardware_mode() -> u8 {
+    match current_mode() {
+        VgaMode::Text => 0x03,
+        VgaMode::Graphics => HARDWARE_MODE.load(Ordering::SeqCst),
+    }
+}
+
+/// Current framebuffer: (address, bytes per row).
+///
+/// Mode 12h is planar, so a row is 640 / 8 bytes in each of its 4 planes.
+pub fn framebuffer_info() -> (usize, u32) {
+    let (width, _, bpp) = geometry();
+    match current_mode() {
+        VgaMode::Text => (TEXT_BUFFER_ADDR, width * 2),
+        VgaMode::Graphics if bpp == 4 => (FRAMEBUFFER_ADDR, width / 8),
+        VgaMode::Graphics => (FRAMEBUFFER_ADDR, width),
+    }
 }
 
 /// Get mutable reference to graphics context
@@ -7,11 +7,13 @@
 //!
 //! - **GraphicsContext**: Main rendering context (like Bevy's rendering context)
 //! - **VGA Graphics Mode**: Mode 13h (320x200x256 colors)
-//! - **Drawing Primitives**: Pixel, rectangle, line, text
+//! - **Drawing Primitives**: Pixel, rectangle, line, circle, text
 //! - **Double Buffering**: Smooth updates
 
 pub mod graphics;
 pub mod desktop;
 pub mod console;
+pub mod dashboard;
+pub mod agent_graph;
 pub mod fonts;
 
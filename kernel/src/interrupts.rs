@@ -21,7 +21,8 @@ use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
 use crate::{println, serial_println};
 use spin::{Lazy, Mutex};
 use pic8259::ChainedPics;
-use pc_keyboard::{layouts, DecodedKey, HandleControl, Keyboard, ScancodeSet1, KeyState};
+use pc_keyboard::{layouts, DecodedKey, HandleControl, Keyboard, ScancodeSet1, KeyState, KeyCode};
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
 // SHELL is accessed via crate::shell::Shell::push_char
 
 /// PIC offset - we remap hardware interrupts to start at 32
@@ -63,18 +64,209 @@ static IDT: Lazy<InterruptDescriptorTable> = Lazy::new(|| {
 pub static PICS: Mutex<ChainedPics> = 
     Mutex::new(unsafe { ChainedPics::new(PIC_1_OFFSET, PIC_2_OFFSET) });
 
+/// PIT input clock in Hz
+const PIT_BASE_HZ: u64 = 1_193_182;
+
+/// PIT channel 0 reload value: the firmware default of 65536 (~18.2 Hz),
+/// which Genesis never reprograms
+const PIT_DIVISOR: u64 = 65_536;
+
+/// Timer interrupts since interrupts were enabled
+static TIMER_TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Timer interrupts since interrupts were enabled
+pub fn timer_ticks() -> u64 {
+    TIMER_TICKS.load(Ordering::Relaxed)
+}
+
+/// Milliseconds since interrupts were enabled, from the PIT tick count
+pub fn uptime_ms() -> u64 {
+    ticks_to_ms(timer_ticks())
+}
+
+/// Convert PIT ticks to milliseconds (rounding down)
+pub fn ticks_to_ms(ticks: u64) -> u64 {
+    ticks * PIT_DIVISOR * 1000 / PIT_BASE_HZ
+}
+
+/// Convert milliseconds to PIT ticks (rounding up, so a nonzero delay is never 0).
+/// Absurdly long delays saturate rather than overflow.
+fn ms_to_ticks(ms: u64) -> u64 {
+    ms.saturating_mul(PIT_BASE_HZ).div_ceil(PIT_DIVISOR * 1000)
+}
+
+/// Default wait before a held key starts repeating (~500 ms)
+const DEFAULT_REPEAT_DELAY_TICKS: u64 = 9;
+
+/// Default wait between repeats of a held key (~110 ms)
+const DEFAULT_REPEAT_INTERVAL_TICKS: u64 = 2;
+
+/// PIT ticks a held key must stay down before its first repeat
+static REPEAT_DELAY_TICKS: AtomicU64 = AtomicU64::new(DEFAULT_REPEAT_DELAY_TICKS);
+
+/// PIT ticks between accepted repeats once a held key is repeating
+static REPEAT_INTERVAL_TICKS: AtomicU64 = AtomicU64::new(DEFAULT_REPEAT_INTERVAL_TICKS);
+
+/// The key currently held down (repeat make codes arrive without a release)
+struct HeldKey {
+    code: KeyCode,
+    /// PIT tick of the last press or repeat let through
+    last_accepted: u64,
+    /// Whether the initial delay has already passed
+    repeating: bool,
+}
+
+static HELD_KEY: Mutex<Option<HeldKey>> = Mutex::new(None);
+
+/// Set the typematic cadence: initial delay and repeat interval in milliseconds
+pub fn set_key_repeat(delay_ms: u64, interval_ms: u64) {
+    REPEAT_DELAY_TICKS.store(ms_to_ticks(delay_ms), Ordering::Relaxed);
+    REPEAT_INTERVAL_TICKS.store(ms_to_ticks(interval_ms), Ordering::Relaxed);
+}
+
+/// Current (delay, interval) in milliseconds, rounded to whole PIT ticks
+pub fn key_repeat() -> (u64, u64) {
+    (ticks_to_ms(REPEAT_DELAY_TICKS.load(Ordering::Relaxed)),
+     ticks_to_ms(REPEAT_INTERVAL_TICKS.load(Ordering::Relaxed)))
+}
+
+/// Filter typematic repeats; returns false for a repeat that arrived too soon
+///
+/// Only the same key pressed again without a release counts as a repeat, so
+/// fast typing of distinct keys (or re-pressing a key) is never suppressed.
+fn accept_key(code: KeyCode, state: KeyState) -> bool {
+    let now = timer_ticks();
+    let mut held = HELD_KEY.lock();
+    match state {
+        KeyState::Up => {
+            if held.as_ref().is_some_and(|h| h.code == code) {
+                *held = None;
+            }
+            true
+        }
+        KeyState::Down => match held.as_mut() {
+            Some(h) if h.code == code => {
+                let wait = if h.repeating {
+                    REPEAT_INTERVAL_TICKS.load(Ordering::Relaxed)
+                } else {
+                    REPEAT_DELAY_TICKS.load(Ordering::Relaxed)
+                };
+                if now.wrapping_sub(h.last_accepted) < wait {
+                    return false;
+                }
+                h.last_accepted = now;
+                h.repeating = true;
+                true
+            }
+            _ => {
+                *held = Some(HeldKey { code, last_accepted: now, repeating: false });
+                true
+            }
+        },
+        KeyState::SingleShot => true,
+    }
+}
+
+/// Whether either Shift key is currently held
+static SHIFT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Non-modifier key presses seen while Shift is held
+static KEYS_WHILE_SHIFTED: AtomicU8 = AtomicU8::new(0);
+
+/// Key presses after which a held Shift is assumed to be a missed release
+const SHIFT_STALE_AFTER: u8 = 32;
+
+/// Track Shift press/release; returns true if the event was a Shift key
+fn track_shift(code: KeyCode, state: KeyState) -> bool {
+    match code {
+        KeyCode::LShift | KeyCode::RShift => {
+            SHIFT_DOWN.store(state == KeyState::Down, Ordering::SeqCst);
+            KEYS_WHILE_SHIFTED.store(0, Ordering::SeqCst);
+            true
+        }
+        _ => {
+            if state == KeyState::Down && SHIFT_DOWN.load(Ordering::SeqCst) {
+                // A release can be lost (e.g. focus change in QEMU); don't stay stuck
+                let count = KEYS_WHILE_SHIFTED.fetch_add(1, Ordering::SeqCst) + 1;
+                if count >= SHIFT_STALE_AFTER {
+                    serial_println!("[KEY] Shift held for {} keys - assuming missed release", count);
+                    SHIFT_DOWN.store(false, Ordering::SeqCst);
+                    KEYS_WHILE_SHIFTED.store(0, Ordering::SeqCst);
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Check whether Shift is currently held
+pub fn shift_down() -> bool {
+    SHIFT_DOWN.load(Ordering::SeqCst)
+}
+
 /// Keyboard decoder - translates scancodes to characters
 /// 
 /// The PS/2 keyboard sends "scancodes" (raw numbers like 0x1E for 'A').
-/// This decoder knows the US keyboard layout and converts them.
-static KEYBOARD: Lazy<Mutex<Keyboard<layouts::Us104Key, ScancodeSet1>>> = Lazy::new(|| {
-    Mutex::new(Keyboard::new(
-        ScancodeSet1::new(),
-        layouts::Us104Key,
-        HandleControl::Ignore,
-    ))
+/// This decoder knows the active keyboard layout (US until `keyboard layout`
+/// picks another) and converts them.
+static KEYBOARD: Lazy<Mutex<Keyboard<layouts::AnyLayout, ScancodeSet1>>> = Lazy::new(|| {
+    Mutex::new(new_keyboard(layouts::AnyLayout::Us104Key(layouts::Us104Key)))
 });
 
+/// Layout names accepted by `keyboard layout <name>`
+pub const KEYBOARD_LAYOUTS: [&str; 10] =
+    ["us", "uk", "de", "azerty", "no", "fi-se", "jis", "colemak", "dvorak", "dvp"];
+
+/// Name of the layout chosen for this session (an entry of `KEYBOARD_LAYOUTS`)
+static ACTIVE_LAYOUT: Mutex<&str> = Mutex::new("us");
+
+/// The `pc_keyboard` layout behind a `KEYBOARD_LAYOUTS` name
+fn layout_by_name(name: &str) -> Option<layouts::AnyLayout> {
+    use layouts::AnyLayout;
+    Some(match name {
+        "us" => AnyLayout::Us104Key(layouts::Us104Key),
+        "uk" => AnyLayout::Uk105Key(layouts::Uk105Key),
+        "de" => AnyLayout::De105Key(layouts::De105Key),
+        "azerty" => AnyLayout::Azerty(layouts::Azerty),
+        "no" => AnyLayout::No105Key(layouts::No105Key),
+        "fi-se" => AnyLayout::FiSe105Key(layouts::FiSe105Key),
+        "jis" => AnyLayout::Jis109Key(layouts::Jis109Key),
+        "colemak" => AnyLayout::Colemak(layouts::Colemak),
+        "dvorak" => AnyLayout::Dvorak104Key(layouts::Dvorak104Key),
+        "dvp" => AnyLayout::DVP104Key(layouts::DVP104Key),
+        _ => return None,
+    })
+}
+
+fn new_keyboard(layout: layouts::AnyLayout) -> Keyboard<layouts::AnyLayout, ScancodeSet1> {
+    Keyboard::new(ScancodeSet1::new(), layout, HandleControl::Ignore)
+}
+
+/// Switch the keyboard decoder to a layout from `KEYBOARD_LAYOUTS`
+/// (case-insensitive). Returns false for an unknown name.
+///
+/// The decoder is rebuilt, so modifier state starts fresh. Interrupts are
+/// off meanwhile so the keyboard handler can't contend for the lock.
+pub fn set_keyboard_layout(name: &str) -> bool {
+    let Some(name) = KEYBOARD_LAYOUTS.into_iter().find(|l| l.eq_ignore_ascii_case(name)) else {
+        return false;
+    };
+    let Some(layout) = layout_by_name(name) else {
+        return false;
+    };
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        *KEYBOARD.lock() = new_keyboard(layout);
+        *ACTIVE_LAYOUT.lock() = name;
+    });
+    serial_println!("[KEYBOARD] Layout switched to {}", name);
+    true
+}
+
+/// Name of the active keyboard layout
+pub fn keyboard_layout() -> &'static str {
+    *ACTIVE_LAYOUT.lock()
+}
+
 /// Initialize the IDT and enable interrupts
 pub fn init() {
     serial_println!("[INIT] Loading Interrupt Descriptor Table...");
@@ -119,12 +311,23 @@ extern "x86-interrupt" fn double_fault_handler(
 // ============================================================================
 
 extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    TIMER_TICKS.fetch_add(1, Ordering::Relaxed);
     // Future: Agent Scheduler would run here
     unsafe {
         PICS.lock().notify_end_of_interrupt(InterruptIndex::Timer.as_u8());
     }
 }
 
+/// Switch between text and graphics mode (Shift+F1 or Shift+Esc)
+fn toggle_vga_mode(key: &str) {
+    serial_println!("[KEY] Shift+{} pressed - toggling VGA mode...", key);
+    if let Err(e) = unsafe { crate::gui::graphics::toggle_mode() } {
+        serial_println!("[MODE] Graphics unavailable: {}", e);
+    }
+    let current = crate::gui::graphics::current_mode();
+    serial_println!("[MODE] Switched to {:?} mode", current);
+}
+
 /// Keyboard interrupt handler
 /// 
 /// This is called every time a key is pressed or released!
@@ -144,6 +347,19 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
     let mut keyboard = KEYBOARD.lock();
     
     if let Ok(Some(key_event)) = keyboard.add_byte(scancode) {
+        // Shift releases must reach the decoder too, or it keeps producing uppercase
+        if track_shift(key_event.code, key_event.state) && key_event.state == KeyState::Up {
+            keyboard.process_keyevent(key_event.clone());
+        }
+
+        // Held keys resend their make code; let repeats through at the typematic cadence
+        if !accept_key(key_event.code, key_event.state) {
+            unsafe {
+                PICS.lock().notify_end_of_interrupt(InterruptIndex::Keyboard.as_u8());
+            }
+            return;
+        }
+
         // CRITICAL: Only process key DOWN events, not releases
         // This prevents duplicate character processing and modifier key noise
         if key_event.state != KeyState::Down {
@@ -153,6 +369,15 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
             return;
         }
         
+        // Any key ends a live view (dashboard or agent graph) and is swallowed
+        if crate::gui::dashboard::exit_on_key() || crate::gui::agent_graph::exit_on_key() {
+            serial_println!("[KEY] Live view closed");
+            unsafe {
+                PICS.lock().notify_end_of_interrupt(InterruptIndex::Keyboard.as_u8());
+            }
+            return;
+        }
+        
         if let Some(key) = keyboard.process_keyevent(key_event) {
             match key {
                 DecodedKey::Unicode(character) => {
@@ -160,6 +385,10 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
                     if character == '\n' || character == '\r' {
                         serial_println!("[KEY] Enter/Return received as Unicode - executing command");
                         crate::shell::Shell::push_char('\n');
+                    } else if character == '\u{1b}' && shift_down() {
+                        // The decoder hands Esc over as Unicode; Shift+Esc is the
+                        // Mac-friendly alternative to Shift+F1 (plain Esc cancels the line)
+                        toggle_vga_mode("Esc");
                     } else {
                         // Send character to shell queue
                         serial_println!("[KEY] Character received: '{}' (U+{:04X})", character, character as u32);
@@ -173,23 +402,15 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
                             // Send backspace character to shell
                             crate::shell::Shell::push_char('\u{08}');
                         }
-                        pc_keyboard::KeyCode::Escape => {
-                            // Toggle between text and graphics mode (Mac-friendly alternative to F1)
-                            serial_println!("[KEY] Escape pressed - toggling VGA mode...");
-                            unsafe {
-                                crate::gui::graphics::toggle_mode();
-                            }
-                            let current = crate::gui::graphics::current_mode();
-                            serial_println!("[MODE] Switched to {:?} mode", current);
+                        pc_keyboard::KeyCode::LShift | pc_keyboard::KeyCode::RShift => {
+                            // Tracked in track_shift()
+                        }
+                        pc_keyboard::KeyCode::F1 if shift_down() => {
+                            // Shift+F1 toggles text/graphics mode
+                            toggle_vga_mode("F1");
                         }
                         pc_keyboard::KeyCode::F1 => {
-                            // Toggle between text and graphics mode
-                            serial_println!("[KEY] F1 pressed - toggling VGA mode...");
-                            unsafe {
-                                crate::gui::graphics::toggle_mode();
-                            }
-                            let current = crate::gui::graphics::current_mode();
-                            serial_println!("[MODE] Switched to {:?} mode", current);
+                            serial_println!("[KEY] F1 pressed - use Shift+F1 to toggle VGA mode");
                         }
                         pc_keyboard::KeyCode::ArrowLeft | 
                         pc_keyboard::KeyCode::ArrowRight |
@@ -235,15 +456,50 @@ extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStac
 /// 
 /// This is called when data arrives on the serial port (COM1).
 extern "x86-interrupt" fn serial_interrupt_handler(_stack_frame: InterruptStackFrame) {
-    use crate::serial::SERIAL1;
-    
-    let mut serial = SERIAL1.lock();
-    while let Some(byte) = serial.try_receive() {
-        // Send character to shell queue
-        crate::shell::Shell::push_char(byte as char);
-    }
+    // Buffer the bytes; the main loop feeds them to the shell at its own pace
+    crate::serial::receive_into_queue();
     
     unsafe {
         PICS.lock().notify_end_of_interrupt(InterruptIndex::Serial1.as_u8());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode Shift + `scancode` with a fresh decoder for the named layout
+    fn shifted(layout: &str, scancode: u8) -> Option<DecodedKey> {
+        let mut keyboard = new_keyboard(layout_by_name(layout).unwrap());
+        let mut decoded = None;
+        for byte in [0x2A, scancode] {
+            if let Ok(Some(event)) = keyboard.add_byte(byte) {
+                decoded = keyboard.process_keyevent(event);
+            }
+        }
+        decoded
+    }
+
+    #[test]
+    fn every_listed_layout_resolves() {
+        assert!(KEYBOARD_LAYOUTS.iter().all(|name| layout_by_name(name).is_some()));
+        assert!(layout_by_name("klingon").is_none());
+    }
+
+    #[test]
+    fn uk_layout_swaps_at_and_double_quote() {
+        // 0x03 is the '2' key, 0x28 the quote key
+        assert_eq!(shifted("us", 0x03), Some(DecodedKey::Unicode('@')));
+        assert_eq!(shifted("us", 0x28), Some(DecodedKey::Unicode('"')));
+        assert_eq!(shifted("uk", 0x03), Some(DecodedKey::Unicode('"')));
+        assert_eq!(shifted("uk", 0x28), Some(DecodedKey::Unicode('@')));
+    }
+
+    #[test]
+    fn huge_key_repeat_delays_saturate() {
+        assert_eq!(ms_to_ticks(0), 0);
+        assert_eq!(ms_to_ticks(1), 1);
+        assert!(ms_to_ticks(20_000_000_000_000) > ms_to_ticks(1_000));
+        assert_eq!(ms_to_ticks(u64::MAX), ms_to_ticks(u64::MAX - 1));
+    }
+}
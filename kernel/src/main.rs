@@ -9,12 +9,14 @@
 #![no_std]  // Don't link the Rust standard library
 #![no_main] // Disable all Rust-level entry points
 #![feature(abi_x86_interrupt)]  // Required for interrupt handlers
+#![feature(alloc_error_handler)] // Custom out-of-memory handler
 
 extern crate alloc;  // Enable heap allocation types (Vec, String, Box)
 
 use alloc::boxed::Box;
 use alloc::string::String;
 use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use bootloader::{BootInfo, entry_point};
 use x86_64::VirtAddr;
 
@@ -28,6 +30,11 @@ mod agents;
 mod shell;
 mod gui;
 mod storage;
+mod power;
+mod rng;
+mod rtc;
+mod cpuid;
+mod event_log;
 
 use agents::supervisor::Supervisor;
 use agents::thomas::Thomas;
@@ -35,33 +42,193 @@ use agents::archimedes::Archimedes;
 use gui::desktop;
 use gui::console;
 
+/// In low-power mode the supervisor ticks only once per this many wakeups
+const LOW_POWER_TICK_STRIDE: u64 = 10;
+
 // Define the kernel entry point
 entry_point!(kernel_main);
 
+/// Whole seconds from `kernel_main` entry to shell start (by the RTC)
+pub static BOOT_SECONDS: AtomicU64 = AtomicU64::new(0);
+
+/// Which boot sequence to run, chosen at compile time with a cargo feature
+///
+/// - `boot-quiet`: skip the boot screen, desktop warm-up and rhythm preview
+/// - `boot-demo`: today's boot plus a scripted rhythm demo
+/// - neither: `Verbose`, the full boot as it has always run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BootProfile {
+    Quiet,
+    Verbose,
+    Demo,
+}
+
+impl BootProfile {
+    /// The profile this kernel was built with
+    const SELECTED: BootProfile = if cfg!(feature = "boot-quiet") {
+        BootProfile::Quiet
+    } else if cfg!(feature = "boot-demo") {
+        BootProfile::Demo
+    } else {
+        BootProfile::Verbose
+    };
+
+    /// Whether boot steps should show themselves off (screens, banners)
+    fn is_showy(self) -> bool {
+        self != BootProfile::Quiet
+    }
+}
+
 /// Kernel entry point - called by the bootloader
 /// 
 /// This is where Genesis awakens.
 fn kernel_main(boot_info: &'static BootInfo) -> ! {
+    let profile = BootProfile::SELECTED;
+    let boot_started = rtc::now();
+
     // Initialize serial port for debug output
     serial::init();
-    
-    // Log to serial (appears in your terminal!)
-    serial_println!("=====================================");
-    serial_println!("  Genesis Kernel - Serial Debug Log");
-    serial_println!("=====================================");
-    serial_println!();
-    serial_println!("[BOOT] Serial port initialized");
-    serial_println!("[BOOT] VGA buffer at 0xb8000");
+    if profile.is_showy() {
+        // Log to serial (appears in your terminal!)
+        serial_println!("=====================================");
+        serial_println!("  Genesis Kernel - Serial Debug Log");
+        serial_println!("=====================================");
+        serial_println!();
+        serial_println!("[BOOT] Serial port initialized");
+        serial_println!("[BOOT] VGA buffer at 0xb8000");
+    } else {
+        serial_println!("[BOOT] Genesis kernel ({:?} boot)", profile);
+    }
     
     // Clear the screen (but don't draw boot screen to text buffer)
     // We'll draw it in graphics mode instead to avoid QEMU display issues
     vga_buffer::clear_screen();
-    serial_println!("[BOOT] Screen cleared");
-    serial_println!("[BOOT] Skipping text-mode boot screen - will render in graphics mode");
+    if profile.is_showy() {
+        serial_println!("[BOOT] Screen cleared");
+        serial_println!("[BOOT] Skipping text-mode boot screen - will render in graphics mode");
+    }
+    
+    init_memory(boot_info, profile);
+    cpuid::log_summary();
+    if profile.is_showy() {
+        show_boot_screen();
+    }
+    
+    // Initialize interrupts (IDT + PIC)
+    interrupts::init();
+    
+    // Seed the pseudo-random generator (PIT counter + RTC)
+    rng::init();
     
+    load_memories();
+    let mut supervisor = start_agents();
+    prepare_desktop(profile);
+    
+    match profile {
+        BootProfile::Quiet => {}
+        BootProfile::Verbose => {
+            // Print agent status
+            supervisor.print_status();
+            run_rhythm_preview(&mut supervisor);
+        }
+        BootProfile::Demo => {
+            supervisor.print_status();
+            run_demo_script(&mut supervisor);
+        }
+    }
+    
+    announce_ready(&supervisor, profile);
+    
+    // The RTC works before interrupts are up, so it can time the whole boot
+    let boot_seconds = (rtc::now().seconds_of_day() + 86_400 - boot_started.seconds_of_day()) % 86_400;
+    BOOT_SECONDS.store(boot_seconds, Ordering::Relaxed);
+    serial_println!("[BOOT] Boot took ~{boot_seconds}s");
+
+    // Initialize shell
+    shell::SHELL.lock().init();
+    
+    // Main loop - handle interrupts and process shell input
+    let mut wakeups: u64 = 0;
+    loop {
+        wakeups += 1;
+
+        // Poll serial input (fallback if interrupts don't fire)
+        // This ensures we get data from the bridge even without serial interrupts
+        serial::poll_receive();
+        
+        // Drain received bytes into the shell queue, leaving the rest buffered
+        // in the serial ring until the shell has room
+        while !shell::Shell::input_full() {
+            match serial::pop_received() {
+                Some(byte) => shell::Shell::push_char(byte as char),
+                None => break,
+            }
+        }
+        
+        // Any keyboard or serial input restores full activity
+        if !shell::INPUT_QUEUE.is_empty() {
+            supervisor.wake();
+        }
+        
+        // Process shell input (characters from keyboard or serial)
+        shell::SHELL.lock().process_input(&mut supervisor);
+        shell::SHELL.lock().check_timeouts(supervisor.current_tick());
+        
+        // Process agent ticks; when idle, tick (and render) only every few wakeups
+        let low_power = supervisor.is_low_power();
+        if low_power && wakeups % LOW_POWER_TICK_STRIDE != 0 {
+            x86_64::instructions::hlt();
+            continue;
+        }
+        supervisor.tick();
+
+        // Periodically re-render desktop in graphics mode to keep console visible
+        // (This ensures console updates even if render wasn't triggered by input)
+        if gui::graphics::current_mode() == gui::graphics::VgaMode::Graphics {
+            if gui::dashboard::is_active() {
+                // Live dashboard owns the screen; it repaints only what changed
+                if supervisor.current_tick() % 10 == 0 {
+                    gui::dashboard::update(&supervisor.agent_overview(), supervisor.current_tick());
+                }
+            } else if gui::agent_graph::is_active() {
+                // The agent graph redraws only when traffic changed
+                if supervisor.current_tick() % 50 == 0 {
+                    gui::agent_graph::update(&supervisor.agent_overview(), supervisor.agent_links());
+                }
+            } else if gui::dashboard::take_exited() | gui::agent_graph::take_exited() {
+                // A key closed a live view - bring the desktop back
+                // (`|` so both flags are taken)
+                gui::desktop::render();
+            } else if gui::desktop::auto_render() {
+                // Auto mode: only redraw when something changed
+                if gui::desktop::needs_render() {
+                    gui::desktop::render();
+                }
+            } else if !low_power {
+                static mut RENDER_COUNTER: u64 = 0;
+                unsafe {
+                    RENDER_COUNTER += 1;
+                    // Re-render more frequently (every 100 ticks) to keep desktop visible
+                    // This prevents the screen from going black
+                    if RENDER_COUNTER % 100 == 0 {
+                        gui::desktop::render();
+                    }
+                }
+            }
+        }
+        
+        // Halt the CPU until the next interrupt
+        x86_64::instructions::hlt();
+    }
+}
+
+/// Page tables, frame allocator and kernel heap
+fn init_memory(boot_info: &'static BootInfo, profile: BootProfile) {
     // Initialize memory management
-    serial_println!();
-    serial_println!("[MEMORY] Initializing memory management...");
+    if profile.is_showy() {
+        serial_println!();
+        serial_println!("[MEMORY] Initializing memory management...");
+    }
     
     let phys_mem_offset = VirtAddr::new(boot_info.physical_memory_offset);
     let mut mapper = unsafe { memory::init(phys_mem_offset) };
@@ -69,15 +236,20 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
         memory::BootInfoFrameAllocator::init(&boot_info.memory_map)
     };
     
-    serial_println!("[MEMORY] Page mapper initialized");
-    serial_println!("[MEMORY] Frame allocator initialized");
+    if profile.is_showy() {
+        serial_println!("[MEMORY] Page mapper initialized");
+        serial_println!("[MEMORY] Frame allocator initialized");
+    }
     
     // Initialize the heap
     allocator::init_heap(&mut mapper, &mut frame_allocator)
         .expect("heap initialization failed");
     
     serial_println!("[MEMORY] Heap initialized");
-    
+}
+
+/// Text-mode boot screen, held briefly, then cleared for the shell
+fn show_boot_screen() {
     // Stay in TEXT MODE for reliable shell input
     // Graphics will be initialized on-demand (e.g., when 'desktop' command is run)
     // This avoids VGA mode switching corruption issues
@@ -94,22 +266,28 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     // Clear screen for shell
     vga_buffer::clear_screen();
     serial_println!("[BOOT] Screen cleared - ready for shell");
-    
-    // Initialize interrupts (IDT + PIC)
-    interrupts::init();
-    
+}
+
+/// Load memories from disk and ask the bridge for its copy and today's ambition
+fn load_memories() {
     // =========================================================================
     // MEMORY STORE INITIALIZATION
     // =========================================================================
 
     serial_println!();
     serial_println!("[MEMORY_STORE] Loading persistent memories...");
+    let session = rtc::now().stamp();
+    crate::storage::memory_store::begin_session(session);
+    serial_println!("[MEMORY_STORE] Session {session}");
     crate::storage::memory_store::load();
     // Request persisted memories from the serial bridge (host disk)
     serial_println!("[MEMORY_REQUEST]");
     // Request today's ambition from the bridge (persisted daily files)
     serial_println!("[AMBITION_REQUEST]");
+}
 
+/// Create the supervisor, register the agents and run the agent-first boot
+fn start_agents() -> Supervisor {
     // =========================================================================
     // AGENT SYSTEM INITIALIZATION
     // =========================================================================
@@ -125,14 +303,26 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     
     // Create agents
     // Archimedes - Daily Ambition Agent (loads ambition, organizes workspace)
-    let archimedes_id = supervisor.next_id();
-    let archimedes = Archimedes::new(archimedes_id);
-    supervisor.register(Box::new(archimedes));
+    match supervisor.try_next_id() {
+        Some(archimedes_id) => {
+            let archimedes = Archimedes::new(archimedes_id);
+            if let Err(e) = supervisor.register(Box::new(archimedes)) {
+                serial_println!("[BOOT] Archimedes not registered: {:?}", e);
+            }
+        }
+        None => serial_println!("[BOOT] Archimedes not registered: agent limit reached"),
+    }
     
     // Thomas - Guardian/Tester Agent
-    let thomas_id = supervisor.next_id();
-    let thomas = Thomas::new(thomas_id);
-    supervisor.register(Box::new(thomas));
+    match supervisor.try_next_id() {
+        Some(thomas_id) => {
+            let thomas = Thomas::new(thomas_id);
+            if let Err(e) = supervisor.register(Box::new(thomas)) {
+                serial_println!("[BOOT] Thomas not registered: {:?}", e);
+            }
+        }
+        None => serial_println!("[BOOT] Thomas not registered: agent limit reached"),
+    }
     
     // =========================================================================
     // AGENT-FIRST BOOT SEQUENCE
@@ -142,7 +332,11 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     // Phase 3: Environment Setup (agents organize before GUI)
     serial_println!();
     supervisor.agent_boot_sequence();
-    
+    supervisor
+}
+
+/// Lay out the desktop; showy profiles also render it a few times up front
+fn prepare_desktop(profile: BootProfile) {
     // =========================================================================
     // DESKTOP LAYOUT - Render organized desktop
     // =========================================================================
@@ -152,8 +346,10 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     serial_println!();
     serial_println!("[DESKTOP] Desktop available on-demand via 'desktop' command");
     console::add_output_line(String::from("Type commands here!"));
-    serial_println!("[CONSOLE] Graphics console overlay initialized");
-    serial_println!("[CONSOLE] Console should appear at bottom of graphics window");
+    if profile.is_showy() {
+        serial_println!("[CONSOLE] Graphics console overlay initialized");
+        serial_println!("[CONSOLE] Console should appear at bottom of graphics window");
+    }
     
     // Get Archimedes's ambition for desktop display
     // For now, we'll create a default layout - in full implementation,
@@ -166,11 +362,15 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
                 String::from("YOU: Set clear goals"),
                 String::from("AI: Support with tools and insights"),
                 String::from("COLLAB: Work together"),
-            ]
+            ],
+            desktop::theme_of_the_day().as_deref(),
         );
     });
     
     serial_println!("[DESKTOP] Desktop layout created");
+    if !profile.is_showy() {
+        return;
+    }
     
     // Render desktop (includes console overlay)
     serial_println!("[DESKTOP] Rendering organized desktop...");
@@ -184,10 +384,10 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
         delay_ms(100); // Small delay between renders
     }
     serial_println!("[DESKTOP] Desktop should now be visible in QEMU window!");
-    
-    // Print agent status
-    supervisor.print_status();
-    
+}
+
+/// Morning ambition, a few ticks and an end-of-day report
+fn run_rhythm_preview(supervisor: &mut Supervisor) {
     // Trigger morning ambition!
     serial_println!();
     supervisor.morning_ambition();
@@ -195,22 +395,39 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     // Run a few ticks
     serial_println!();
     serial_println!("[SUPERVISOR] Running tick loop...");
-    for _ in 0..5 {
-        supervisor.tick();
-    }
+    supervisor.tick_n(5);
     
     // End of day report
     serial_println!();
     supervisor.eod_report();
-    
-    // Update display
-    println!();
-    println!("  Agent Supervisor:    [ ONLINE ]");
-    println!("  Active Agents:       [ {} ]", supervisor.agent_count());
-    println!("  Memory Tier System:  [ ONLINE - Warm Tier ]");
-    println!();
-    println!("  Thomas says: \"All systems nominal!\"");
-    println!();
+}
+
+/// A whole day in miniature: every rhythm phase with ticks in between
+fn run_demo_script(supervisor: &mut Supervisor) {
+    serial_println!();
+    serial_println!("[DEMO] Running scripted day...");
+    supervisor.morning_ambition();
+    supervisor.tick_n(20);
+    supervisor.midday_checkpoint();
+    supervisor.ping_all();
+    supervisor.tick_n(5);
+    supervisor.eod_report();
+    supervisor.nightly_reflection();
+    serial_println!("[DEMO] Scripted day complete");
+}
+
+/// Final boot summary on screen and serial
+fn announce_ready(supervisor: &Supervisor, profile: BootProfile) {
+    if profile.is_showy() {
+        // Update display
+        println!();
+        println!("  Agent Supervisor:    [ ONLINE ]");
+        println!("  Active Agents:       [ {} ]", supervisor.agent_count());
+        println!("  Memory Tier System:  [ ONLINE - Warm Tier ]");
+        println!();
+        println!("  Thomas says: \"All systems nominal!\"");
+        println!();
+    }
     println!("  >> KEYBOARD INPUT ACTIVE - Type something! <<");
 
     serial_println!();
@@ -221,55 +438,6 @@ fn kernel_main(boot_info: &'static BootInfo) -> ! {
     serial_println!("=========================================");
     serial_println!();
     serial_println!("[INFO] Press Ctrl+A, X to exit QEMU");
-
-    // Initialize shell
-    shell::SHELL.lock().init();
-    
-    // Main loop - handle interrupts and process shell input
-    loop {
-        // Poll serial input (fallback if interrupts don't fire)
-        // This ensures we get data from the bridge even without serial interrupts
-        {
-            use crate::serial::SERIAL1;
-            let mut serial = SERIAL1.lock();
-            while let Some(byte) = serial.try_receive() {
-                // Send character to shell queue
-                crate::shell::Shell::push_char(byte as char);
-            }
-        }
-        
-        // Process shell input (characters from keyboard or serial)
-        shell::SHELL.lock().process_input(&mut supervisor);
-        
-        // Process agent ticks
-        supervisor.tick();
-        
-        // Periodically auto-save memory store
-        static mut MEMORY_SAVE_COUNTER: u64 = 0;
-        unsafe {
-            MEMORY_SAVE_COUNTER += 1;
-            if MEMORY_SAVE_COUNTER % 10000 == 0 {
-                crate::storage::memory_store::save();
-            }
-        }
-
-        // Periodically re-render desktop in graphics mode to keep console visible
-        // (This ensures console updates even if render wasn't triggered by input)
-        if gui::graphics::current_mode() == gui::graphics::VgaMode::Graphics {
-            static mut RENDER_COUNTER: u64 = 0;
-            unsafe {
-                RENDER_COUNTER += 1;
-                // Re-render more frequently (every 100 ticks) to keep desktop visible
-                // This prevents the screen from going black
-                if RENDER_COUNTER % 100 == 0 {
-                    gui::desktop::render();
-                }
-            }
-        }
-        
-        // Halt the CPU until the next interrupt
-        x86_64::instructions::hlt();
-    }
 }
 
 /// Simple delay function using busy-wait loop
@@ -291,15 +459,27 @@ fn delay_ms(milliseconds: u64) {
 
 /// Display the boot screen with ASCII art and status
 fn display_boot_screen() {
+    use vga_buffer::Color;
+
+    const BANNER: [&str; 6] = [
+        "   ██████╗ ███████╗███╗   ██╗███████╗███████╗██╗███████╗",
+        "  ██╔════╝ ██╔════╝████╗  ██║██╔════╝██╔════╝██║██╔════╝",
+        "  ██║  ███╗█████╗  ██╔██╗ ██║█████╗  ███████╗██║███████╗",
+        "  ██║   ██║██╔══╝  ██║╚██╗██║██╔══╝  ╚════██║██║╚════██║",
+        "  ╚██████╔╝███████╗██║ ╚████║███████╗███████║██║███████║",
+        "   ╚═════╝ ╚══════╝╚═╝  ╚═══╝╚══════╝╚══════╝╚═╝╚══════╝",
+    ];
+    // Light to deep blue, top to bottom
+    const GRADIENT: [Color; 6] = [
+        Color::White, Color::LightCyan, Color::Cyan, Color::LightBlue, Color::LightBlue, Color::Blue,
+    ];
+
     println!();
     println!("================================================================");
     println!();
-    println!("   ██████╗ ███████╗███╗   ██╗███████╗███████╗██╗███████╗");
-    println!("  ██╔════╝ ██╔════╝████╗  ██║██╔════╝██╔════╝██║██╔════╝");
-    println!("  ██║  ███╗█████╗  ██╔██╗ ██║█████╗  ███████╗██║███████╗");
-    println!("  ██║   ██║██╔══╝  ██║╚██╗██║██╔══╝  ╚════██║██║╚════██║");
-    println!("  ╚██████╔╝███████╗██║ ╚████║███████╗███████║██║███████║");
-    println!("   ╚═════╝ ╚══════╝╚═╝  ╚═══╝╚══════╝╚══════╝╚═╝╚══════╝");
+    for (line, color) in BANNER.iter().zip(GRADIENT) {
+        vga_buffer::with_color(color, Color::Black, || println!("{}", line));
+    }
     println!();
     println!("           A G E N T I C   O P E R A T I N G");
     println!("                   S Y S T E M");
@@ -314,24 +494,118 @@ fn display_boot_screen() {
     println!("================================================================");
 }
 
+/// Panics while already handling this many stop at a bare halt
+const MAX_PANIC_DEPTH: usize = 2;
+
 /// Panic handler - called when a panic occurs
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    // Print to both VGA and serial so we definitely see it
-    println!();
-    println!("================================================================");
-    println!("  KERNEL PANIC - Genesis encountered an error");
-    println!("================================================================");
-    println!();
-    println!("  {}", info);
+    use vga_buffer::{panic_println, Color};
     
-    serial_println!();
-    serial_println!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!");
-    serial_println!("  KERNEL PANIC");
-    serial_println!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!");
-    serial_println!("{}", info);
+    // No interrupt handler may run (and take a lock) from here on
+    x86_64::instructions::interrupts::disable();
     
-    loop {
-        x86_64::instructions::hlt();
+    // A panic inside this handler re-enters it; report once more, then give up
+    static PANIC_DEPTH: AtomicUsize = AtomicUsize::new(0);
+    let depth = PANIC_DEPTH.fetch_add(1, Ordering::SeqCst);
+    if depth >= MAX_PANIC_DEPTH {
+        power::halt_forever();
+    }
+    if depth > 0 {
+        serial::panic_println(format_args!("[PANIC] Panicked again while handling a panic: {info}"));
+        power::halt_forever();
+    }
+    
+    // The panic may have come from desktop rendering with `GRAPHICS` held:
+    // restore text mode with raw register writes so the banner is readable
+    unsafe { gui::graphics::force_text_mode() };
+    
+    // Print to both VGA and serial so we definitely see it; neither waits
+    // on a lock the panicking code might hold
+    let banner = |text: &str| panic_println(Color::White, Color::Red, format_args!("{text}"));
+    panic_println(Color::Cyan, Color::Black, format_args!(""));
+    banner("================================================================");
+    banner("  KERNEL PANIC - Genesis encountered an error");
+    banner("================================================================");
+    panic_println(Color::Cyan, Color::Black, format_args!("\n  {info}"));
+    
+    serial::panic_println(format_args!("\n!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!"));
+    serial::panic_println(format_args!("  KERNEL PANIC"));
+    serial::panic_println(format_args!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!"));
+    serial::panic_println(format_args!("{info}"));
+    
+    // The dump uses plain serial output; skip it if the port is held for good
+    if serial::SERIAL1.try_lock().is_some() {
+        dump_crash_context();
+    } else {
+        serial::panic_println(format_args!("[PANIC] Serial port held by the panicking code - skipping crash dump"));
+    }
+    
+    power::halt_forever();
+}
+
+/// Heap exhausted: say so plainly, try to save memories, and halt
+///
+/// The final persist needs to allocate; if that fails too we re-enter here
+/// and skip straight to the halt.
+#[alloc_error_handler]
+fn out_of_memory(layout: core::alloc::Layout) -> ! {
+    use vga_buffer::{panic_println, Color};
+    
+    // Like a panic: no interrupt handler may run (and take a lock) from here
+    // on, and the failed allocation may have come with a print lock held
+    x86_64::instructions::interrupts::disable();
+    
+    let banner = |text: &str| panic_println(Color::White, Color::Red, format_args!("{text}"));
+    panic_println(Color::Cyan, Color::Black, format_args!(""));
+    banner("================================================================");
+    panic_println(Color::White, Color::Red,
+        format_args!("  OUT OF MEMORY - could not allocate {} bytes", layout.size()));
+    banner("================================================================");
+    serial::panic_println(format_args!("\n!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!"));
+    serial::panic_println(format_args!("  OUT OF MEMORY ({} bytes, align {}, heap {} KiB)",
+        layout.size(), layout.align(), allocator::HEAP_SIZE / 1024));
+    serial::panic_println(format_args!("!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!"));
+    
+    static PERSISTING: AtomicBool = AtomicBool::new(false);
+    if !PERSISTING.swap(true, Ordering::SeqCst) {
+        serial::panic_println(format_args!("[OOM] Attempting final memory persist..."));
+        // The persist prints through the locking serial port, like the crash dump
+        if serial::SERIAL1.try_lock().is_none() {
+            serial::panic_println(format_args!("[OOM] Serial port held - final persist skipped"));
+        } else if !storage::memory_store::try_persist_to_serial() {
+            serial::panic_println(format_args!("[OOM] Memory store locked - final persist skipped"));
+        }
+    } else {
+        serial::panic_println(format_args!("[OOM] Final persist ran out of memory too"));
+    }
+    
+    panic_println(Color::Cyan, Color::Black,
+        format_args!("  Genesis has halted. Memories were sent to the bridge if possible."));
+    power::halt_forever();
+}
+
+/// Best-effort post-mortem: supervisor counters, recent memories, and a final
+/// memory persist. Locks are only try-locked, so a lock held by the panicking
+/// code skips that step rather than deadlocking.
+fn dump_crash_context() {
+    use agents::supervisor::PANIC_CONTEXT;
+    use storage::memory_store;
+    
+    let insights = &PANIC_CONTEXT.insights;
+    serial_println!("[PANIC] Tick {}, {} agents", 
+        PANIC_CONTEXT.tick.load(Ordering::Relaxed),
+        PANIC_CONTEXT.agents.load(Ordering::Relaxed));
+    serial_println!("[PANIC] Insights: {} sparks, {} connections, {} resources, {} feelings",
+        insights[0].load(Ordering::Relaxed),
+        insights[1].load(Ordering::Relaxed),
+        insights[2].load(Ordering::Relaxed),
+        insights[3].load(Ordering::Relaxed));
+    
+    if !memory_store::panic_dump(5) {
+        serial_println!("[PANIC] Memory store locked - skipping dump");
+    }
+    if !memory_store::try_persist_to_serial() {
+        serial_println!("[PANIC] Memory store locked - final persist skipped");
     }
 }
@@ -0,0 +1,35 @@
+//! Power Management
+//!
+//! Clean exit for the `shutdown` command. Under QEMU with an
+//! `isa-debug-exit` device at port 0xf4 the write terminates the emulator;
+//! everywhere else the CPU is parked in a `hlt` loop.
+
+use x86_64::instructions::port::Port;
+
+/// I/O port of QEMU's isa-debug-exit device
+const QEMU_EXIT_PORT: u16 = 0xf4;
+
+/// Exit code written to the debug-exit device (QEMU exits with `(code << 1) | 1`)
+const QEMU_EXIT_SUCCESS: u32 = 0x10;
+
+/// Halt the CPU forever
+pub fn halt_forever() -> ! {
+    loop {
+        x86_64::instructions::hlt();
+    }
+}
+
+/// Power off the machine
+///
+/// The debug-exit port is only touched under a hypervisor so real hardware
+/// never sees a write to an unknown device. If the device isn't configured
+/// the write is ignored and we fall through to the halt loop.
+pub fn power_off() -> ! {
+    if crate::cpuid::has_feature(crate::cpuid::Feature::Hypervisor) {
+        unsafe {
+            let mut port: Port<u32> = Port::new(QEMU_EXIT_PORT);
+            port.write(QEMU_EXIT_SUCCESS);
+        }
+    }
+    halt_forever()
+}
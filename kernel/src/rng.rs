@@ -0,0 +1,92 @@
+//! Pseudo-Random Numbers for Genesis
+//!
+//! A tiny xorshift64 generator so agents can vary their behavior without a
+//! `rand` crate. Not cryptographic - just enough to pick a tip, phrase an
+//! ambition differently, or split experiment trials between arms.
+//!
+//! The state is seeded at boot from the PIT counter and the RTC clock, so
+//! each boot differs. Call `seed()` with a fixed value to make runs
+//! reproducible.
+
+use spin::Mutex;
+use x86_64::instructions::port::Port;
+
+/// Generator state (xorshift64 must never be zero)
+static STATE: Mutex<u64> = Mutex::new(0x9E37_79B9_7F4A_7C15);
+
+/// Advance a xorshift64 state and return the new value
+fn xorshift64(state: &mut u64) -> u64 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    *state = x;
+    x
+}
+
+/// Read the current PIT channel 0 counter
+fn read_pit_counter() -> u16 {
+    let mut command: Port<u8> = Port::new(0x43);
+    let mut channel0: Port<u8> = Port::new(0x40);
+    unsafe {
+        command.write(0x00); // Latch channel 0
+        let lo = channel0.read() as u16;
+        let hi = channel0.read() as u16;
+        (hi << 8) | lo
+    }
+}
+
+/// Seed the generator from the PIT counter and RTC time
+pub fn init() {
+    let pit = read_pit_counter() as u64;
+    // Seconds, minutes, hours, day of month
+    let read_rtc = crate::rtc::read_register;
+    let rtc = read_rtc(0x00) as u64
+        | (read_rtc(0x02) as u64) << 8
+        | (read_rtc(0x04) as u64) << 16
+        | (read_rtc(0x07) as u64) << 24;
+    seed((rtc << 16) | pit);
+    crate::serial_println!("[RNG] Seeded (pit=0x{:04X}, rtc=0x{:08X})", pit, rtc);
+}
+
+/// Set the generator state (a zero seed is replaced with a fixed constant)
+pub fn seed(value: u64) {
+    *STATE.lock() = if value == 0 { 0x9E37_79B9_7F4A_7C15 } else { value };
+}
+
+/// Next pseudo-random 64-bit value
+pub fn next_u64() -> u64 {
+    xorshift64(&mut STATE.lock())
+}
+
+/// Pseudo-random value in `lo..hi` (returns `lo` if the range is empty)
+pub fn range(lo: u64, hi: u64) -> u64 {
+    if hi <= lo {
+        return lo;
+    }
+    lo + next_u64() % (hi - lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_seed_gives_known_sequence() {
+        let mut state = 1;
+        assert_eq!(xorshift64(&mut state), 1082269761);
+        assert_eq!(xorshift64(&mut state), 1152992998833853505);
+        assert_eq!(xorshift64(&mut state), 11177516664432764457);
+        assert_eq!(xorshift64(&mut state), 17678023832001937445);
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        seed(42);
+        for _ in 0..100 {
+            let v = range(10, 20);
+            assert!((10..20).contains(&v));
+        }
+        assert_eq!(range(5, 5), 5);
+    }
+}
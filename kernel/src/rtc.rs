@@ -0,0 +1,114 @@
+//! Real-Time Clock for Genesis
+//!
+//! Reads wall-clock time from the CMOS RTC (ports 0x70/0x71). The RTC may
+//! report BCD or binary values and 12- or 24-hour time depending on status
+//! register B; `now()` normalizes both to a plain 24-hour `DateTime`.
+//!
+//! The time is whatever the host/firmware set - under QEMU that is the host's
+//! local time unless `-rtc base=utc` is given.
+
+use x86_64::instructions::port::Port;
+
+/// Status register A (bit 7 = update in progress)
+const STATUS_A: u8 = 0x0A;
+/// Status register B (bit 1 = 24-hour, bit 2 = binary)
+const STATUS_B: u8 = 0x0B;
+
+/// Wall-clock date and time (24-hour)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// The calendar date, for "once per day" bookkeeping
+    pub fn date(&self) -> (u16, u8, u8) {
+        (self.year, self.month, self.day)
+    }
+
+    /// Packed as the decimal number `YYYYMMDDhhmmss` (sortable, human-readable)
+    pub fn stamp(&self) -> u64 {
+        let date = self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64;
+        let time = self.hour as u64 * 10_000 + self.minute as u64 * 100 + self.second as u64;
+        date * 1_000_000 + time
+    }
+
+    /// Seconds since midnight
+    pub fn seconds_of_day(&self) -> u64 {
+        self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64
+    }
+}
+
+/// Read a CMOS RTC register
+pub fn read_register(register: u8) -> u8 {
+    let mut index: Port<u8> = Port::new(0x70);
+    let mut data: Port<u8> = Port::new(0x71);
+    unsafe {
+        index.write(register);
+        data.read()
+    }
+}
+
+/// Raw (undecoded) time registers: sec, min, hour, day, month, year
+fn read_raw() -> [u8; 6] {
+    // Wait out an update in progress (bounded, the RTC updates in <2ms)
+    for _ in 0..10_000 {
+        if read_register(STATUS_A) & 0x80 == 0 {
+            break;
+        }
+    }
+    [
+        read_register(0x00),
+        read_register(0x02),
+        read_register(0x04),
+        read_register(0x07),
+        read_register(0x08),
+        read_register(0x09),
+    ]
+}
+
+fn bcd_to_binary(value: u8) -> u8 {
+    (value & 0x0F) + (value >> 4) * 10
+}
+
+/// Current wall-clock time
+pub fn now() -> DateTime {
+    // Read until two consecutive reads agree, so we never mix two seconds
+    let mut raw = read_raw();
+    for _ in 0..4 {
+        let again = read_raw();
+        if again == raw {
+            break;
+        }
+        raw = again;
+    }
+
+    let status_b = read_register(STATUS_B);
+    let binary = status_b & 0x04 != 0;
+    let twenty_four_hour = status_b & 0x02 != 0;
+
+    let decode = |v: u8| if binary { v } else { bcd_to_binary(v) };
+    let pm = raw[2] & 0x80 != 0;
+    let mut hour = decode(raw[2] & 0x7F);
+    if !twenty_four_hour {
+        // 12-hour clock: 12 AM is 0, 12 PM stays 12
+        hour %= 12;
+        if pm {
+            hour += 12;
+        }
+    }
+
+    DateTime {
+        year: 2000 + decode(raw[5]) as u16,
+        month: decode(raw[4]),
+        day: decode(raw[3]),
+        hour,
+        minute: decode(raw[1]),
+        second: decode(raw[0]),
+    }
+}
@@ -16,17 +16,80 @@
 //! 
 //! In the agentic future, serial could be one channel agents use to
 //! communicate with external systems - a simple, reliable protocol.
+//!
+//! ## Notifications
+//!
+//! Lines meant for the bridge to act on (e.g. `[TELEGRAM_REPLY]`) go through
+//! `notify!`, which a token bucket caps at `NOTIFY_PER_SECOND` so a busy
+//! stretch can't flood the bridge faster than it reads. Excess lines are
+//! dropped and counted. `serial_println!` debug output is never throttled.
 
-use spin::Mutex;
+use spin::{Mutex, Lazy};
 use x86_64::instructions::port::Port;
 use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use crossbeam_queue::ArrayQueue;
 
 /// COM1 base port address
 const COM1: u16 = 0x3F8;
 
+/// Capacity of the receive ring buffer (bytes)
+const RX_QUEUE_SIZE: usize = 512;
+
 /// Global serial port instance
 pub static SERIAL1: Mutex<SerialPort> = Mutex::new(SerialPort::new(COM1));
 
+/// Bytes received from COM1, waiting for the main loop to hand them to the shell
+static RX_QUEUE: Lazy<ArrayQueue<u8>> = Lazy::new(|| ArrayQueue::new(RX_QUEUE_SIZE));
+
+/// Bytes accepted into the receive queue
+static RX_RECEIVED: AtomicU64 = AtomicU64::new(0);
+
+/// Bytes lost because the receive queue was full
+static RX_DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Sustained `notify!` rate, and the burst allowed after a quiet spell
+pub const NOTIFY_PER_SECOND: u64 = 10;
+
+/// Token bucket shared by every `notify!`
+static NOTIFY_BUCKET: Mutex<TokenBucket> = Mutex::new(TokenBucket::new(NOTIFY_PER_SECOND));
+
+/// Notifications written to the port
+static NOTIFY_SENT: AtomicU64 = AtomicU64::new(0);
+
+/// Notifications dropped by the rate limit
+static NOTIFY_DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Token bucket refilled from elapsed milliseconds
+///
+/// Tokens are kept in thousandths so refills between PIT ticks (~55 ms)
+/// aren't lost to rounding.
+pub struct TokenBucket {
+    per_second: u64,
+    milli_tokens: u64,
+    last_ms: u64,
+}
+
+impl TokenBucket {
+    /// A full bucket allowing `per_second` tokens per second
+    pub const fn new(per_second: u64) -> Self {
+        TokenBucket { per_second, milli_tokens: per_second * 1000, last_ms: 0 }
+    }
+
+    /// Take one token at time `now_ms`; false if the bucket is empty
+    pub fn try_take(&mut self, now_ms: u64) -> bool {
+        let elapsed = now_ms.saturating_sub(self.last_ms);
+        self.last_ms = now_ms;
+        let capacity = self.per_second * 1000;
+        self.milli_tokens = (self.milli_tokens + elapsed.saturating_mul(self.per_second)).min(capacity);
+        if self.milli_tokens < 1000 {
+            return false;
+        }
+        self.milli_tokens -= 1000;
+        true
+    }
+}
+
 /// A serial port for text output
 pub struct SerialPort {
     /// Data port - where we send/receive bytes
@@ -159,6 +222,54 @@ pub fn init() {
     SERIAL1.lock().init();
 }
 
+/// Move every byte waiting in the UART into the receive queue
+///
+/// Called from the serial interrupt handler. Uses its own port handle rather
+/// than locking `SERIAL1`, so an interrupt arriving mid-`serial_println!`
+/// can't deadlock.
+pub fn receive_into_queue() {
+    let mut port = SerialPort::new(COM1);
+    while let Some(byte) = port.try_receive() {
+        if RX_QUEUE.push(byte).is_ok() {
+            RX_RECEIVED.fetch_add(1, Ordering::Relaxed);
+        } else {
+            RX_DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Poll the UART from the main loop (fallback if serial interrupts don't fire)
+pub fn poll_receive() {
+    x86_64::instructions::interrupts::without_interrupts(receive_into_queue);
+}
+
+/// Take the next received byte, if any
+pub fn pop_received() -> Option<u8> {
+    RX_QUEUE.pop()
+}
+
+/// Receive queue statistics
+pub struct RxStats {
+    /// Bytes accepted into the queue since boot
+    pub received: u64,
+    /// Bytes dropped because the queue was full
+    pub dropped: u64,
+    /// Bytes currently waiting in the queue
+    pub queued: usize,
+    /// Queue capacity
+    pub capacity: usize,
+}
+
+/// Get receive queue statistics
+pub fn rx_stats() -> RxStats {
+    RxStats {
+        received: RX_RECEIVED.load(Ordering::Relaxed),
+        dropped: RX_DROPPED.load(Ordering::Relaxed),
+        queued: RX_QUEUE.len(),
+        capacity: RX_QUEUE_SIZE,
+    }
+}
+
 /// Print to serial port (internal function)
 #[doc(hidden)]
 pub fn _print(args: fmt::Arguments) {
@@ -166,6 +277,36 @@ pub fn _print(args: fmt::Arguments) {
     SERIAL1.lock().write_fmt(args).unwrap();
 }
 
+/// Print a line for the panic handler, never waiting on `SERIAL1`: if the
+/// code that panicked holds it, a second handle on COM1 writes instead
+pub fn panic_println(args: fmt::Arguments) {
+    use core::fmt::Write;
+    match SERIAL1.try_lock() {
+        Some(mut serial) => {
+            let _ = writeln!(serial, "{args}");
+        }
+        None => {
+            let _ = writeln!(SerialPort::new(COM1), "{args}");
+        }
+    }
+}
+
+/// Send a rate-limited notification line (internal function)
+#[doc(hidden)]
+pub fn _notify(args: fmt::Arguments) {
+    if NOTIFY_BUCKET.lock().try_take(crate::interrupts::uptime_ms()) {
+        NOTIFY_SENT.fetch_add(1, Ordering::Relaxed);
+        _print(format_args!("{args}\n"));
+    } else {
+        NOTIFY_DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Notifications (sent, dropped by the rate limit) since boot
+pub fn notify_stats() -> (u64, u64) {
+    (NOTIFY_SENT.load(Ordering::Relaxed), NOTIFY_DROPPED.load(Ordering::Relaxed))
+}
+
 /// Print to serial port (like print! but goes to terminal)
 #[macro_export]
 macro_rules! serial_print {
@@ -179,3 +320,29 @@ macro_rules! serial_println {
     ($($arg:tt)*) => ($crate::serial_print!("{}\n", format_args!($($arg)*)));
 }
 
+/// Print a line meant for the bridge, dropped if over the notification rate
+#[macro_export]
+macro_rules! notify {
+    ($($arg:tt)*) => ($crate::serial::_notify(format_args!($($arg)*)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_drops_bursts_and_refills() {
+        let mut bucket = TokenBucket::new(2);
+        assert!(bucket.try_take(0));
+        assert!(bucket.try_take(0));
+        assert!(!bucket.try_take(0));
+        // Half a second refills one token at 2 per second
+        assert!(!bucket.try_take(400));
+        assert!(bucket.try_take(500));
+        // A long quiet spell refills only up to the burst size
+        assert!(bucket.try_take(60_000));
+        assert!(bucket.try_take(60_000));
+        assert!(!bucket.try_take(60_000));
+    }
+}
+
@@ -8,7 +8,9 @@ use alloc::vec::Vec;
 use alloc::vec;
 use alloc::format;
 use crate::{print, println};
-use crate::agents::supervisor::Supervisor;
+use crate::agents::supervisor::{Supervisor, TelegramRoute};
+use crate::agents::message::AnnounceLevel;
+use crate::agents::thomas::TEST_SUITES;
 use spin::{Mutex, Lazy};
 use crossbeam_queue::ArrayQueue;
 
@@ -23,8 +25,10 @@ macro_rules! shell_print {
     };
     ($($arg:tt)*) => {
         {
-            println!($($arg)*);
-            serial_println!($($arg)*);
+            // Color markers (see vga_buffer) on VGA, plain text on serial
+            let line = alloc::format!($($arg)*);
+            crate::vga_buffer::println_marked(&line);
+            serial_println!("{}", crate::vga_buffer::strip_markers(&line));
         }
     };
 }
@@ -42,6 +46,150 @@ macro_rules! shell_print_no_nl {
 /// The maximum length of a command line
 const MAX_COMMAND_LEN: usize = 128;
 
+/// Ticks to wait for an [LLM_RESPONSE] before giving up on a request (~30s)
+const LLM_TIMEOUT_TICKS: u64 = 3_000;
+
+/// A bridge response within this many ticks counts as "recent" (~2 min)
+const BRIDGE_RECENT_TICKS: u64 = 12_000;
+
+/// Completion time recorded for each simulated success in `cert simulate` (~1s)
+const CERT_SIM_COMPLETION_TICKS: u64 = 100;
+
+/// PRNG seed for `demo`, so agents' random choices repeat from run to run
+const DEMO_SEED: u64 = 0x6E6E_5E51_5DE7_0001;
+
+/// Ticks `demo` runs the agents between steps: one per PIT interrupt
+/// (~55 ms), so ~1s
+const DEMO_PAUSE_TICKS: u64 = 18;
+
+/// Line prefixes the bridge sends *to* the shell; `serial send` refuses them
+/// so an echoed line can't be mistaken for a real bridge response
+const INCOMING_PREFIXES: &[&str] = &[
+    "[LLM_RESPONSE]", "[TELEGRAM]", "[TELEGRAM_REPLY]", "[MEMORY_LOAD]",
+    "[MEMORY_LOAD_DONE]", "[AMBITION_LOAD]", "[AMBITION_HISTORY]",
+];
+
+/// Usage and one-line description of every command (`help`, `help --search`)
+const HELP: &[(&str, &str)] = &[
+    ("help", "Show this help message"),
+    ("help --search <term>", "Find commands whose name or description mentions a term"),
+    ("clear [--scrollback]", "Clear the screen and the graphics console history"),
+    ("console echo <text>", "Add a line to the graphics console (GUI testing)"),
+    ("console tabs <n>", "Set the graphics console's tab stop"),
+    ("status", "Show agent status"),
+    ("log [n]", "Show the last n events (default 20)"),
+    ("status --watch", "Live agent dashboard (graphics mode, any key exits)"),
+    ("agents graph", "Live graph of who messages whom (graphics mode, any key exits)"),
+    ("health", "Show each agent's health report"),
+    ("roster", "Table of agents: id, state, role, certification, write tier"),
+    ("routes [--reset]", "Show (or reset) per-agent message counts"),
+    ("traffic [--reset]", "Show (or reset) messages routed by kind"),
+    ("agents [--limit <n>]", "Show agent count (or set the agent limit)"),
+    ("agent log <name>", "Show one agent's recent activity"),
+    ("agent resume <name>", "Resume an agent the tick watchdog paused"),
+    ("announcements", "Show agents' recent status announcements"),
+    ("academy", "Show Academy certifications"),
+    ("cert simulate <char_id> <invocations> <success%>", "Feed synthetic metrics and check certification"),
+    ("ping [name]", "Ping all agents (or one) and report round-trip ticks"),
+    ("ambition", "Trigger morning ambitions"),
+    ("ambition edit", "Set the ambition through Archimedes's 4-phase interview"),
+    ("midday", "Trigger midday checkpoint"),
+    ("report", "Trigger end-of-day report"),
+    ("reflect", "Trigger nightly reflection"),
+    ("schedule [--show]", "Show the RTC-driven daily rhythm"),
+    ("schedule morning <hour>", "Set the morning ambition hour"),
+    ("shutdown", "Stop all agents, save memory, and power off"),
+    ("thomas", "Talk to Thomas specifically"),
+    ("capabilities <name>", "Show what an agent can do"),
+    ("workspace", "Show Archimedes's ambition, commitments and folders"),
+    ("subscriptions <name>", "Show which broadcasts an agent receives"),
+    ("query <agent> <topic>", "Ask an agent a structured query"),
+    ("spark <content> | <context>", "Add your own Spark to the constellation"),
+    ("connect <from> | <to> | <pattern>", "Add a manual Connection"),
+    ("evolution list", "Show prompt evolution experiments"),
+    ("evolution start <char_id> <hypothesis>", "Start an A/B experiment"),
+    ("evolution conclude <id>", "Conclude an experiment and show the result"),
+    ("uptime", "Show time since boot and supervisor ticks"),
+    ("cpuinfo", "Show the CPU vendor and feature flags (CPUID)"),
+    ("whoami", "Show current user info"),
+    ("breathe [text]", "Set the living ambition (the soul)"),
+    ("breathe --history", "List past living ambitions"),
+    ("breathe --restore <n>", "Breathe past ambition <n> in again"),
+    ("heartbeat", "View current ambition pulse"),
+    ("heartbeat --status", "Show each agent's last heartbeat ack"),
+    ("insights", "View collected Sparks and Connections"),
+    ("insights --filter <kind>", "Only spark|connection|resource|feeling"),
+    ("insights --summary", "Show only the counts per kind"),
+    ("keyrepeat [delay interval]", "Show or set held-key repeat timing (ms)"),
+    ("keyboard layout [name]", "Show or switch the keyboard layout (us, uk, de, ...)"),
+    ("scout video [path]", "Request video analysis (via bridge)"),
+    ("test", "Trigger Thomas to run tests and send a Spark"),
+    ("demo", "Run a scripted multi-agent scenario (reproducible)"),
+    ("test --verbose", "Run tests and print each individual result"),
+    ("test --suite <name>", "Run one suite (alloc|string|math|memory), printing each result"),
+    ("haiku", "Ask TypeWrite to generate a haiku (tests LLM connection)"),
+    ("bridge status", "Show outstanding LLM requests and last bridge response"),
+    ("snapshot save|load", "Save or restore supervisor and agent state"),
+    ("graphics", "Test graphics rendering (draw test pattern)"),
+    ("graphics stipple", "Draw stipple fill patterns side by side"),
+    ("graphics logo", "Blit the Genesis logo (opaque, keyed, clipped)"),
+    ("graphics panic", "Panic inside a graphics closure (tests the panic screen)"),
+    ("archimedes", "Talk to Archimedes (Daily Ambition Agent)"),
+    ("desktop", "Show split-screen desktop (Conversation + Ambition)"),
+    ("desktop layout [name]", "Switch desktop arrangement (split, focus, dashboard, journal)"),
+    ("desktop theme [name]", "Switch desktop color palette (classic, amber, matrix-green)"),
+    ("protection", "Show protection tier summary and agent access levels"),
+    ("protection check <path> <change>", "Preview tier and ceremony (read|modify|create|delete|recommend|secret)"),
+    ("memory search <q> [page]", "Search memory (10 results per page)"),
+    ("memory list [--json]", "Show recent memory entries (last 10)"),
+    ("memory by-source <source>", "Show everything one source stored"),
+    ("memory sessions", "List boot sessions with entry counts"),
+    ("memory by-session <id>", "Show what one boot session stored"),
+    ("memory stats [--json]", "Show memory store statistics"),
+    ("memory agents", "Estimated heap footprint of each agent's buffers"),
+    ("memory keywords [n]", "Keyword frequency heatmap (default 20)"),
+    ("memory get <id> [--json]", "Show full details of a memory entry (--json: for tooling)"),
+    ("memory why <id1> <id2>", "Explain how two memories are related (shared keywords)"),
+    ("memory importance [n]", "Rank entries by importance (lowest are evicted first)"),
+    ("memory pin|unpin <id>", "Protect an entry from eviction, or release it"),
+    ("memory tag|untag <id> <tag>", "Label an entry by hand (find it with 'memory search #tag')"),
+    ("memory save", "Persist memory to filesystem"),
+    ("memory merge", "Merge the memory file into this session's memories"),
+    ("memory checkpoint [ticks]", "Flush the WAL now (or set the interval)"),
+    ("memory autosave <minutes>", "Autosave memory every N real minutes (0 = off)"),
+    ("memory compact", "Rewrite the memory file without tombstones"),
+    ("memory reindex", "Re-extract keywords under the current index config"),
+    ("memory stopword add|remove <word>", "Tune keyword stop words"),
+    ("memory minlen <n>", "Set the minimum keyword length"),
+    ("memory store <text>", "Manually store an observation"),
+    ("task add <text>", "Add a task to the shared todo list"),
+    ("task list [--all]", "Show open tasks (--all includes done)"),
+    ("task done <id>", "Mark a task as done"),
+    ("serial stats", "Show serial receive queue counters"),
+    ("notify stats", "Show bridge notifications sent and dropped by the rate limit"),
+    ("serial send <text>", "Write a raw line to serial (low-level bridge debugging)"),
+    ("outbox cap <n>", "Limit messages each agent may send per tick"),
+    ("connections [--by-pattern]", "Show the graph of linked insights"),
+    ("resources", "Reading list of Resource insights, newest first"),
+    ("fonts list | fonts set <name>", "List or switch the desktop font"),
+    ("mode", "Switch VGA mode (text/graphics/auto) or show current mode"),
+    ("mode info", "Show screen resolution and framebuffer geometry"),
+    ("Shift+F1 or Shift+Esc", "Toggle between text and graphics mode"),
+    ("Esc", "Cancel the current input line"),
+    ("F11", "Show fullscreen exit instructions"),
+];
+
+/// First words of every shell command (for "did you mean" suggestions)
+const COMMAND_NAMES: &[&str] = &[
+    "academy", "agent", "agents", "ambition", "announcements", "archimedes", "breathe", "bridge",
+    "capabilities", "cert", "clear", "connect", "connections", "console", "cpuinfo", "demo", "desktop", "evolution", "fonts", "graphics",
+    "haiku", "health", "heartbeat", "help", "insights", "keyboard", "keyrepeat", "log", "memory", "midday",
+    "mode", "notify", "outbox", "ping", "protection", "query", "reflect", "report",
+    "resources", "roster", "routes", "schedule", "scout", "serial", "shutdown", "snapshot", "spark",
+    "status",
+    "subscriptions", "task", "test", "thomas", "traffic", "uptime", "whoami", "workspace",
+];
+
 /// A queue for incoming characters from interrupts (keyboard/serial)
 pub static INPUT_QUEUE: Lazy<ArrayQueue<char>> = Lazy::new(|| ArrayQueue::new(128));
 
@@ -53,12 +201,41 @@ lazy_static::lazy_static! {
     pub static ref SHELL: Mutex<Shell> = Mutex::new(Shell::new());
 }
 
+/// An LLM request sent to the bridge that hasn't been answered yet
+struct PendingLlmRequest {
+    /// Command that sent it (for the timeout message)
+    what: &'static str,
+    /// Tick it was sent at
+    sent_at: u64,
+}
+
+/// Prompt shown while an `ambition edit` interview waits for an answer
+const INTERVIEW_PROMPT: &str = "  > ";
+
+/// An `ambition edit` interview in progress
+struct AmbitionInterview {
+    /// (phase title, question) for each phase, from Archimedes's prompt
+    questions: Vec<(String, String)>,
+    /// Answers so far, one per completed phase
+    answers: Vec<String>,
+}
+
 /// The state of the shell
 pub struct Shell {
     /// Input buffer for the current command
     buffer: String,
     /// Prompt string
     prompt: &'static str,
+    /// Last `memory search` query and its scored results (reused when paging)
+    last_search: Option<(String, Vec<(u64, u32)>)>,
+    /// Outstanding LLM bridge request (one at a time)
+    pending_llm: Option<PendingLlmRequest>,
+    /// Tick of the last [LLM_RESPONSE] line from the bridge
+    last_llm_response: Option<u64>,
+    /// Whether `uptime` has already reported the boot duration
+    boot_reported: bool,
+    /// `ambition edit` interview that typed lines currently answer
+    interview: Option<AmbitionInterview>,
 }
 
 impl Shell {
@@ -67,7 +244,54 @@ impl Shell {
         Shell {
             buffer: String::with_capacity(MAX_COMMAND_LEN),
             prompt: "genesis> ",
+            last_search: None,
+            pending_llm: None,
+            last_llm_response: None,
+            boot_reported: false,
+            interview: None,
+        }
+    }
+
+    /// The prompt for the line being typed: a command, or an interview answer
+    fn current_prompt(&self) -> &'static str {
+        if self.interview.is_some() { INTERVIEW_PROMPT } else { self.prompt }
+    }
+
+    /// Print the question for the interview's next unanswered phase
+    fn ask_interview_question(&self) {
+        use crate::serial_println;
+        let Some(interview) = &self.interview else {
+            return;
+        };
+        let phase = interview.answers.len();
+        if let Some((title, question)) = interview.questions.get(phase) {
+            shell_print!("{{y}}Phase {}/{}: {}{{/}}", phase + 1, interview.questions.len(), title);
+            shell_print!("  {}", question);
+        }
+    }
+
+    /// Take the typed line as the answer to the interview's current phase;
+    /// after the last phase, synthesize the ambition and breathe it in
+    fn answer_interview(&mut self, supervisor: &mut Supervisor) {
+        use crate::serial_println;
+        let answer = String::from(self.buffer.trim());
+        if answer.eq_ignore_ascii_case("q") {
+            self.interview = None;
+            shell_print!("Interview aborted - ambition unchanged.");
+            return;
+        }
+        let Some(interview) = self.interview.as_mut() else {
+            return;
+        };
+        interview.answers.push(answer);
+        if interview.answers.len() < interview.questions.len() {
+            self.ask_interview_question();
+            return;
         }
+        let ambition = crate::agents::archimedes::synthesize_ambition(&interview.answers);
+        self.interview = None;
+        shell_print!("{{g}}Today's ambition:{{/}} \"{}\"", ambition);
+        supervisor.breathe(ambition);
     }
 
     /// Initialize the shell and print the first prompt
@@ -97,6 +321,11 @@ impl Shell {
         }
     }
 
+    /// Check whether the input queue has no room left
+    pub fn input_full() -> bool {
+        INPUT_QUEUE.is_full()
+    }
+
     /// Process all characters in the queue
     pub fn process_input(&mut self, supervisor: &mut Supervisor) {
         while let Some(c) = INPUT_QUEUE.pop() {
@@ -104,6 +333,35 @@ impl Shell {
         }
     }
 
+    /// Note an LLM request sent to the bridge. Only one is tracked; a new
+    /// request replaces one still waiting.
+    fn track_llm_request(&mut self, what: &'static str, tick: u64) {
+        if let Some(previous) = self.pending_llm.take() {
+            use crate::serial_println;
+            serial_println!("[BRIDGE] '{}' request superseded before a response", previous.what);
+        }
+        self.pending_llm = Some(PendingLlmRequest { what, sent_at: tick });
+    }
+
+    /// Give up on an LLM request the bridge hasn't answered in time
+    pub fn check_timeouts(&mut self, tick: u64) {
+        use crate::serial_println;
+
+        let expired = self.pending_llm.as_ref()
+            .is_some_and(|req| tick.saturating_sub(req.sent_at) >= LLM_TIMEOUT_TICKS);
+        if !expired {
+            return;
+        }
+        if let Some(req) = self.pending_llm.take() {
+            println!();
+            serial_println!();
+            shell_print!("{{r}}LLM bridge timed out{{/}} ('{}': no response after {} ticks)", req.what, LLM_TIMEOUT_TICKS);
+            // Restore the prompt and whatever was being typed
+            print!("{}{}", self.current_prompt(), self.buffer);
+            crate::serial_print!("{}{}", self.current_prompt(), self.buffer);
+        }
+    }
+
     /// Handle a single character input
     fn handle_char(&mut self, c: char, supervisor: &mut Supervisor) {
         match c {
@@ -116,6 +374,8 @@ impl Shell {
                 // Check if this is a bridge response (not a command)
                 if self.buffer.starts_with("[LLM_RESPONSE]") {
                     // Display the response cleanly (no echo, no prompt after)
+                    self.pending_llm = None;
+                    self.last_llm_response = Some(supervisor.current_tick());
                     let response = self.buffer.strip_prefix("[LLM_RESPONSE]").unwrap_or(&self.buffer).trim();
                     if !response.is_empty() {
                         shell_print!("  {}", response);
@@ -126,11 +386,12 @@ impl Shell {
                     // Incoming Telegram message from user via bridge
                     let message = self.buffer.strip_prefix("[TELEGRAM]").unwrap_or(&self.buffer).trim();
                     if !message.is_empty() {
-                        shell_print!("  [Telegram] {}", message);
                         // Route to agents as a Text message so they can respond
-                        supervisor.broadcast(crate::agents::message::MessageKind::Text(
-                            format!("TELEGRAM: {}", message),
-                        ));
+                        match supervisor.route_telegram(message) {
+                            TelegramRoute::Broadcast => shell_print!("  [Telegram] {}", message),
+                            TelegramRoute::Directed(agent) => shell_print!("  [Telegram -> {}] {}", agent, message),
+                            TelegramRoute::Unknown(name) => shell_print!("  [Telegram] {} (no agent '@{}')", message, name),
+                        }
                     }
                     self.buffer.clear();
                 } else if self.buffer.starts_with("[MEMORY_LOAD_DONE]") {
@@ -142,6 +403,7 @@ impl Shell {
                         d
                     };
                     crate::storage::memory_store::load_from_serial_data(&data);
+                    supervisor.load_ambition_history();
                     self.buffer.clear();
                 } else if self.buffer.starts_with("[MEMORY_LOAD]") {
                     // Accumulate a memory entry line from the bridge
@@ -173,21 +435,40 @@ impl Shell {
                     // Outgoing reply from agent — don't display locally, bridge handles it
                     self.buffer.clear();
                 } else {
-                    // Normal command execution
-                    self.execute_command(supervisor);
+                    // Normal command execution (or the answer to an interview question)
+                    if self.interview.is_some() {
+                        self.answer_interview(supervisor);
+                    } else {
+                        self.execute_command(supervisor);
+                    }
+                    self.buffer.clear();
+                    print!("{}", self.current_prompt());
+                    crate::serial_print!("{}", self.current_prompt()); // Also to serial
+                }
+            }
+            '\u{1b}' => {
+                use crate::serial_println;
+                // Esc: cancel the current input line
+                if !self.buffer.is_empty() {
                     self.buffer.clear();
-                    print!("{}", self.prompt);
-                    crate::serial_print!("{}", self.prompt); // Also to serial
+                    println!();
+                    serial_println!();
+                    print!("{}", self.current_prompt());
+                    crate::serial_print!("{}", self.current_prompt());
                 }
             }
             '\u{08}' | '\u{7f}' => {
                 use crate::serial_print;
                 // Handle backspace
-                if !self.buffer.is_empty() {
-                    self.buffer.pop();
-                    // Move cursor back, print space, move cursor back again (on both VGA and serial)
-                    print!("\u{08} \u{08}");
-                    serial_print!("\u{08} \u{08}"); // Also update serial output
+                if let Some((vga_cols, term_cols)) = erase_last_char(&mut self.buffer) {
+                    // Move cursor back, print space, move cursor back again - once per
+                    // column the removed char occupied (VGA and serial widths differ)
+                    for _ in 0..vga_cols {
+                        print!("\u{08} \u{08}");
+                    }
+                    for _ in 0..term_cols {
+                        serial_print!("\u{08} \u{08}"); // Also update serial output
+                    }
                     
                     // Text mode input - no graphics console needed
                 }
@@ -219,34 +500,11 @@ impl Shell {
         match cmd {
             "help" => {
                 shell_print!("Available commands:");
-                shell_print!("  help      - Show this help message");
-                shell_print!("  clear     - Clear the screen");
-                shell_print!("  status    - Show agent status");
-                shell_print!("  academy   - Show Academy certifications");
-                shell_print!("  ping      - Ping all agents");
-                shell_print!("  ambition  - Trigger morning ambitions");
-                shell_print!("  report    - Trigger end-of-day report");
-                shell_print!("  thomas    - Talk to Thomas specifically");
-                shell_print!("  whoami    - Show current user info");
-                shell_print!("  breathe [text] - Set the living ambition (the soul)");
-                shell_print!("  heartbeat - View current ambition pulse");
-                shell_print!("  insights  - View collected Sparks and Connections");
-                shell_print!("  scout video [path] - Request video analysis (via bridge)");
-                shell_print!("  test      - Trigger Thomas to run tests and send a Spark");
-                shell_print!("  haiku     - Ask TypeWrite to generate a haiku (tests LLM connection)");
-                shell_print!("  graphics  - Test graphics rendering (draw test pattern)");
-                shell_print!("  archimedes - Talk to Archimedes (Daily Ambition Agent)");
-                shell_print!("  desktop   - Show split-screen desktop (Conversation + Ambition)");
-                shell_print!("  protection - Show protection tier summary and agent access levels");
-                shell_print!("  memory search <q> - Search memory for matching entries");
-                shell_print!("  memory list   - Show recent memory entries (last 10)");
-                shell_print!("  memory stats  - Show memory store statistics");
-                shell_print!("  memory get <id> - Show full details of a memory entry");
-                shell_print!("  memory save   - Persist memory to filesystem");
-                shell_print!("  memory store <text> - Manually store an observation");
-                shell_print!("  mode      - Switch VGA mode (text/graphics) or show current mode");
-                shell_print!("  F1 or Esc - Toggle between text and graphics mode (keyboard shortcut)");
-                shell_print!("  F11       - Show fullscreen exit instructions");
+                for (usage, description) in HELP {
+                    shell_print!("  {:<9} - {}", usage, description);
+                }
+                shell_print!("  (output colors: {{g}}green{{/}} {{r}}red{{/}} {{y}}yellow{{/}} {{b}}blue{{/}} {{m}}magenta{{/}};");
+                shell_print!("   a marker is the color's first letter in braces, a slash in braces resets)");
                 shell_print!();
                 shell_print!("QEMU Fullscreen: Press Ctrl+Alt+F (or Ctrl+Alt+G) to exit");
                 shell_print!("                 Or Ctrl+A, X to quit QEMU");
@@ -259,26 +517,71 @@ impl Shell {
             "test" => {
                 shell_print!("Triggering Thomas to run tests...");
                 // Send a request message to Thomas via broadcast
-                supervisor.broadcast(crate::agents::message::MessageKind::Request {
-                    action: String::from("run_tests"),
-                    params: Vec::new(),
-                });
+                supervisor.request_tests(None, false);
                 shell_print!("Test request sent. Run 'insights' to see the Spark!");
             }
+            "demo" => {
+                run_demo(supervisor);
+            }
+            "test --verbose" => {
+                shell_print!("Triggering Thomas to run tests (verbose)...");
+                supervisor.request_tests(None, true);
+                shell_print!("Each test result will be printed when Thomas reports back.");
+            }
             "haiku" => {
                 shell_print!("Asking TypeWrite to generate a haiku...");
                 shell_print!("(Sending request to Serial Bridge for Gemini processing)");
                 // Send to serial bridge - it will detect this and call Gemini
                 serial_println!("[LLM_REQUEST] TypeWrite haiku request");
+                self.track_llm_request("haiku", supervisor.current_tick());
             }
             "graphics" => {
                 shell_print!("Drawing graphics test pattern...");
-                crate::gui::graphics::with_graphics(|gfx| {
+                let drawn = crate::gui::graphics::with_graphics(|gfx| {
                     gfx.draw_test_pattern();
                     gfx.swap_buffers();
                 });
-                shell_print!("Graphics test pattern drawn!");
-                shell_print!("(Check QEMU display window to see graphics)");
+                if drawn.is_some() {
+                    shell_print!("Graphics test pattern drawn!");
+                    shell_print!("(Check QEMU display window to see graphics)");
+                } else {
+                    shell_print!("Graphics not initialized - run 'desktop' or 'mode graphics' first");
+                }
+            }
+            "graphics panic" => {
+                // Exercises the panic handler with GRAPHICS locked and the screen in graphics mode
+                let entered = crate::gui::graphics::with_graphics(|_| {
+                    panic!("deliberate panic inside with_graphics ('graphics panic')");
+                });
+                if entered.is_none() {
+                    shell_print!("Graphics not initialized - run 'desktop' or 'mode graphics' first");
+                }
+            }
+            "graphics stipple" => {
+                shell_print!("Drawing stipple patterns...");
+                let drawn = crate::gui::graphics::with_graphics(|gfx| {
+                    gfx.draw_stipple_samples();
+                    gfx.swap_buffers();
+                });
+                if drawn.is_some() {
+                    shell_print!("Patterns (left to right): 0xFF 0xAA 0x88 0x80 0xCC");
+                    shell_print!("(Check QEMU display window to see graphics)");
+                } else {
+                    shell_print!("Graphics not initialized - run 'desktop' or 'mode graphics' first");
+                }
+            }
+            "graphics logo" => {
+                shell_print!("Blitting Genesis logo...");
+                let drawn = crate::gui::graphics::with_graphics(|gfx| {
+                    gfx.draw_logo_sample();
+                    gfx.swap_buffers();
+                });
+                if drawn.is_some() {
+                    shell_print!("Left: opaque blit, middle: color-keyed, right: clipped at screen edge");
+                    shell_print!("(Check QEMU display window to see graphics)");
+                } else {
+                    shell_print!("Graphics not initialized - run 'desktop' or 'mode graphics' first");
+                }
             }
             "archimedes" => {
                 shell_print!("=== ARCHIMEDES - Daily Ambition Agent ===");
@@ -311,7 +614,11 @@ impl Shell {
                 unsafe {
                     if crate::gui::graphics::current_mode() == crate::gui::graphics::VgaMode::Text {
                         shell_print!("Initializing graphics mode...");
-                        crate::gui::graphics::init();
+                        if let Err(e) = crate::gui::graphics::init() {
+                            shell_print!("{{r}}Graphics unavailable: {e}{{/}}");
+                            shell_print!("Staying in text mode; the desktop was not rendered.");
+                            return;
+                        }
                         
                         // Initialize custom font system (Agent Alliance Academy font)
                         use crate::gui::fonts;
@@ -319,41 +626,169 @@ impl Shell {
                         fonts::set_font(academy_font);
                         shell_print!("Agent Alliance Academy font loaded");
                         
-                        // Initialize desktop and console
+                        // Initialize desktop and console (after the font, which sets its wrap width)
                         use crate::gui::desktop;
                         desktop::init(crate::gui::graphics::WIDTH, crate::gui::graphics::HEIGHT);
+                        crate::gui::console::init(crate::gui::graphics::WIDTH, crate::gui::graphics::HEIGHT);
                         shell_print!("Graphics mode initialized");
                     }
                 }
                 
-                // Get current ambition for display
-                let default_ambition = String::from("Today, I want us to build something amazing together.");
-                let ambition = supervisor.get_ambition()
-                    .unwrap_or(&default_ambition);
-                
-                // Create commitments from supervisor insights or defaults
-                let commitments = vec![
-                    String::from("YOU: Set clear goals"),
-                    String::from("AI: Support with tools and insights"),
-                    String::from("COLLAB: Work together"),
-                ];
-                
                 // Render desktop layout
-                use crate::gui::desktop;
-                desktop::with_desktop(|layout| {
-                    layout.create_ambition_layout(Some(&ambition), &commitments);
-                });
-                desktop::render();
+                build_desktop_layout("split", supervisor);
+                crate::gui::desktop::render();
                 
                 shell_print!("Desktop rendered! Check QEMU graphics window.");
                 shell_print!("(Note: Graphics window is separate from this terminal)");
             }
-            "clear" => {
+            "clear" | "clear --scrollback" => {
+                // The graphics console's line buffer is its only history, so
+                // --scrollback drops the same lines; there's no separate ring yet
                 crate::vga_buffer::clear_screen();
+                crate::gui::console::clear_output();
+                if crate::gui::graphics::current_mode() == crate::gui::graphics::VgaMode::Graphics {
+                    repaint_graphics_screen();
+                }
             }
             "status" => {
                 supervisor.print_status();
                 shell_print!("Agents active: {}", supervisor.agent_count());
+                shell_print!("Idle: {} ticks{}", supervisor.idle_ticks(),
+                    if supervisor.is_low_power() { " (low-power)" } else { "" });
+                shell_print!("Pending timers: {}", supervisor.pending_timers());
+                for ((_, state, _), description) in supervisor.agent_overview().iter().zip(supervisor.agent_descriptions()) {
+                    let label = format!("{state:?}");
+                    crate::print!("  ");
+                    crate::vga_buffer::with_color(state.color(), crate::vga_buffer::Color::Black,
+                        || crate::print!("{:<12}", label));
+                    crate::println!(" {}", description);
+                    serial_println!("  {:<12} {}", label, description);
+                }
+                let unresponsive = supervisor.unresponsive_agents();
+                if !unresponsive.is_empty() {
+                    shell_print!("Unresponsive (no heartbeat ack): {}", unresponsive.join(", "));
+                }
+                let overflowing = supervisor.overflowing_agents();
+                if !overflowing.is_empty() {
+                    shell_print!("Outbox overflows: {} ({} dead letters)",
+                        overflowing.join(", "), supervisor.dead_letters().len());
+                }
+            }
+            "status --watch" => {
+                if crate::gui::graphics::current_mode() != crate::gui::graphics::VgaMode::Graphics {
+                    shell_print!("status --watch needs graphics mode (try 'mode graphics').");
+                } else {
+                    crate::gui::dashboard::start();
+                    shell_print!("Watching agents live - press any key to exit.");
+                }
+            }
+            "agents graph" => {
+                if crate::gui::graphics::current_mode() != crate::gui::graphics::VgaMode::Graphics {
+                    shell_print!("agents graph needs graphics mode (try 'mode graphics').");
+                } else {
+                    crate::gui::agent_graph::start();
+                    shell_print!("Graphing agent messages - press any key to exit.");
+                }
+            }
+            "cpuinfo" => {
+                use crate::cpuid::{self, Feature};
+                let (max_leaf, max_extended) = cpuid::max_leaves();
+                shell_print!("=== CPU ===");
+                shell_print!("  Vendor:     {}", cpuid::vendor());
+                shell_print!("  Brand:      {}", cpuid::brand().unwrap_or("(not reported)"));
+                shell_print!("  Hypervisor: {}", cpuid::hypervisor_vendor().unwrap_or("none (bare metal)"));
+                shell_print!("  Max leaf:   0x{:X} basic, 0x{:X} extended", max_leaf, max_extended);
+                let missing: Vec<&str> = Feature::ALL.iter()
+                    .filter(|f| !cpuid::has_feature(**f))
+                    .map(|f| f.name())
+                    .collect();
+                shell_print!("  Features:   {{g}}{}{{/}}", cpuid::present_features().join(" "));
+                if !missing.is_empty() {
+                    shell_print!("  Missing:    {{r}}{}{{/}}", missing.join(" "));
+                }
+            }
+            "uptime" => {
+                let seconds = crate::interrupts::uptime_ms() / 1000;
+                shell_print!("Up {} ({} timer interrupts, {} supervisor ticks)",
+                    format_hms(seconds), crate::interrupts::timer_ticks(), supervisor.current_tick());
+                if !self.boot_reported {
+                    self.boot_reported = true;
+                    shell_print!("Boot took ~{}s (kernel entry to shell, by the RTC)",
+                        crate::BOOT_SECONDS.load(core::sync::atomic::Ordering::Relaxed));
+                }
+            }
+            "roster" => {
+                let roster = supervisor.roster();
+                if roster.is_empty() {
+                    shell_print!("No agents registered.");
+                } else {
+                    let name_width = roster.iter().map(|r| r.name.chars().count()).max().unwrap_or(0).max(4);
+                    shell_print!("=== ROSTER ({}) ===", roster.len());
+                    shell_print!("  {:<name_width$} {:>4}  {:<12} {:<19} {:<11} {:<10} FOCUS",
+                        "NAME", "ID", "STATE", "ROLE", "CERT", "TIER");
+                    for entry in &roster {
+                        let state = format!("{:?}", entry.state);
+                        let role = entry.role.map(|r| format!("{r:?}")).unwrap_or_else(|| String::from("-"));
+                        let cert = entry.certification.map(|c| c.name()).unwrap_or("-");
+                        shell_print!("  {:<name_width$} {:>4}  {:<12} {:<19} {:<11} {:<10} {}",
+                            entry.name, entry.id.0, state, role, cert, entry.max_write_tier.name(),
+                            entry.focus.as_deref().unwrap_or("-"));
+                    }
+                }
+            }
+            "announcements" => {
+                let announcements = supervisor.announcements();
+                if announcements.is_empty() {
+                    shell_print!("No announcements yet.");
+                } else {
+                    shell_print!("=== ANNOUNCEMENTS (last {}) ===", announcements.len());
+                    for a in announcements {
+                        match a.level {
+                            AnnounceLevel::Info => shell_print!("  [t={}] {}: {}", a.tick, a.agent_name, a.text),
+                            AnnounceLevel::Warn => shell_print!("  [t={}] {{y}}{}: {}{{/}}", a.tick, a.agent_name, a.text),
+                            AnnounceLevel::Error => shell_print!("  [t={}] {{r}}{}: {}{{/}}", a.tick, a.agent_name, a.text),
+                        }
+                    }
+                }
+            }
+            "agents" => {
+                shell_print!("Agents: {}/{}", supervisor.agent_count(), supervisor.max_agents());
+                shell_print!("Use 'agents --limit <n>' to change the limit.");
+            }
+            "routes" => {
+                let stats = supervisor.route_stats();
+                shell_print!("=== MESSAGE ROUTES (tick {}) ===", supervisor.current_tick());
+                shell_print!("  {:<12} {:>10} {:>10} {:>8}", "AGENT", "DELIVERED", "SENT", "DROPPED");
+                for (name, s) in &stats {
+                    shell_print!("  {:<12} {:>10} {:>10} {:>8}", name, s.delivered, s.sent, s.dropped);
+                }
+            }
+            "routes --reset" => {
+                supervisor.reset_route_stats();
+                shell_print!("Routing counters reset.");
+            }
+            "traffic" => {
+                const BAR_WIDTH: u64 = 30;
+                let mut counts: Vec<(&str, u64)> = supervisor.kind_counts().iter().map(|(k, n)| (*k, *n)).collect();
+                if counts.is_empty() {
+                    shell_print!("No messages routed since the last reset.");
+                } else {
+                    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+                    let total: u64 = counts.iter().map(|(_, n)| n).sum();
+                    let max = counts[0].1.max(1);
+                    shell_print!("=== MESSAGE TRAFFIC ({} routed) ===", total);
+                    for (kind, n) in &counts {
+                        let bar = "#".repeat((n * BAR_WIDTH).div_ceil(max) as usize);
+                        shell_print!("  {:<16} {:>8} {:>3}% {}", kind, n, n * 100 / total, bar);
+                    }
+                }
+            }
+            "traffic --reset" => {
+                supervisor.reset_kind_counts();
+                shell_print!("Traffic counters reset.");
+            }
+            "health" => {
+                supervisor.print_health();
             }
             "academy" => {
                 supervisor.print_academy_status();
@@ -363,23 +798,124 @@ impl Shell {
             }
             "ping" => {
                 shell_print!("Pinging all agents...");
-                supervisor.broadcast(crate::agents::message::MessageKind::Ping);
-                // Wait a moment for responses, then check for pongs
-                // Note: In a real system, this would be async. For now, we'll check on next tick.
-                shell_print!("(Responses will appear as agents process messages)");
+                supervisor.ping_all();
+                // Pongs come back through the supervisor, which prints each round trip
+                shell_print!("(Round-trip ticks appear as each agent answers)");
             }
             "ambition" => {
                 supervisor.morning_ambition();
             }
+            "ambition edit" => {
+                // Answers arrive as typed lines (see `answer_interview`), so agents keep ticking
+                shell_print!("=== DAILY AMBITION INTERVIEW with Archimedes ===");
+                shell_print!("Answer each question on one line ('q' aborts, keeping the current ambition).");
+                self.interview = Some(AmbitionInterview {
+                    questions: crate::agents::archimedes::interview_questions(),
+                    answers: Vec::new(),
+                });
+                self.ask_interview_question();
+            }
+            "midday" => {
+                supervisor.midday_checkpoint();
+            }
             "report" => {
                 supervisor.eod_report();
             }
+            "shutdown" => {
+                supervisor.shutdown_all();
+                crate::storage::memory_store::save();
+                shell_print!();
+                shell_print!("Genesis has shut down. It is now safe to close the window.");
+                crate::power::power_off();
+            }
+            "reflect" => {
+                supervisor.nightly_reflection();
+            }
+            "schedule" | "schedule --show" => {
+                use crate::agents::supervisor::DailyPhase;
+                let sched = supervisor.daily_schedule();
+                let now = crate::rtc::now();
+                shell_print!("=== DAILY SCHEDULE (RTC {:04}-{:02}-{:02} {:02}:{:02}) ===",
+                    now.year, now.month, now.day, now.hour, now.minute);
+                for phase in DailyPhase::ALL {
+                    let done = if sched.has_fired(phase) { "{g}done{/}" } else { "{y}pending{/}" };
+                    shell_print!("  {:02}:00  {:<20} {}", sched.hour(phase), phase.name(), done);
+                }
+                shell_print!("(Set the morning hour with 'schedule morning <hour>')");
+            }
             "thomas" => {
                 shell_print!("Thomas is a 🟢 Rookie at the Agent Alliance Academy.");
                 shell_print!("His motto is: \"Trust, but verify.\"");
                 if let Some(_prompt) = supervisor.get_prompt(crate::agents::prompts::character_ids::THOMAS) {
                     serial_println!("[SHELL] Thomas's full prompt sent to bridge.");
                     shell_print!("(Full prompt sent to Serial Bridge for LLM processing)");
+                    self.track_llm_request("thomas", supervisor.current_tick());
+                }
+            }
+            "snapshot save" => {
+                use crate::agents::supervisor::SNAPSHOT_PATH;
+                let data = supervisor.snapshot();
+                let _ = crate::storage::filesystem::create_dir("/storage/state");
+                match crate::storage::filesystem::write_file_string(SNAPSHOT_PATH, &data) {
+                    Ok(()) => shell_print!("Supervisor state saved to {} ({} bytes).", SNAPSHOT_PATH, data.len()),
+                    Err(e) => shell_print!("Snapshot save failed: {:?}", e),
+                }
+            }
+            "snapshot load" => {
+                use crate::agents::supervisor::SNAPSHOT_PATH;
+                match crate::storage::filesystem::read_file_string(SNAPSHOT_PATH) {
+                    Ok(data) => match supervisor.restore_from(&data) {
+                        Ok(agents) => shell_print!("Restored tick {} and {} agents from {}.",
+                            supervisor.current_tick(), agents, SNAPSHOT_PATH),
+                        Err(e) => shell_print!("Snapshot load failed: {}", e),
+                    },
+                    Err(e) => shell_print!("No snapshot at {} ({:?}). Try 'snapshot save' first.", SNAPSHOT_PATH, e),
+                }
+            }
+            "workspace" => {
+                let ask = |topic: &str| supervisor.query("archimedes", topic, &[]).map(Option::unwrap_or_default);
+                match (ask("ambition"), ask("commitments"), ask("workspace_folders")) {
+                    (Some(ambition), Some(commitments), Some(folders)) => {
+                        shell_print!("=== ARCHIMEDES WORKSPACE ===");
+                        match ambition.first() {
+                            Some(text) => shell_print!("  Ambition: {}", preview(text, 60)),
+                            None => shell_print!("  Ambition: (none loaded)"),
+                        }
+                        shell_print!("  Commitments ({}):", commitments.len());
+                        for commitment in &commitments {
+                            shell_print!("    - {}", commitment);
+                        }
+                        shell_print!("  Folders ({}):", folders.len());
+                        for folder in &folders {
+                            if crate::storage::filesystem::dir_exists(folder) {
+                                shell_print!("    {{g}}[ok]{{/}}      {}", folder);
+                            } else {
+                                shell_print!("    {{r}}[missing]{{/}} {}", folder);
+                            }
+                        }
+                        if folders.is_empty() {
+                            shell_print!("    (none yet - created during environment setup)");
+                        }
+                    }
+                    _ => shell_print!("Archimedes isn't registered, so there is no workspace to show."),
+                }
+            }
+            "bridge status" => {
+                let tick = supervisor.current_tick();
+                shell_print!("=== LLM BRIDGE ===");
+                match &self.pending_llm {
+                    Some(req) => shell_print!("  Outstanding requests: 1 ('{}', sent {} ticks ago, timeout {})",
+                        req.what, tick.saturating_sub(req.sent_at), LLM_TIMEOUT_TICKS),
+                    None => shell_print!("  Outstanding requests: 0"),
+                }
+                match self.last_llm_response {
+                    Some(at) if tick.saturating_sub(at) <= BRIDGE_RECENT_TICKS => {
+                        shell_print!("  Last response: {} ticks ago {{g}}(responding){{/}}", tick.saturating_sub(at));
+                    }
+                    Some(at) => {
+                        shell_print!("  Last response: {} ticks ago {{y}}(quiet){{/}}", tick.saturating_sub(at));
+                    }
+                    None => shell_print!("  Last response: {{y}}never{{/}}"),
                 }
             }
             "whoami" => {
@@ -398,77 +934,66 @@ impl Shell {
                     shell_print!("Use 'breathe [ambition]' to set the soul of Genesis.");
                 }
             }
+            "heartbeat --status" => {
+                supervisor.print_heartbeat_status();
+            }
             "insights" => {
-                let insights = supervisor.get_insights();
-                if insights.is_empty() {
-                    shell_print!("No insights collected yet.");
-                    shell_print!("Agents will send Sparks and Connections as they work.");
-                } else {
-                    shell_print!("Constellation of Insights ({} total):", insights.len());
-                    shell_print!();
-                    
-                    let mut spark_count = 0;
-                    let mut connection_count = 0;
-                    let mut resource_count = 0;
-                    let mut feeling_count = 0;
-                    
-                    for (i, insight) in insights.iter().enumerate() {
-                        match insight {
-                            crate::agents::message::FeedbackType::Spark { content, context } => {
-                                spark_count += 1;
-                                shell_print!("  [{:3}] ✨ SPARK", i + 1);
-                                shell_print!("       Content: {}", content);
-                                shell_print!("       Context: {}", context);
-                            }
-                            crate::agents::message::FeedbackType::Connection { from, to, pattern } => {
-                                connection_count += 1;
-                                shell_print!("  [{:3}] 🔗 CONNECTION", i + 1);
-                                shell_print!("       From: {}", from);
-                                shell_print!("       To: {}", to);
-                                shell_print!("       Pattern: {}", pattern);
-                            }
-                            crate::agents::message::FeedbackType::Resource { description, location } => {
-                                resource_count += 1;
-                                shell_print!("  [{:3}] 📚 RESOURCE", i + 1);
-                                shell_print!("       Description: {}", description);
-                                shell_print!("       Location: {}", location);
-                            }
-                            crate::agents::message::FeedbackType::Feeling { tag, intensity } => {
-                                feeling_count += 1;
-                                shell_print!("  [{:3}] 💭 FEELING", i + 1);
-                                shell_print!("       Tag: {}", tag);
-                                shell_print!("       Intensity: {}%", intensity);
-                            }
-                        }
-                        shell_print!();
-                    }
-                    
-                    shell_print!("Summary:");
-                    shell_print!("  Sparks: {}", spark_count);
-                    shell_print!("  Connections: {}", connection_count);
-                    shell_print!("  Resources: {}", resource_count);
-                    shell_print!("  Feelings: {}", feeling_count);
-                }
+                show_insights(supervisor, None, false);
+            }
+            "connections" => {
+                show_connections(supervisor, false);
+            }
+            "connections --by-pattern" => {
+                show_connections(supervisor, true);
+            }
+            "resources" => {
+                show_resources(supervisor);
             }
             "mode" => {
                 let current = crate::gui::graphics::current_mode();
                 shell_print!("Current VGA mode: {:?}", current);
-                shell_print!("Press F1 to toggle, or use 'mode text' / 'mode graphics'");
+                shell_print!("Auto-render: {}", if crate::gui::desktop::auto_render() { "on" } else { "off" });
+                shell_print!("Press Shift+F1 to toggle, or use 'mode text' / 'mode graphics'");
             }
             _ => {
                 // Memory commands
-                if cmd == "memory stats" {
+                if cmd == "memory stats --json" {
+                    print_json(&crate::storage::memory_store::stats().to_json());
+                } else if cmd == "memory list --json" {
+                    let entries: Vec<String> = crate::storage::memory_store::recent(10)
+                        .iter()
+                        .map(|entry| entry.to_json())
+                        .collect();
+                    print_json(&format!("[{}]", entries.join(",")));
+                } else if cmd == "memory stats" {
                     let st = crate::storage::memory_store::stats();
                     shell_print!("=== MEMORY STORE ===");
                     shell_print!("  Entries: {}", st.entry_count);
                     shell_print!("  Index keywords: {}", st.index_size);
                     shell_print!("  Estimated size: {} bytes", st.estimated_bytes);
+                    let agent_bytes: usize = supervisor.agent_memory().iter().map(|(_, bytes)| bytes).sum();
+                    shell_print!("  Agent buffers:  {} bytes (see 'memory agents')", agent_bytes);
                     if !st.top_keywords.is_empty() {
                         shell_print!("  Top keywords:");
                         for (kw, count) in st.top_keywords.iter().take(5) {
                             shell_print!("    {} ({})", kw, count);
                         }
                     }
+                } else if cmd == "memory agents" {
+                    let usage = supervisor.agent_memory();
+                    if usage.is_empty() {
+                        shell_print!("No agents registered.");
+                    } else {
+                        let name_width = usage.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).max(5);
+                        shell_print!("=== AGENT MEMORY (estimated) ===");
+                        for (name, bytes) in &usage {
+                            shell_print!("  {:<name_width$} {:>8} bytes", name, bytes);
+                        }
+                        let total: usize = usage.iter().map(|(_, bytes)| bytes).sum();
+                        let store = crate::storage::memory_store::stats().estimated_bytes;
+                        shell_print!("  {:<name_width$} {:>8} bytes", "TOTAL", total);
+                        shell_print!("  (memory store: {} bytes)", store);
+                    }
                 } else if cmd == "memory list" {
                     let entries = crate::storage::memory_store::recent(10);
                     if entries.is_empty() {
@@ -476,55 +1001,283 @@ impl Shell {
                     } else {
                         shell_print!("=== RECENT MEMORIES ({}) ===", entries.len());
                         for entry in &entries {
-                            let preview = if entry.content.len() > 60 {
-                                let s: String = entry.content.chars().take(57).collect();
-                                format!("{}...", s)
-                            } else {
-                                entry.content.clone()
-                            };
+                            let preview = preview(&entry.content, 60);
                             shell_print!("  [{}] ({}) {}", entry.id, entry.kind.as_str(), preview);
                         }
                     }
+                } else if let Some(source) = cmd.strip_prefix("memory by-source ") {
+                    const SHOWN: usize = 20;
+                    let source = source.trim();
+                    let entries = crate::storage::memory_store::by_source(source);
+                    if entries.is_empty() {
+                        shell_print!("No memories from '{}' (sources look like 'agent-4' or 'shell').", source);
+                    } else {
+                        shell_print!("=== MEMORIES FROM {} ({}) ===", source, entries.len());
+                        for entry in entries.iter().take(SHOWN) {
+                            shell_print!("  [{}] t={} ({}) {}", entry.id, entry.timestamp, entry.kind.as_str(),
+                                preview(&entry.content, 50));
+                        }
+                        if entries.len() > SHOWN {
+                            shell_print!("  ... and {} older", entries.len() - SHOWN);
+                        }
+                    }
+                } else if cmd == "memory sessions" {
+                    let sessions = crate::storage::memory_store::sessions();
+                    if sessions.is_empty() {
+                        shell_print!("No memories stored yet.");
+                    } else {
+                        shell_print!("=== SESSIONS ({}) ===", sessions.len());
+                        for (session, count) in sessions {
+                            shell_print!("  {:<14}  {}  {} entries", session, format_session(session), count);
+                        }
+                    }
+                } else if let Some(arg) = cmd.strip_prefix("memory by-session ") {
+                    const SHOWN: usize = 20;
+                    match arg.trim().parse::<u64>() {
+                        Ok(session) => {
+                            let entries = crate::storage::memory_store::by_session(session);
+                            if entries.is_empty() {
+                                shell_print!("No memories from session {}.", session);
+                            } else {
+                                shell_print!("=== SESSION {} ({}) ===", format_session(session), entries.len());
+                                for entry in entries.iter().take(SHOWN) {
+                                    shell_print!("  [{}] t={} ({}) {}", entry.id, entry.timestamp, entry.kind.as_str(),
+                                        preview(&entry.content, 50));
+                                }
+                                if entries.len() > SHOWN {
+                                    shell_print!("  ... and {} older", entries.len() - SHOWN);
+                                }
+                            }
+                        }
+                        Err(_) => shell_print!("Usage: memory by-session <id> (see 'memory sessions')"),
+                    }
+                } else if let Some(args) = cmd.strip_prefix("memory why ") {
+                    let ids: Vec<Option<u64>> = args.split_whitespace().map(|a| a.parse().ok()).collect();
+                    match ids.as_slice() {
+                        [Some(first), Some(second)] => {
+                            match crate::storage::memory_store::explain_relation(*first, *second) {
+                                None => shell_print!("No memory #{} or #{} (see 'memory list')", first, second),
+                                Some(why) if why.shared.is_empty() => {
+                                    shell_print!("Memories #{} and #{} share no keywords - unrelated.", first, second);
+                                }
+                                Some(why) => {
+                                    shell_print!("=== #{} <-> #{}: relatedness {} ===", first, second, why.score);
+                                    for (keyword, freq) in &why.shared {
+                                        shell_print!("  {:<16} in {} memories", keyword, freq);
+                                    }
+                                    shell_print!("(rarer shared keywords score higher)");
+                                }
+                            }
+                        }
+                        _ => shell_print!("Usage: memory why <id1> <id2>"),
+                    }
+                } else if cmd == "memory importance" || cmd.starts_with("memory importance ") {
+                    let arg = cmd.strip_prefix("memory importance").unwrap_or("").trim();
+                    let count = if arg.is_empty() { Some(20) } else { arg.parse::<usize>().ok() };
+                    match count {
+                        Some(n) => {
+                            let ranked = crate::storage::memory_store::by_importance(supervisor.current_tick());
+                            if ranked.is_empty() {
+                                shell_print!("No memories stored yet.");
+                            } else {
+                                shell_print!("=== MEMORY IMPORTANCE (top {} of {}) ===", n.min(ranked.len()), ranked.len());
+                                for (score, entry) in ranked.iter().take(n) {
+                                    let pin = if entry.pinned { "pinned " } else { "" };
+                                    shell_print!("  {:>5} [{}] {}({}, {} reads) {}", score, entry.id, pin,
+                                        entry.kind.as_str(), entry.access_count, preview(&entry.content, 40));
+                                }
+                            }
+                        }
+                        None => shell_print!("Usage: memory importance [n]"),
+                    }
+                } else if let Some((verb, arg)) = cmd.strip_prefix("memory pin ").map(|a| ("pin", a))
+                    .or_else(|| cmd.strip_prefix("memory unpin ").map(|a| ("unpin", a))) {
+                    match arg.trim().parse::<u64>() {
+                        Ok(id) if crate::storage::memory_store::set_pinned(id, verb == "pin") => {
+                            shell_print!("Memory #{} {}ned.", id, verb);
+                        }
+                        Ok(id) => shell_print!("No memory with ID {}", id),
+                        Err(_) => shell_print!("Usage: memory {} <id>", verb),
+                    }
+                } else if let Some((verb, arg)) = cmd.strip_prefix("memory tag ").map(|a| ("tag", a))
+                    .or_else(|| cmd.strip_prefix("memory untag ").map(|a| ("untag", a))) {
+                    use crate::storage::memory_store;
+                    let mut args = arg.split_whitespace();
+                    match (args.next().map(str::parse::<u64>), args.next(), args.next()) {
+                        (Some(Ok(id)), Some(tag), None) => {
+                            let normalized = memory_store::normalize_tag(tag);
+                            let shown = normalized.as_deref().unwrap_or(tag.trim_start_matches('#'));
+                            if verb == "tag" && memory_store::add_tag(id, tag) {
+                                shell_print!("Memory #{} tagged #{}.", id, shown);
+                            } else if verb == "untag" && memory_store::remove_tag(id, tag) {
+                                shell_print!("Removed #{} from memory #{}.", shown, id);
+                            } else if memory_store::peek(id).is_none() {
+                                shell_print!("No memory with ID {}", id);
+                            } else if verb == "untag" {
+                                shell_print!("Memory #{} has no tag #{}.", id, shown);
+                            } else {
+                                shell_print!("Tags may use letters, digits, '-' and '_'.");
+                            }
+                        }
+                        _ => shell_print!("Usage: memory {} <id> <tag>", verb),
+                    }
                 } else if cmd == "memory save" {
                     crate::storage::memory_store::save();
                     shell_print!("Memory persisted to filesystem.");
+                } else if cmd == "memory reindex" {
+                    let (entries, keywords) = crate::storage::memory_store::reindex();
+                    let config = crate::storage::memory_store::index_config();
+                    shell_print!("Reindexed {} entries: {} unique keywords (min length {}, {} stop words).",
+                        entries, keywords, config.min_word_len, config.stop_words.len());
+                } else if let Some(word) = cmd.strip_prefix("memory stopword add ") {
+                    if crate::storage::memory_store::add_stop_word(word) {
+                        shell_print!("Stop word '{}' added (affects new memories; 'memory reindex' for old ones).", word.trim());
+                    } else {
+                        shell_print!("'{}' is already a stop word.", word.trim());
+                    }
+                } else if let Some(word) = cmd.strip_prefix("memory stopword remove ") {
+                    if crate::storage::memory_store::remove_stop_word(word) {
+                        shell_print!("Stop word '{}' removed (affects new memories; 'memory reindex' for old ones).", word.trim());
+                    } else {
+                        shell_print!("'{}' is not a stop word.", word.trim());
+                    }
+                } else if let Some(arg) = cmd.strip_prefix("memory minlen ") {
+                    match arg.trim().parse::<usize>() {
+                        Ok(len) => {
+                            crate::storage::memory_store::set_min_word_len(len);
+                            shell_print!("Minimum keyword length: {}", crate::storage::memory_store::index_config().min_word_len);
+                        }
+                        Err(_) => shell_print!("Usage: memory minlen <n>"),
+                    }
+                } else if cmd == "memory merge" {
+                    match crate::storage::memory_store::merge_from_file() {
+                        Some(added) => shell_print!("Merged {} entries from the memory file (duplicates skipped).", added),
+                        None => shell_print!("No memory file to merge."),
+                    }
+                } else if cmd == "memory compact" {
+                    let st = crate::storage::memory_store::compact();
+                    shell_print!("Memory file compacted: {} -> {} bytes ({} reclaimed, {} ops folded)",
+                        st.bytes_before, st.bytes_after,
+                        st.bytes_before.saturating_sub(st.bytes_after), st.ops_folded);
+                } else if cmd == "memory checkpoint" || cmd.starts_with("memory checkpoint ") {
+                    let arg = cmd.strip_prefix("memory checkpoint").unwrap_or("").trim();
+                    if arg.is_empty() {
+                        let flushed = crate::storage::memory_store::checkpoint();
+                        shell_print!("Checkpoint flushed {} WAL ops.", flushed);
+                    } else if let Ok(ticks) = arg.parse::<u64>() {
+                        supervisor.set_memory_checkpoint_interval(ticks);
+                        if ticks == 0 {
+                            shell_print!("Periodic memory checkpoints disabled.");
+                        } else {
+                            shell_print!("Memory checkpoint every {} ticks.", ticks);
+                        }
+                    } else {
+                        shell_print!("Usage: memory checkpoint [ticks]");
+                        shell_print!("  (current interval: {} ticks)", supervisor.memory_checkpoint_interval());
+                    }
+                } else if cmd == "memory autosave" || cmd.starts_with("memory autosave ") {
+                    let arg = cmd.strip_prefix("memory autosave").unwrap_or("").trim();
+                    if let Ok(minutes) = arg.parse::<u64>() {
+                        supervisor.set_autosave_minutes(minutes);
+                        if minutes == 0 {
+                            shell_print!("Memory autosave disabled.");
+                        } else {
+                            shell_print!("Memory autosave every {} min (skipped when nothing changed).", minutes);
+                        }
+                    } else {
+                        shell_print!("Usage: memory autosave <minutes>   (0 disables)");
+                        match supervisor.autosave_minutes() {
+                            0 => shell_print!("  (autosave is off)"),
+                            m => shell_print!("  (current interval: {} min)", m),
+                        }
+                    }
+                } else if cmd == "memory keywords" || cmd.starts_with("memory keywords ") {
+                    const BAR_WIDTH: usize = 30;
+                    let arg = cmd.strip_prefix("memory keywords").unwrap_or("").trim();
+                    let count = if arg.is_empty() { Some(20) } else { arg.parse::<usize>().ok() };
+                    match count {
+                        Some(n) => {
+                            // top_keywords truncates, so n is capped at the index size
+                            let keywords = crate::storage::memory_store::top_keywords(n);
+                            if keywords.is_empty() {
+                                shell_print!("No keywords indexed yet.");
+                            } else {
+                                let max = keywords[0].1.max(1);
+                                shell_print!("=== KEYWORD HEATMAP (top {}) ===", keywords.len());
+                                for (kw, freq) in &keywords {
+                                    let len = (freq * BAR_WIDTH).div_ceil(max);
+                                    let bar = "#".repeat(len);
+                                    shell_print!("  {:<16} {:>3} {}", kw, freq, bar);
+                                }
+                            }
+                        }
+                        None => shell_print!("Usage: memory keywords [n]"),
+                    }
                 } else if cmd.starts_with("memory search ") {
-                    let query = cmd.strip_prefix("memory search ").unwrap_or("").trim();
+                    const PAGE_SIZE: usize = 10;
+                    let args = cmd.strip_prefix("memory search ").unwrap_or("").trim();
+                    // A trailing number is the page, as long as something precedes it
+                    let (query, page_arg) = match args.rsplit_once(' ') {
+                        Some((q, p)) if p.parse::<usize>().is_ok() => (q.trim(), p.parse::<usize>().ok()),
+                        _ => (args, None),
+                    };
                     if query.is_empty() {
-                        shell_print!("Usage: memory search <query>");
+                        shell_print!("Usage: memory search <query> [page]");
                     } else {
-                        let results = crate::storage::memory_store::search(query);
+                        // Page changes on the same query reuse the cached scores
+                        let cached = page_arg.is_some()
+                            && self.last_search.as_ref().map(|(q, _)| q.as_str() == query).unwrap_or(false);
+                        if !cached {
+                            let results = crate::storage::memory_store::search(query);
+                            self.last_search = Some((String::from(query), results));
+                        }
+                        let results = self.last_search.as_ref().map(|(_, r)| r.as_slice()).unwrap_or(&[]);
                         if results.is_empty() {
                             shell_print!("No results for: {}", query);
                         } else {
+                            let total_pages = results.len().div_ceil(PAGE_SIZE);
+                            let requested = page_arg.unwrap_or(1);
+                            let page = requested.clamp(1, total_pages);
+                            if page != requested {
+                                shell_print!("Page {} out of range, showing page {}", requested, page);
+                            }
                             shell_print!("=== SEARCH RESULTS ({}) ===", results.len());
-                            for (id, score) in results.iter().take(10) {
+                            for (id, score) in results.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE) {
                                 if let Some(entry) = crate::storage::memory_store::get(*id) {
-                                    let preview = if entry.content.len() > 50 {
-                                        let s: String = entry.content.chars().take(47).collect();
-                                        format!("{}...", s)
-                                    } else {
-                                        entry.content.clone()
-                                    };
+                                    let preview = preview(&entry.content, 50);
                                     shell_print!("  [{}] score={} ({}) {}", id, score, entry.kind.as_str(), preview);
                                 }
                             }
+                            shell_print!("page {} of {} (total {})", page, total_pages, results.len());
                         }
                     }
                 } else if cmd.starts_with("memory get ") {
                     let id_str = cmd.strip_prefix("memory get ").unwrap_or("").trim();
+                    let (id_str, json) = match id_str.strip_suffix("--json") {
+                        Some(rest) => (rest.trim(), true),
+                        None => (id_str, false),
+                    };
                     match id_str.parse::<u64>() {
                         Ok(id) => {
                             match crate::storage::memory_store::get(id) {
+                                Some(entry) if json => print_json(&entry.to_json()),
                                 Some(entry) => {
                                     shell_print!("=== MEMORY #{} ===", entry.id);
                                     shell_print!("  Kind: {}", entry.kind.as_str());
                                     shell_print!("  Source: {}", entry.source);
                                     shell_print!("  Timestamp: {}", entry.timestamp);
                                     shell_print!("  Accessed: {} times", entry.access_count);
+                                    if entry.pinned {
+                                        shell_print!("  Pinned: never evicted");
+                                    }
+                                    if !entry.tags.is_empty() {
+                                        let tags: Vec<String> = entry.tags.iter().map(|t| format!("#{t}")).collect();
+                                        shell_print!("  Tags: {}", tags.join(" "));
+                                    }
                                     shell_print!("  Keywords: {}", entry.keywords.join(", "));
                                     shell_print!("  Content: {}", entry.content);
                                 }
+                                None if json => print_json("null"),
                                 None => {
                                     shell_print!("No memory with ID {}", id);
                                 }
@@ -539,20 +1292,37 @@ impl Shell {
                     if text.is_empty() {
                         shell_print!("Usage: memory store <text>");
                     } else {
-                        let id = crate::storage::memory_store::store(
+                        let id = crate::storage::memory_store::store_with_timestamp(
                             text,
                             crate::storage::memory_store::MemoryKind::Observation,
                             "shell",
+                            supervisor.current_tick(),
                         );
                         shell_print!("Stored as memory #{}", id);
                     }
                 } else if cmd == "memory" {
                     shell_print!("Usage: memory <command>");
-                    shell_print!("  memory search <query> - Search memories");
+                    shell_print!("  memory search <query> [page] - Search memories");
                     shell_print!("  memory list           - Show recent entries");
+                    shell_print!("  memory by-source <s>  - Entries from one source");
+                    shell_print!("  memory sessions       - Boot sessions and counts");
+                    shell_print!("  memory by-session <id> - Entries from one session");
                     shell_print!("  memory stats          - Show statistics");
+                    shell_print!("  memory keywords [n]   - Keyword frequency heatmap");
                     shell_print!("  memory get <id>       - Show full entry");
+                    shell_print!("  memory why <id> <id>  - Shared keywords of two entries");
+                    shell_print!("  memory importance [n] - Rank entries; lowest evicted first");
+                    shell_print!("  memory pin|unpin <id> - Protect an entry from eviction");
+                    shell_print!("  memory tag|untag <id> <tag> - Label an entry (search #tag)");
+                    shell_print!("  memory list|get|stats --json - One-line JSON for tooling");
                     shell_print!("  memory save           - Persist to filesystem");
+                    shell_print!("  memory merge          - Merge file, keep session entries");
+                    shell_print!("  memory checkpoint [n] - Flush WAL / set interval");
+                    shell_print!("  memory autosave <min> - Autosave every N minutes");
+                    shell_print!("  memory compact        - Rewrite file, drop tombstones");
+                    shell_print!("  memory reindex        - Rebuild keywords and index");
+                    shell_print!("  memory stopword add|remove <w> - Tune stop words");
+                    shell_print!("  memory minlen <n>     - Minimum keyword length");
                     shell_print!("  memory store <text>   - Store an observation");
                 // Check if it's a "mode" command with argument
                 } else if cmd.starts_with("mode ") {
@@ -566,22 +1336,566 @@ impl Shell {
                             shell_print!("(VGA text buffer at 0xB8000)");
                         }
                         "graphics" | "gfx" => {
-                            unsafe {
-                                crate::gui::graphics::switch_to_graphics_mode();
+                            match unsafe { crate::gui::graphics::switch_to_graphics_mode() } {
+                                Ok(()) => {
+                                    shell_print!("Switched to GRAPHICS mode (Mode 13h)");
+                                    shell_print!("(Framebuffer at 0xA0000, 320x200)");
+                                }
+                                Err(e) => {
+                                    shell_print!("{{r}}Graphics unavailable: {e}{{/}}");
+                                    shell_print!("Staying in TEXT mode");
+                                }
+                            }
+                        }
+                        "info" | "graphics --res" => {
+                            let (width, height, bpp) = crate::gui::graphics::geometry();
+                            let (addr, pitch) = crate::gui::graphics::framebuffer_info();
+                            let unit = if crate::gui::graphics::current_mode() == crate::gui::graphics::VgaMode::Text {
+                                "chars"
+                            } else {
+                                "pixels"
+                            };
+                            shell_print!("VGA mode {:02X}h: {}x{} {}, {} bpp",
+                                crate::gui::graphics::hardware_mode(), width, height, unit, bpp);
+                            shell_print!("  Framebuffer: {:#X}, {} bytes per row", addr, pitch);
+                        }
+                        "auto" | "auto on" | "auto off" => {
+                            let enabled = match mode_arg {
+                                "auto on" => true,
+                                "auto off" => false,
+                                _ => !crate::gui::desktop::auto_render(),
+                            };
+                            crate::gui::desktop::set_auto_render(enabled);
+                            if enabled {
+                                shell_print!("Auto-render ON (desktop redraws only when content changes)");
+                            } else {
+                                shell_print!("Auto-render OFF (desktop redraws every 100 ticks)");
                             }
-                            shell_print!("Switched to GRAPHICS mode (Mode 13h)");
-                            shell_print!("(Framebuffer at 0xA0000, 320x200)");
                         }
                         _ => {
-                            shell_print!("Usage: mode [text|graphics]");
+                            shell_print!("Usage: mode [text|graphics|auto|info]");
                             shell_print!("  mode text      - Switch to text mode");
                             shell_print!("  mode graphics  - Switch to graphics mode");
+                            shell_print!("  mode auto      - Toggle render-only-when-changed (or 'auto on|off')");
+                            shell_print!("  mode info      - Show resolution, depth and framebuffer layout");
                             shell_print!("  mode           - Show current mode");
                         }
                     }
+                } else if cmd == "capabilities" || cmd.starts_with("capabilities ") {
+                    let name = cmd.strip_prefix("capabilities").unwrap_or("").trim();
+                    if name.is_empty() {
+                        shell_print!("Usage: capabilities <agent name>");
+                    } else {
+                        match supervisor.agent_capabilities(name) {
+                            Some(caps) if caps.is_empty() => {
+                                shell_print!("{} advertises no capabilities", name);
+                            }
+                            Some(caps) => {
+                                shell_print!("=== {} CAPABILITIES ===", name.to_uppercase());
+                                for cap in &caps {
+                                    shell_print!("  - {}", cap);
+                                }
+                            }
+                            None => shell_print!("No agent named '{}'", name),
+                        }
+                    }
+                } else if cmd == "subscriptions" || cmd.starts_with("subscriptions ") {
+                    let name = cmd.strip_prefix("subscriptions").unwrap_or("").trim();
+                    if name.is_empty() {
+                        shell_print!("Usage: subscriptions <agent name>");
+                    } else {
+                        match supervisor.agent_subscriptions(name) {
+                            Some(filter) if filter.is_all() => {
+                                shell_print!("{} receives all broadcasts", name);
+                            }
+                            Some(filter) => {
+                                let kinds = filter.names();
+                                if kinds.is_empty() {
+                                    shell_print!("{} receives no broadcasts (direct messages only)", name);
+                                } else {
+                                    shell_print!("{} receives broadcasts of: {}", name, kinds.join(", "));
+                                }
+                            }
+                            None => shell_print!("No agent named '{}'", name),
+                        }
+                    }
+                } else if cmd == "query" || cmd.starts_with("query ") {
+                    let mut parts = cmd["query".len()..].split_whitespace();
+                    match (parts.next(), parts.next()) {
+                        (Some(agent), Some(topic)) => {
+                            let args: Vec<String> = parts.map(String::from).collect();
+                            match supervisor.send_query(agent, topic, args) {
+                                Some(id) => shell_print!("Query #{} sent to {}; the answer follows.", id, agent),
+                                None => shell_print!("No agent named '{}'", agent),
+                            }
+                        }
+                        _ => {
+                            shell_print!("Usage: query <agent> <topic> [args...]");
+                            shell_print!("  e.g. query thomas test_history, query archimedes commitments");
+                        }
+                    }
+                } else if cmd.starts_with("agents --limit") {
+                    let arg = cmd.strip_prefix("agents --limit").unwrap_or("").trim();
+                    match arg.parse::<usize>() {
+                        Ok(n) => {
+                            let applied = supervisor.set_max_agents(n);
+                            if applied != n {
+                                shell_print!("Agent limit set to {} (can't go below {} running agents)",
+                                    applied, supervisor.agent_count());
+                            } else {
+                                shell_print!("Agent limit set to {}", applied);
+                            }
+                        }
+                        Err(_) => shell_print!("Usage: agents --limit <n>"),
+                    }
+                } else if let Some(arg) = cmd.strip_prefix("schedule morning ") {
+                    match arg.trim().parse::<u8>() {
+                        Ok(hour) if supervisor.set_morning_hour(hour) => {
+                            shell_print!("Morning ambition now at {:02}:00.", hour);
+                        }
+                        _ => shell_print!("Usage: schedule morning <hour 0-23>"),
+                    }
+                } else if cmd == "fonts list" || cmd == "fonts" {
+                    let current = crate::gui::fonts::current_name();
+                    shell_print!("=== FONTS ===");
+                    for name in crate::gui::fonts::list_names() {
+                        let marker = if Some(name) == current { "*" } else { " " };
+                        shell_print!("  {} {}", marker, name);
+                    }
+                    shell_print!("(Switch with 'fonts set <name>')");
+                } else if let Some(name) = cmd.strip_prefix("fonts set ") {
+                    if crate::gui::fonts::set_by_name(name) {
+                        shell_print!("Font set to {}", name.trim());
+                        // Glyph widths changed, so does how much fits on a console line
+                        crate::gui::console::fit_to_width(crate::gui::graphics::WIDTH);
+                        if crate::gui::graphics::current_mode() == crate::gui::graphics::VgaMode::Graphics {
+                            crate::gui::desktop::mark_dirty();
+                            crate::gui::desktop::render();
+                        }
+                    } else {
+                        shell_print!("Unknown font '{}'. Available: {}", name.trim(),
+                            crate::gui::fonts::list_names().join(", "));
+                    }
+                } else if cmd == "log" || cmd.starts_with("log ") {
+                    let arg = cmd.strip_prefix("log").unwrap_or("").trim();
+                    let count = if arg.is_empty() { Ok(20) } else { arg.parse::<usize>() };
+                    match count {
+                        Ok(count) => {
+                            let events = crate::event_log::recent(count);
+                            if events.is_empty() {
+                                shell_print!("No events recorded yet.");
+                            } else {
+                                shell_print!("=== EVENT LOG (last {}) ===", events.len());
+                                for event in &events {
+                                    shell_print!("  {}", event);
+                                }
+                            }
+                            let dropped = crate::event_log::dropped();
+                            if dropped > 0 {
+                                shell_print!("  ({} events dropped while the log was busy)", dropped);
+                            }
+                        }
+                        Err(_) => shell_print!("Usage: log [n]"),
+                    }
+                } else if cmd == "evolution list" {
+                    use crate::agents::prompts::evolution;
+                    let rows = evolution::with_engine(|engine| {
+                        engine.experiments().iter().map(|e| {
+                            format!("  #{} char={} {}/{} trials {:?} - {}",
+                                e.id, e.character_id, e.current_trials, e.target_trials,
+                                e.status, e.hypothesis)
+                        }).collect::<Vec<String>>()
+                    }).unwrap_or_default();
+                    if rows.is_empty() {
+                        shell_print!("No experiments yet. Use 'evolution start <character_id> <hypothesis>'");
+                    } else {
+                        shell_print!("=== EVOLUTION EXPERIMENTS ({}) ===", rows.len());
+                        for row in &rows {
+                            shell_print!("{}", row);
+                        }
+                    }
+                } else if cmd.starts_with("evolution conclude ") {
+                    use crate::agents::prompts::evolution;
+                    let id_str = cmd.strip_prefix("evolution conclude ").unwrap_or("").trim();
+                    match id_str.parse::<u64>() {
+                        Ok(id) => {
+                            match evolution::with_engine_mut(|engine| engine.force_conclude(id)).flatten() {
+                                Some(result) => {
+                                    shell_print!("=== EXPERIMENT #{} CONCLUDED ===", result.experiment_id);
+                                    shell_print!("  Winner: v{}", result.winner.version_string());
+                                    shell_print!("  Baseline: {}%  Variant: {}%",
+                                        result.baseline_success_rate, result.variant_success_rate);
+                                    shell_print!("  Confidence: {}%", (result.confidence * 100.0) as u32);
+                                    shell_print!("  {}", result.recommendation);
+                                }
+                                None => shell_print!("Experiment #{} not found or already concluded", id),
+                            }
+                        }
+                        Err(_) => shell_print!("Usage: evolution conclude <id>"),
+                    }
+                } else if cmd == "protection check" || cmd.starts_with("protection check ") {
+                    use crate::agents::protection::{self, ChangeKind};
+                    let args: Vec<&str> = cmd.split_whitespace().skip(2).collect();
+                    match args.as_slice() {
+                        [path, change] => match ChangeKind::from_str(&change.to_lowercase()) {
+                            Some(change) => {
+                                let result = protection::check(path, change);
+                                shell_print!("{}: {}", result.path, change.description());
+                                shell_print!("  Tier:     {}", result.tier);
+                                if result.proceed {
+                                    shell_print!("  Proceed:  {{g}}yes{{/}}");
+                                } else {
+                                    shell_print!("  Proceed:  {{r}}no{{/}}");
+                                }
+                                shell_print!("  Ceremony: {}", result.ceremony);
+                            }
+                            None => shell_print!("Unknown change '{}'. Valid: {}", change, ChangeKind::NAMES.join(", ")),
+                        },
+                        _ => shell_print!("Usage: protection check <path> <{}>", ChangeKind::NAMES.join("|")),
+                    }
+                } else if cmd == "help --search" || cmd.starts_with("help --search ") {
+                    let term = cmd.strip_prefix("help --search").unwrap_or("").trim().to_lowercase();
+                    if term.is_empty() {
+                        shell_print!("Usage: help --search <term>");
+                    } else {
+                        let matches: Vec<&(&str, &str)> = HELP
+                            .iter()
+                            .filter(|(usage, description)| {
+                                usage.to_lowercase().contains(&term) || description.to_lowercase().contains(&term)
+                            })
+                            .collect();
+                        if matches.is_empty() {
+                            shell_print!("No commands match '{}'. Type 'help' for the full list.", term);
+                        } else {
+                            shell_print!("Commands matching '{}':", term);
+                            for (usage, description) in matches {
+                                shell_print!("  {:<9} - {}", usage, description);
+                            }
+                        }
+                    }
+                } else if let Some(name) = cmd.strip_prefix("agent log ") {
+                    match supervisor.agent_activity(name.trim()) {
+                        Some((name, activity)) if activity.is_empty() => {
+                            shell_print!("{} has no recorded activity yet.", name);
+                        }
+                        Some((name, activity)) => {
+                            shell_print!("=== {} ACTIVITY ({}) ===", name.to_uppercase(), activity.len());
+                            for line in activity {
+                                shell_print!("  {}", line);
+                            }
+                        }
+                        None => shell_print!("No agent named '{}'. Try 'roster'.", name.trim()),
+                    }
+                } else if let Some(name) = cmd.strip_prefix("agent resume ") {
+                    match supervisor.resume_agent(name.trim()) {
+                        Some(name) => shell_print!("{{g}}{} resumed.{{/}}", name),
+                        None => shell_print!("No agent named '{}' is paused. See 'health'.", name.trim()),
+                    }
+                } else if cmd == "agent" || cmd == "agent log" || cmd == "agent resume" {
+                    shell_print!("Usage: agent log <name> | agent resume <name>");
+                } else if cmd == "keyboard layout" || cmd.starts_with("keyboard layout ") {
+                    use crate::interrupts::{keyboard_layout, set_keyboard_layout, KEYBOARD_LAYOUTS};
+                    let name = cmd.strip_prefix("keyboard layout").unwrap_or("").trim();
+                    if !name.is_empty() && !set_keyboard_layout(name) {
+                        shell_print!("Unknown layout '{}'. Available: {}", name, KEYBOARD_LAYOUTS.join(", "));
+                    }
+                    shell_print!("Keyboard layout: {}", keyboard_layout());
+                } else if cmd == "keyrepeat" || cmd.starts_with("keyrepeat ") {
+                    let args: Vec<&str> = cmd.split_whitespace().skip(1).collect();
+                    match args.as_slice() {
+                        [] => {}
+                        [delay, interval] => match (delay.parse::<u64>(), interval.parse::<u64>()) {
+                            (Ok(delay), Ok(interval)) => crate::interrupts::set_key_repeat(delay, interval),
+                            _ => shell_print!("Usage: keyrepeat [<delay_ms> <interval_ms>]"),
+                        },
+                        _ => shell_print!("Usage: keyrepeat [<delay_ms> <interval_ms>]"),
+                    }
+                    let (delay, interval) = crate::interrupts::key_repeat();
+                    shell_print!("Key repeat: {} ms delay, then every {} ms", delay, interval);
+                } else if cmd == "spark" || cmd.starts_with("spark ") {
+                    use crate::agents::AgentId;
+                    use crate::agents::message::{FeedbackType, Message, MessageKind};
+                    let fields: Vec<&str> = cmd.strip_prefix("spark").unwrap_or("").split('|').map(str::trim).collect();
+                    match fields.as_slice() {
+                        [content, context] if !content.is_empty() && !context.is_empty() => {
+                            supervisor.send(Message::new(
+                                AgentId::OPERATOR,
+                                None,
+                                MessageKind::Feedback(FeedbackType::Spark {
+                                    content: String::from(*content),
+                                    context: String::from(*context),
+                                }),
+                            ));
+                            shell_print!("{{y}}Spark sent:{{/}} {}", content);
+                        }
+                        _ => shell_print!("Usage: spark <content> | <context>"),
+                    }
+                } else if cmd == "connect" || cmd.starts_with("connect ") {
+                    use crate::agents::AgentId;
+                    use crate::agents::message::{FeedbackType, Message, MessageKind};
+                    let fields: Vec<&str> = cmd.strip_prefix("connect").unwrap_or("").split('|').map(str::trim).collect();
+                    match fields.as_slice() {
+                        [from, to, pattern] if !from.is_empty() && !to.is_empty() && !pattern.is_empty() => {
+                            supervisor.send(Message::new(
+                                AgentId::OPERATOR,
+                                None,
+                                MessageKind::Feedback(FeedbackType::Connection {
+                                    from: String::from(*from),
+                                    to: String::from(*to),
+                                    pattern: String::from(*pattern),
+                                }),
+                            ));
+                            shell_print!("{{m}}Connection sent:{{/}} {} -> {}", from, to);
+                        }
+                        _ => shell_print!("Usage: connect <from> | <to> | <pattern>"),
+                    }
+                } else if cmd.starts_with("ping ") {
+                    let name = cmd.strip_prefix("ping ").unwrap_or("").trim();
+                    if supervisor.ping_agent(name) {
+                        shell_print!("Pinging {}... (latency is reported when the pong arrives)", name);
+                    } else {
+                        shell_print!("No agent named '{}'", name);
+                    }
+                } else if cmd.starts_with("cert simulate ") {
+                    use crate::agents::prompts::{academy, library};
+                    let args: Vec<&str> = cmd.strip_prefix("cert simulate ").unwrap_or("").split_whitespace().collect();
+                    let character_id = args.first().and_then(|s| s.parse::<u32>().ok());
+                    let invocations = args.get(1).and_then(|s| s.parse::<u64>().ok());
+                    let success_rate = args.get(2).and_then(|s| s.parse::<u64>().ok());
+                    match (character_id, invocations, success_rate) {
+                        (Some(character_id), Some(invocations), Some(success_rate))
+                            if args.len() == 3 && invocations > 0 && success_rate <= 100 =>
+                        {
+                            let successes = invocations * success_rate / 100;
+                            // Successes rate 5/5, failures 2/5, so satisfaction tracks the success rate
+                            let simulated = library::with_library_mut(|lib| {
+                                let prompt = lib.get_active_mut(character_id)?;
+                                for i in 0..invocations {
+                                    if i < successes {
+                                        prompt.metrics.record_success(CERT_SIM_COMPLETION_TICKS);
+                                        prompt.metrics.record_satisfaction(5);
+                                    } else {
+                                        prompt.metrics.record_failure();
+                                        prompt.metrics.record_satisfaction(2);
+                                    }
+                                }
+                                Some((prompt.id, prompt.certification, prompt.metrics.clone()))
+                            }).flatten();
+                            match simulated {
+                                Some((prompt_id, before, metrics)) => {
+                                    // Library lock is released here: upgrade_certification takes it again
+                                    let eligible = academy::with_academy(|a| {
+                                        a.check_certification_eligibility(character_id, &prompt_id, &metrics)
+                                    }).flatten();
+                                    let after = match eligible {
+                                        Some(level) if level > before => {
+                                            academy::with_academy_mut(|a| a.upgrade_certification(character_id, level));
+                                            level
+                                        }
+                                        _ => before,
+                                    };
+                                    shell_print!("Character {} ({}): {} invocations, {}% success, satisfaction {}.{}/5",
+                                        character_id, prompt_id.version_string(), metrics.invocations,
+                                        metrics.success_rate(), metrics.avg_satisfaction_x10() / 10,
+                                        metrics.avg_satisfaction_x10() % 10);
+                                    if after > before {
+                                        shell_print!("{{g}}Upgraded:{{/}} {} {} -> {} {}",
+                                            before.badge(), before.name(), after.badge(), after.name());
+                                    } else {
+                                        shell_print!("{{y}}No upgrade:{{/}} still {} {}", before.badge(), before.name());
+                                        if eligible.is_none() {
+                                            shell_print!("  (metrics or course completions below the Rookie requirements)");
+                                        }
+                                    }
+                                }
+                                None => shell_print!("No active prompt for character {}", character_id),
+                            }
+                        }
+                        _ => shell_print!("Usage: cert simulate <character_id> <invocations> <success_rate 0-100>"),
+                    }
+                } else if cmd.starts_with("evolution start ") {
+                    use crate::agents::prompts::{evolution, library};
+                    let args = cmd.strip_prefix("evolution start ").unwrap_or("").trim();
+                    let (char_str, hypothesis) = args.split_once(' ').unwrap_or((args, ""));
+                    let hypothesis = hypothesis.trim();
+                    match char_str.parse::<u32>() {
+                        Ok(character_id) if !hypothesis.is_empty() => {
+                            // Trivial variant: the active prompt plus a marker line
+                            let started = evolution::with_engine_mut(|engine| {
+                                library::with_library_mut(|lib| {
+                                    let (baseline_id, variant_text) = {
+                                        let active = lib.get_active(character_id)?;
+                                        (active.id, format!("{}\n\n[Variant: {}]", active.system_prompt, hypothesis))
+                                    };
+                                    engine.start_experiment(lib, baseline_id, &variant_text, hypothesis, 20)
+                                }).flatten()
+                            }).flatten();
+                            match started {
+                                Some(id) => shell_print!("Started experiment #{} for character {} (20 trials)", id, character_id),
+                                None => shell_print!("No active prompt for character {}", character_id),
+                            }
+                        }
+                        _ => shell_print!("Usage: evolution start <character_id> <hypothesis>"),
+                    }
+                } else if cmd.starts_with("outbox cap ") {
+                    match cmd.strip_prefix("outbox cap ").unwrap_or("").trim().parse::<usize>() {
+                        Ok(cap) if cap > 0 => {
+                            supervisor.set_outbox_cap(cap);
+                            shell_print!("Outbox cap set to {} messages per agent per tick", cap);
+                        }
+                        _ => shell_print!("Usage: outbox cap <n>  (n >= 1)"),
+                    }
+                } else if let Some(suite) = cmd.strip_prefix("test --suite") {
+                    let suite = suite.trim();
+                    if TEST_SUITES.contains(&suite) {
+                        shell_print!("Triggering Thomas to run the '{}' suite...", suite);
+                        supervisor.request_tests(Some(suite), true);
+                    } else {
+                        shell_print!("Usage: test --suite <{}>", TEST_SUITES.join("|"));
+                    }
+                } else if cmd.starts_with("task add ") {
+                    let text = cmd.strip_prefix("task add ").unwrap_or("").trim();
+                    if text.is_empty() {
+                        shell_print!("Usage: task add <text>");
+                    } else {
+                        let id = crate::storage::memory_store::add_task(text, "shell", supervisor.current_tick());
+                        shell_print!("Added task #{}", id);
+                    }
+                } else if cmd == "task list" || cmd == "task list --all" {
+                    use crate::storage::memory_store::TaskStatus;
+                    let include_done = cmd.ends_with("--all");
+                    let tasks = crate::storage::memory_store::tasks(include_done);
+                    if tasks.is_empty() {
+                        shell_print!("No open tasks. Use 'task add <text>' to create one.");
+                    } else {
+                        shell_print!("=== TASKS ({}) ===", tasks.len());
+                        for task in &tasks {
+                            let mark = if task.task_status == Some(TaskStatus::Done) { "x" } else { " " };
+                            shell_print!("  [{}] #{} {} ({})", mark, task.id, task.content, task.source);
+                        }
+                    }
+                } else if cmd.starts_with("task done ") {
+                    let id_str = cmd.strip_prefix("task done ").unwrap_or("").trim();
+                    match id_str.parse::<u64>() {
+                        Ok(id) if crate::storage::memory_store::complete_task(id) => {
+                            shell_print!("Task #{} marked done", id);
+                        }
+                        Ok(id) => shell_print!("No task with id {}", id),
+                        Err(_) => shell_print!("Usage: task done <id>"),
+                    }
+                } else if cmd == "insights --summary" {
+                    show_insights(supervisor, None, true);
+                } else if cmd.starts_with("insights --filter") {
+                    let kind = cmd.strip_prefix("insights --filter").unwrap_or("").trim();
+                    match kind {
+                        "spark" | "connection" | "resource" | "feeling" => {
+                            show_insights(supervisor, Some(kind), false);
+                        }
+                        _ => shell_print!("Usage: insights --filter spark|connection|resource|feeling"),
+                    }
+                } else if cmd == "desktop layout" || cmd.starts_with("desktop layout ") {
+                    use crate::gui::desktop;
+                    let name = cmd["desktop layout".len()..].trim();
+                    if name.is_empty() {
+                        match desktop::with_desktop(|layout| layout.layout()) {
+                            Some(current) => shell_print!("Current layout: {}", current),
+                            None => shell_print!("Desktop not initialized - run 'desktop' first"),
+                        }
+                    } else if !desktop::LAYOUTS.contains(&name) {
+                        shell_print!("Unknown layout '{}'.", name);
+                    } else if build_desktop_layout(name, supervisor) {
+                        desktop::render();
+                        shell_print!("Layout set to '{}'.", name);
+                    } else {
+                        shell_print!("Desktop not initialized - run 'desktop' first");
+                    }
+                    if !desktop::LAYOUTS.contains(&name) {
+                        shell_print!("Available layouts: {}", desktop::LAYOUTS.join(", "));
+                    }
+                } else if cmd == "desktop theme" || cmd.starts_with("desktop theme ") {
+                    use crate::gui::graphics;
+                    let name = cmd["desktop theme".len()..].trim();
+                    if name.is_empty() {
+                        shell_print!("Current theme: {}", graphics::current_theme());
+                    } else if graphics::set_theme(name) {
+                        shell_print!("Theme set to '{}'.", name);
+                    } else {
+                        shell_print!("Unknown theme '{}'.", name);
+                    }
+                    if name.is_empty() || graphics::current_theme() != name {
+                        shell_print!("Available themes:");
+                        for (theme, _) in graphics::THEMES.iter() {
+                            shell_print!("  {}", theme);
+                        }
+                    }
+                } else if cmd == "serial send" || cmd.starts_with("serial send ") {
+                    // Low-level escape hatch: the bridge sees exactly this line
+                    let text = cmd.strip_prefix("serial send").unwrap_or("").trim();
+                    if text.is_empty() {
+                        shell_print!("Usage: serial send <text>");
+                    } else if let Some(prefix) = INCOMING_PREFIXES.iter().find(|p| text.starts_with(**p)) {
+                        shell_print!("Refusing to send '{}' - the shell treats that prefix as bridge input", prefix);
+                    } else {
+                        serial_println!("{}", text);
+                        shell_print!("Sent {} bytes to serial", text.len());
+                    }
+                } else if cmd == "serial stats" {
+                    let st = crate::serial::rx_stats();
+                    shell_print!("=== SERIAL RX ===");
+                    shell_print!("  Received: {} bytes", st.received);
+                    shell_print!("  Dropped:  {} bytes", st.dropped);
+                    shell_print!("  Queued:   {}/{} bytes", st.queued, st.capacity);
+                    if st.dropped > 0 {
+                        shell_print!("  Warning: receive queue overflowed - bridge data was lost");
+                    }
+                } else if cmd == "notify stats" {
+                    let (sent, dropped) = crate::serial::notify_stats();
+                    shell_print!("=== NOTIFICATIONS (limit {}/s) ===", crate::serial::NOTIFY_PER_SECOND);
+                    shell_print!("  Sent:    {}", sent);
+                    shell_print!("  Dropped: {}", dropped);
+                } else if let Some(arg) = cmd.strip_prefix("console tabs") {
+                    match arg.trim().parse::<usize>() {
+                        Ok(n) if n > 0 => {
+                            crate::gui::console::set_tab_stop(n);
+                            shell_print!("Console tab stop set to {} columns.", n);
+                        }
+                        _ => shell_print!("Usage: console tabs <n> (n >= 1)"),
+                    }
+                } else if let Some(text) = cmd.strip_prefix("console echo") {
+                    // Developer tool: controlled input for the console's
+                    // rendering, scrollback and word wrap
+                    use crate::gui::{console, graphics};
+                    let text = text.strip_prefix(' ').unwrap_or(text);
+                    console::add_output_line(String::from(text));
+                    if graphics::current_mode() == graphics::VgaMode::Graphics {
+                        console::render_overlay(graphics::WIDTH, graphics::HEIGHT);
+                        serial_println!("[CONSOLE] {}", text);
+                    } else {
+                        shell_print!("{}", text);
+                        shell_print!("(text mode - switch to graphics mode to see it in the console overlay)");
+                    }
                 } else if cmd == "breathe" {
                     shell_print!("Usage: breathe [your ambition text]");
                     shell_print!("Example: breathe Today I want us to build the graphics system");
+                } else if cmd == "breathe --history" {
+                    let history = supervisor.ambition_history();
+                    if history.is_empty() {
+                        shell_print!("No ambitions breathed yet.");
+                    } else {
+                        shell_print!("=== AMBITION HISTORY ({}) ===", history.len());
+                        // Only the ambition breathed now is starred; after a reboot nothing is yet
+                        let living = history.iter().rposition(|(_, a)| Some(a) == supervisor.get_ambition());
+                        for (i, (tick, ambition)) in history.iter().enumerate() {
+                            let marker = if Some(i) == living { "{g}*{/}" } else { " " };
+                            shell_print!("{} {:>2}. [tick {}] {}", marker, i + 1, tick, ambition);
+                        }
+                        shell_print!("Use 'breathe --restore <n>' to breathe one in again.");
+                    }
+                } else if let Some(arg) = cmd.strip_prefix("breathe --restore") {
+                    match arg.trim().parse::<usize>().ok().and_then(|n| supervisor.restore_ambition(n)) {
+                        Some(_) => {}
+                        None => shell_print!("Usage: breathe --restore <n> (see 'breathe --history')"),
+                    }
                 } else if cmd.starts_with("breathe ") {
                     let ambition = cmd.strip_prefix("breathe ").unwrap_or("").trim();
                     if ambition.is_empty() {
@@ -604,9 +1918,463 @@ impl Shell {
                     }
                 } else {
                     shell_print!("Unknown command: {}", cmd);
+                    if let Some(suggestion) = suggest_command(cmd) {
+                        shell_print!("Did you mean `{}`?", suggestion);
+                    }
                     shell_print!("Type 'help' for a list of commands.");
                 }
             }
         }
     }
 }
+
+/// Print a line of JSON verbatim to VGA and serial
+///
+/// Bypasses `shell_print!`, whose color markers could match text inside
+/// JSON strings.
+fn print_json(json: &str) {
+    use crate::serial_println;
+    println!("{}", json);
+    serial_println!("{}", json);
+}
+
+/// Rebuild the desktop zones as one of `desktop::LAYOUTS` from current supervisor
+/// data. Returns false if the desktop hasn't been initialized.
+fn build_desktop_layout(name: &str, supervisor: &Supervisor) -> bool {
+    use crate::gui::desktop;
+    let default_ambition = String::from("Today, I want us to build something amazing together.");
+    let ambition = supervisor.get_ambition().unwrap_or(&default_ambition);
+    let commitments = vec![
+        String::from("YOU: Set clear goals"),
+        String::from("AI: Support with tools and insights"),
+        String::from("COLLAB: Work together"),
+    ];
+    let theme = desktop::theme_of_the_day();
+
+    desktop::with_desktop(|layout| match name {
+        "focus" => layout.create_focus_layout(Some(ambition), &commitments, theme.as_deref()),
+        "dashboard" => {
+            let agents: Vec<(String, String)> = supervisor.roster()
+                .into_iter()
+                .map(|entry| {
+                    let status = match entry.focus {
+                        Some(focus) => format!("{:?} - {}", entry.state, focus),
+                        None => format!("{:?}", entry.state),
+                    };
+                    (entry.name, status)
+                })
+                .collect();
+            layout.create_dashboard_layout(&agents);
+        }
+        "journal" => layout.create_journal_layout(&supervisor.journal_entries()),
+        _ => layout.create_ambition_layout(Some(ambition), &commitments, theme.as_deref()),
+    }).is_some()
+}
+
+/// Repaint the graphics screen: the desktop if one is laid out, else a blank frame
+fn repaint_graphics_screen() {
+    use crate::gui::{desktop, graphics};
+    if desktop::with_desktop(|_| ()).is_some() {
+        desktop::render();
+    } else {
+        graphics::with_graphics(|gfx| {
+            gfx.clear(graphics::Color::Black);
+            gfx.swap_buffers();
+        });
+    }
+}
+
+/// Run the agents for `ticks` supervisor ticks, one per timer interrupt,
+/// so messages sent by a `demo` step are routed and answered
+fn demo_pause(supervisor: &mut Supervisor, ticks: u64) {
+    for _ in 0..ticks {
+        supervisor.tick();
+        x86_64::instructions::hlt();
+    }
+}
+
+/// Scripted scenario touching most subsystems: ambition, morning ritual,
+/// operator Sparks, Thomas's tests, serendipity and the constellation.
+/// The PRNG is seeded with `DEMO_SEED` for the run, so agents' random
+/// choices (Thomas's ambition phrasing) repeat, and reseeded from the
+/// hardware afterwards.
+fn run_demo(supervisor: &mut Supervisor) {
+    use crate::serial_println;
+    use crate::agents::AgentId;
+    use crate::agents::message::{FeedbackType, Message, MessageKind};
+
+    let had_ambition = supervisor.get_ambition().is_some();
+    crate::rng::seed(DEMO_SEED);
+    shell_print!("{{b}}=== GENESIS DEMO ==={{/}}");
+    shell_print!("{{b}}[demo]{{/}} PRNG seeded with {:#x} - agents' random choices repeat each run.", DEMO_SEED);
+    demo_pause(supervisor, DEMO_PAUSE_TICKS);
+
+    shell_print!("{{b}}[demo]{{/}} 1. Breathing in an ambition - the soul every agent aligns to.");
+    supervisor.breathe(String::from("Today we map a constellation of ideas across memory and graphics"));
+    demo_pause(supervisor, DEMO_PAUSE_TICKS);
+
+    shell_print!("{{b}}[demo]{{/}} 2. The morning ritual: agents answer with their ambitions");
+    shell_print!("       (Thomas phrases his with the seeded PRNG).");
+    supervisor.morning_ambition();
+    demo_pause(supervisor, DEMO_PAUSE_TICKS);
+
+    shell_print!("{{b}}[demo]{{/}} 3. The operator adds two Sparks that share a theme.");
+    let sparks = [
+        ("Memory search can surface the constellation of ideas", "demo: memory"),
+        ("Graphics could draw the constellation of ideas as a map", "demo: graphics"),
+    ];
+    for (content, context) in sparks {
+        supervisor.send(Message::new(
+            AgentId::OPERATOR,
+            None,
+            MessageKind::Feedback(FeedbackType::Spark {
+                content: String::from(content),
+                context: String::from(context),
+            }),
+        ));
+        shell_print!("  {{y}}Spark:{{/}} {}", content);
+    }
+    demo_pause(supervisor, DEMO_PAUSE_TICKS);
+
+    shell_print!("{{b}}[demo]{{/}} 4. Thomas runs the test suites and reports back.");
+    supervisor.request_tests(None, false);
+    demo_pause(supervisor, DEMO_PAUSE_TICKS * 2);
+
+    shell_print!("{{b}}[demo]{{/}} 5. The serendipity engine looks for a shared theme.");
+    if supervisor.force_serendipity() {
+        shell_print!("  {{g}}Connection found.{{/}}");
+    } else {
+        shell_print!("  {{y}}No new theme to connect{{/}} (already linked by an earlier run?)");
+    }
+    demo_pause(supervisor, DEMO_PAUSE_TICKS);
+
+    shell_print!("{{b}}[demo]{{/}} 6. The resulting constellation:");
+    show_insights(supervisor, None, false);
+    crate::rng::init();
+    shell_print!();
+    shell_print!("{{b}}[demo]{{/}} Done.");
+    if had_ambition {
+        shell_print!("Your previous ambition is listed in 'breathe --history'.");
+    }
+}
+
+/// Print the constellation of insights
+///
+/// `filter` keeps only one kind ("spark", "connection", "resource", "feeling");
+/// `summary_only` prints just the per-kind counts.
+fn show_insights(supervisor: &Supervisor, filter: Option<&str>, summary_only: bool) {
+    use crate::serial_println;
+    use crate::agents::message::FeedbackType;
+
+    let insights = supervisor.get_insights();
+    if insights.is_empty() {
+        shell_print!("No insights collected yet.");
+        shell_print!("Agents will send Sparks and Connections as they work.");
+        return;
+    }
+
+    let mut spark_count = 0;
+    let mut connection_count = 0;
+    let mut resource_count = 0;
+    let mut feeling_count = 0;
+    let mut shown = 0;
+
+    if !summary_only {
+        shell_print!("Constellation of Insights ({} total):", insights.len());
+        shell_print!();
+    }
+
+    for (i, insight) in insights.iter().enumerate() {
+        let kind = match insight {
+            FeedbackType::Spark { .. } => { spark_count += 1; "spark" }
+            FeedbackType::Connection { .. } => { connection_count += 1; "connection" }
+            FeedbackType::Resource { .. } => { resource_count += 1; "resource" }
+            FeedbackType::Feeling { .. } => { feeling_count += 1; "feeling" }
+        };
+        if summary_only || filter.map(|f| f != kind).unwrap_or(false) {
+            continue;
+        }
+        shown += 1;
+        match insight {
+            FeedbackType::Spark { content, context } => {
+                shell_print!("  [{:3}] ✨ SPARK", i + 1);
+                shell_print!("       Content: {}", content);
+                shell_print!("       Context: {}", context);
+            }
+            FeedbackType::Connection { from, to, pattern } => {
+                shell_print!("  [{:3}] 🔗 CONNECTION", i + 1);
+                shell_print!("       From: {}", from);
+                shell_print!("       To: {}", to);
+                shell_print!("       Pattern: {}", pattern);
+            }
+            FeedbackType::Resource { description, location } => {
+                shell_print!("  [{:3}] 📚 RESOURCE", i + 1);
+                shell_print!("       Description: {}", description);
+                shell_print!("       Location: {}", location);
+            }
+            FeedbackType::Feeling { tag, intensity } => {
+                let age = supervisor.current_tick().saturating_sub(supervisor.insight_ticks()[i]);
+                let effective = crate::agents::message::decayed_intensity(*intensity, age);
+                shell_print!("  [{:3}] 💭 FEELING", i + 1);
+                shell_print!("       Tag: {}", tag);
+                if effective < *intensity {
+                    shell_print!("       Intensity: {}% (fading from {}%)", effective, intensity);
+                } else {
+                    shell_print!("       Intensity: {}%", intensity);
+                }
+            }
+        }
+        shell_print!();
+    }
+
+    if let Some(kind) = filter {
+        if shown == 0 {
+            shell_print!("No insights of kind '{}'.", kind);
+        }
+        return;
+    }
+
+    shell_print!("Summary:");
+    shell_print!("  Sparks: {}", spark_count);
+    shell_print!("  Connections: {}", connection_count);
+    shell_print!("  Resources: {}", resource_count);
+    shell_print!("  Feelings: {}", feeling_count);
+}
+
+/// Render a session id (`YYYYMMDDhhmmss`) as its RTC start time
+fn format_session(session: u64) -> String {
+    if session == 0 {
+        return String::from("(before sessions)");
+    }
+    let (date, time) = (session / 1_000_000, session % 1_000_000);
+    format!("{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        date / 10_000, date / 100 % 100, date % 100, time / 10_000, time / 100 % 100, time % 100)
+}
+
+/// Format a duration as H:MM:SS (hours keep counting past 24)
+fn format_hms(total_seconds: u64) -> String {
+    format!("{}:{:02}:{:02}", total_seconds / 3600, total_seconds / 60 % 60, total_seconds % 60)
+}
+
+/// Levenshtein distance between two strings (in chars)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + if ca == *cb { 0 } else { 1 };
+            row[j + 1] = substitute.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        core::mem::swap(&mut prev, &mut row);
+    }
+    prev[b.len()]
+}
+
+/// Closest known command to the first word of `input`, if it's a likely typo
+fn suggest_command(input: &str) -> Option<&'static str> {
+    let word = input.split_whitespace().next()?;
+    // Allow one edit in short words, two in longer ones
+    let threshold = if word.chars().count() <= 4 { 1 } else { 2 };
+    COMMAND_NAMES
+        .iter()
+        .map(|name| (edit_distance(word, name), *name))
+        .filter(|(distance, _)| (1..=threshold).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Shorten `text` to at most `max` bytes' worth of chars, ending in "..."
+fn preview(text: &str, max: usize) -> String {
+    if text.len() > max {
+        let s: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", s)
+    } else {
+        String::from(text)
+    }
+}
+
+/// Print the Connection insights as a graph: an adjacency list of
+/// `from -> to: pattern` edges, or grouped by pattern theme
+fn show_connections(supervisor: &Supervisor, by_pattern: bool) {
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use crate::serial_println;
+    use crate::agents::message::FeedbackType;
+
+    const LABEL_LEN: usize = 30;
+
+    // (from, to) -> patterns linking them, in arrival order
+    let mut edges: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for insight in supervisor.get_insights() {
+        if let FeedbackType::Connection { from, to, pattern } = insight {
+            let patterns = edges.entry((preview(from, LABEL_LEN), preview(to, LABEL_LEN))).or_default();
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+            }
+        }
+    }
+    if edges.is_empty() {
+        shell_print!("No connections yet.");
+        shell_print!("The serendipity engine links insights that share a theme.");
+        return;
+    }
+
+    let nodes: BTreeSet<&String> = edges.keys().flat_map(|(from, to)| [from, to]).collect();
+    shell_print!("=== CONNECTIONS ({} nodes, {} edges) ===", nodes.len(), edges.len());
+
+    if by_pattern {
+        let mut themes: BTreeMap<&String, Vec<&(String, String)>> = BTreeMap::new();
+        for (edge, patterns) in &edges {
+            for pattern in patterns {
+                themes.entry(pattern).or_default().push(edge);
+            }
+        }
+        for (pattern, theme_edges) in themes {
+            shell_print!("  {{m}}{}{{/}} ({})", preview(pattern, LABEL_LEN * 2), theme_edges.len());
+            for (from, to) in theme_edges {
+                shell_print!("    {} -> {}", from, to);
+            }
+        }
+    } else {
+        for ((from, to), patterns) in &edges {
+            shell_print!("  {} -> {}: {}", from, to, preview(&patterns.join("; "), LABEL_LEN * 2));
+        }
+    }
+}
+
+/// Print Resource insights as a numbered reading list, newest first and one
+/// per location
+///
+/// This session's constellation comes first (it holds the newest insights),
+/// then Resource memories from every session by descending ID.
+fn show_resources(supervisor: &Supervisor) {
+    use crate::serial_println;
+    use crate::agents::message::FeedbackType;
+    use crate::storage::memory_store::{self, MemoryKind};
+
+    let mut recent: Vec<(&u64, &FeedbackType)> = supervisor.insight_ticks().iter()
+        .zip(supervisor.get_insights())
+        .collect();
+    recent.sort_by(|a, b| b.0.cmp(a.0));
+    let remembered = memory_store::by_kind(MemoryKind::Resource);
+
+    let mut list: Vec<(String, String)> = Vec::new();
+    let insights = recent.iter().filter_map(|(_, insight)| match insight {
+        FeedbackType::Resource { description, location } => Some((description.as_str(), location.as_str())),
+        _ => None,
+    });
+    let memories = remembered.iter().filter_map(|entry| MemoryKind::split_resource(&entry.content));
+    for (description, location) in insights.chain(memories) {
+        if !list.iter().any(|(_, seen)| seen == location) {
+            list.push((String::from(description), String::from(location)));
+        }
+    }
+
+    if list.is_empty() {
+        shell_print!("No resources yet.");
+        shell_print!("Resources appear here as agents discover references (e.g. Archimedes's workspace).");
+        return;
+    }
+    shell_print!("=== READING LIST ({}) ===", list.len());
+    for (n, (description, location)) in list.iter().enumerate() {
+        shell_print!("  {:>2}. {}", n + 1, description);
+        shell_print!("      {{b}}{}{{/}}", location);
+    }
+}
+
+/// Pop the last char from the input buffer and report how many columns it
+/// occupied as (VGA columns, serial terminal columns).
+///
+/// The VGA writer renders each non-ASCII byte as a ■ cell, so a multi-byte
+/// char takes one VGA column per UTF-8 byte. A serial terminal renders it at
+/// its display width instead.
+fn erase_last_char(buffer: &mut String) -> Option<(usize, usize)> {
+    let c = buffer.pop()?;
+    let vga_cols = if c.is_ascii() { 1 } else { c.len_utf8() };
+    Some((vga_cols, display_width(c)))
+}
+
+/// Approximate terminal display width of a char
+///
+/// Combining marks and joiners take no column; East Asian wide characters
+/// and emoji take two. Everything else takes one.
+fn display_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200D | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backspace_removes_whole_multibyte_char() {
+        let mut buffer = String::from("hi\u{1F642}");
+        assert_eq!(erase_last_char(&mut buffer), Some((4, 2)));
+        assert_eq!(buffer, "hi");
+
+        let mut buffer = String::from("caf\u{e9}");
+        assert_eq!(erase_last_char(&mut buffer), Some((2, 1)));
+        assert_eq!(buffer, "caf");
+
+        assert_eq!(erase_last_char(&mut buffer), Some((1, 1)));
+        assert_eq!(buffer, "ca");
+    }
+
+    #[test]
+    fn backspace_on_empty_buffer_is_noop() {
+        let mut buffer = String::new();
+        assert_eq!(erase_last_char(&mut buffer), None);
+    }
+
+    #[test]
+    fn preview_truncates_long_labels() {
+        assert_eq!(preview("short", 10), "short");
+        assert_eq!(preview("exactly-10", 10), "exactly-10");
+        assert_eq!(preview("a much longer label", 10), "a much ...");
+    }
+
+    #[test]
+    fn edit_distance_counts_single_char_edits() {
+        assert_eq!(edit_distance("memory", "memory"), 0);
+        assert_eq!(edit_distance("acedemy", "academy"), 1);
+        assert_eq!(edit_distance("memroy", "memory"), 2);
+        assert_eq!(edit_distance("", "ping"), 4);
+    }
+
+    #[test]
+    fn every_command_has_a_help_entry() {
+        for name in COMMAND_NAMES {
+            assert!(HELP.iter().any(|(usage, _)| usage.split_whitespace().next() == Some(*name)),
+                "no help entry for '{name}'");
+        }
+    }
+
+    #[test]
+    fn format_hms_pads_minutes_and_seconds() {
+        assert_eq!(format_hms(0), "0:00:00");
+        assert_eq!(format_hms(3_725), "1:02:05");
+        assert_eq!(format_hms(90_061), "25:01:01");
+    }
+
+    #[test]
+    fn typos_suggest_the_intended_command() {
+        assert_eq!(suggest_command("memroy stats"), Some("memory"));
+        assert_eq!(suggest_command("statsu"), Some("status"));
+        assert_eq!(suggest_command("acedemy"), Some("academy"));
+        assert_eq!(suggest_command("xyzzy"), None);
+    }
+}
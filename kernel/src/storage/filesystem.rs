@@ -2,6 +2,11 @@
 //!
 //! Provides basic file operations for agent data persistence.
 //! Initially in-memory, will be extended to persistent storage.
+//!
+//! Whole-file writes (`write_file_string`, `write_file_atomic`) stage the
+//! data at a temporary path and then rename it over the target, so a write
+//! interrupted part-way leaves the previous file intact. A stale temporary
+//! file from such a write is simply overwritten by the next one.
 
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -16,6 +21,9 @@ pub enum FileSystemEntry {
     Directory { entries: BTreeMap<String, FileSystemEntry> },
 }
 
+/// Suffix of the temporary file an atomic write stages its data in
+const TEMP_SUFFIX: &str = ".tmp";
+
 /// Simple in-memory file system
 pub struct FileSystem {
     root: BTreeMap<String, FileSystemEntry>,
@@ -112,9 +120,78 @@ impl FileSystem {
         Ok(())
     }
     
-    /// Write file from string
+    /// Write file from string (atomically, see `write_file_atomic`)
     pub fn write_file_string(&mut self, path: &str, content: &str) -> Result<(), FileSystemError> {
-        self.write_file(path, content.as_bytes())
+        self.write_file_atomic(path, content.as_bytes())
+    }
+    
+    /// Replace a file so that readers see either the old or the new
+    /// content, never a partial write: the data goes to a temporary file
+    /// first, which is then renamed over `path`
+    pub fn write_file_atomic(&mut self, path: &str, data: &[u8]) -> Result<(), FileSystemError> {
+        let temp = temp_path(path);
+        self.write_file(&temp, data)?;
+        self.rename(&temp, path)
+    }
+    
+    /// Move an entry to `to`, replacing any file there. Fails without
+    /// changing anything if `from` is missing or `to` is a directory or
+    /// has no parent directory.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), FileSystemError> {
+        // Check the destination first so a failure leaves both paths untouched
+        let (dir, name) = self.parent_dir_mut(to)?;
+        if matches!(dir.get(name), Some(FileSystemEntry::Directory { .. })) {
+            return Err(FileSystemError::InvalidPath);
+        }
+        
+        let (dir, name) = self.parent_dir_mut(from)?;
+        let entry = dir.remove(name).ok_or(FileSystemError::NotFound)?;
+        let (dir, name) = self.parent_dir_mut(to)?;
+        dir.insert(String::from(name), entry);
+        Ok(())
+    }
+    
+    /// The directory holding `path`, and the entry name within it
+    fn parent_dir_mut<'p>(&mut self, path: &'p str)
+        -> Result<(&mut BTreeMap<String, FileSystemEntry>, &'p str), FileSystemError> {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let (name, parents) = parts.split_last().ok_or(FileSystemError::InvalidPath)?;
+        let mut current = &mut self.root;
+        for part in parents {
+            match current.get_mut(*part) {
+                Some(FileSystemEntry::Directory { entries }) => {
+                    current = entries;
+                }
+                _ => return Err(FileSystemError::NotFound),
+            }
+        }
+        Ok((current, name))
+    }
+    
+    /// Append to a file in place (creates it if missing)
+    pub fn append_file(&mut self, path: &str, data: &[u8]) -> Result<(), FileSystemError> {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut current = &mut self.root;
+        
+        // Navigate to parent directory
+        for part in parts.iter().take(parts.len().saturating_sub(1)) {
+            match current.get_mut(*part) {
+                Some(FileSystemEntry::Directory { entries }) => {
+                    current = entries;
+                }
+                _ => return self.write_file(path, data),
+            }
+        }
+        
+        let filename = parts.last().ok_or(FileSystemError::InvalidPath)?;
+        match current.get_mut(*filename) {
+            Some(FileSystemEntry::File { content }) => {
+                content.extend_from_slice(data);
+                Ok(())
+            }
+            Some(FileSystemEntry::Directory { .. }) => Err(FileSystemError::InvalidPath),
+            None => self.write_file(path, data),
+        }
     }
     
     /// List directory
@@ -173,6 +250,13 @@ impl FileSystem {
     }
 }
 
+/// Temporary path an atomic write of `path` stages its data in
+fn temp_path(path: &str) -> String {
+    let mut temp = String::from(path.trim_end_matches('/'));
+    temp.push_str(TEMP_SUFFIX);
+    temp
+}
+
 /// File system errors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileSystemError {
@@ -202,11 +286,21 @@ pub fn write_file(path: &str, data: &[u8]) -> Result<(), FileSystemError> {
     FILESYSTEM.lock().write_file(path, data)
 }
 
-/// Write file as string to global file system
+/// Append to file in global file system
+pub fn append_file(path: &str, data: &[u8]) -> Result<(), FileSystemError> {
+    FILESYSTEM.lock().append_file(path, data)
+}
+
+/// Write file as string to global file system (atomically)
 pub fn write_file_string(path: &str, content: &str) -> Result<(), FileSystemError> {
     FILESYSTEM.lock().write_file_string(path, content)
 }
 
+/// Replace a file in the global file system atomically
+pub fn write_file_atomic(path: &str, data: &[u8]) -> Result<(), FileSystemError> {
+    FILESYSTEM.lock().write_file_atomic(path, data)
+}
+
 /// List directory in global file system
 pub fn list_dir(path: &str) -> Result<Vec<String>, FileSystemError> {
     FILESYSTEM.lock().list_dir(path)
@@ -227,4 +321,30 @@ pub fn dir_exists(path: &str) -> bool {
     FILESYSTEM.lock().dir_exists(path)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    const PATH: &str = "/storage/memory/memories.dat";
+
+    #[test]
+    fn interrupted_write_leaves_the_previous_file_intact() {
+        let mut fs = FileSystem::new();
+        fs.write_file_atomic(PATH, b"good data").unwrap();
+
+        // A save that dies after staging part of its data never renamed it
+        fs.write_file(&temp_path(PATH), b"trunc").unwrap();
+        assert_eq!(fs.read_file(PATH).unwrap(), b"good data");
+
+        // A rename that can't complete changes nothing either
+        fs.create_directory("/storage/memory/dir").unwrap();
+        assert_eq!(fs.rename(&temp_path(PATH), "/storage/memory/dir"), Err(FileSystemError::InvalidPath));
+        assert_eq!(fs.rename(&temp_path(PATH), "/nowhere/memories.dat"), Err(FileSystemError::NotFound));
+        assert_eq!(fs.read_file(PATH).unwrap(), b"good data");
+
+        // The next write replaces the stale temp file and the target
+        fs.write_file_string(PATH, "new data").unwrap();
+        assert_eq!(fs.read_file_string(PATH).unwrap(), "new data");
+        assert!(!fs.file_exists(&temp_path(PATH)));
+    }
+}
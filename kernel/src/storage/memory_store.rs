@@ -7,6 +7,7 @@
 //! - BTreeMap-based inverted index (no hashing crates needed)
 //! - BM25-lite scoring with integer math (no floats/log)
 //! - Pipe-delimited serialization (no serde)
+//! - Write-ahead log of store/delete ops between full snapshots
 //!
 //! ## Architecture
 //!
@@ -21,8 +22,85 @@
 //! │  search() ─> BM25 score ──> ranked ids │
 //! │  serialize() ──> pipe-delimited text   │
 //! │  deserialize() <── filesystem load     │
+//! │  wal ──> checkpoint() ──> serial bridge │
 //! └─────────────────────────────────────────┘
 //! ```
+//!
+//! ## Write-Ahead Log
+//!
+//! Every mutation appends one op line to the WAL:
+//!
+//! ```text
+//! +<entry line>     store (same fields as a v2 snapshot line)
+//! -<id>             delete (capacity eviction or `forget`)
+//! ~<id>|<status>    task status change
+//! ^<id>|<0 or 1>    unpin / pin
+//! =<id>|<tag,...>   replace tags
+//! ```
+//!
+//! `checkpoint()` streams pending ops to the bridge as `[MEMORY_WAL]` lines,
+//! which it appends to its WAL file. A full snapshot (`persist_to_serial`)
+//! supersedes the WAL and clears it. On load, ops following a `#wal` marker
+//! line are replayed on top of the snapshot.
+//!
+//! The filesystem copy uses the same layout: `save()` appends new ops to
+//! `memories.dat` rather than rewriting it, so deletions are `-<id>`
+//! tombstones. `compact()` rewrites the file as a clean snapshot, and runs
+//! automatically once `COMPACT_AFTER_OPS` ops have been appended. Loading
+//! skips tombstoned ids entirely, so they never enter the index.
+//!
+//! ## Sessions
+//!
+//! Each boot is a session, identified by its RTC start time packed as
+//! `YYYYMMDDhhmmss` (see `begin_session`). Every entry records the session
+//! that created it in an optional trailing field, so lines written before
+//! sessions existed still load (as session 0, "unknown").
+//!
+//! ## Importance
+//!
+//! When the store is full, `store()` evicts the unpinned entry with the
+//! lowest `MemoryEntry::importance`, a sum of kind weight (Sparks and
+//! Connections count most), retrieval count, and recency (halved every
+//! `RECENCY_HALF_TICKS` of age). Age is measured against the tick of the
+//! store being made; ticks restart every boot, so entries from an earlier
+//! session get no recency at all. Pinned entries are never evicted, and
+//! neither is the entry just stored. A pinned entry is serialized with a
+//! trailing `|1` field after the session, in both snapshot formats.
+//!
+//! ## Tags
+//!
+//! Operators can label entries by hand (`memory tag <id> <tag>`). Tags are
+//! lowercase words indexed under `#tag` keys, which content keywords never
+//! collide with since keyword extraction strips `#`; a `#tag` word in a
+//! search query matches them. A tagged entry adds the pin flag (`0` or `1`)
+//! and a comma-separated tag field after the session.
+//!
+//! ## Compact Snapshots
+//!
+//! Snapshots sent to the bridge use `serialize_compact()`, a `#v2c` variant
+//! of the v2 format that shrinks serial transfers:
+//!
+//! ```text
+//! #v2c
+//! #k kw0,kw1,...                 keywords shared by 2+ entries
+//! <id delta>|<kind or empty if same>|<source or \^ if same>|<timestamp delta>|
+//!     <access_count>|<task_status>|<shared prefix chars>:<rest of content>|@0,kw,...|
+//!     <session or empty if same>
+//! ```
+//!
+//! Timestamp deltas are signed (entries are in id order, not time order) and
+//! computed with wrapping arithmetic, so any u64 timestamp round-trips
+//! exactly. The first entry's delta is from 0, i.e. its absolute timestamp.
+//!
+//! The bridge stores the lines verbatim; `deserialize()` recognizes the
+//! header and expands the data to plain v2 before loading.
+//!
+//! ## JSON Output
+//!
+//! `memory list|get|stats --json` print one line of hand-rolled JSON (see
+//! `MemoryEntry::to_json`, `MemoryStats::to_json`) for tooling such as the
+//! bridge, so it can read memory state without scraping the human format.
+//! It is output only; nothing parses it back.
 
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -30,19 +108,59 @@ use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
 use spin::Mutex;
 use lazy_static::lazy_static;
+use crate::agents::message::FeedbackType;
 
 /// Path where memories are persisted in the filesystem
 const MEMORY_FILE_PATH: &str = "/storage/memory/memories.dat";
 
-/// Stop words to skip during keyword extraction
-const STOP_WORDS: &[&str] = &[
+/// Stop words skipped during keyword extraction until reconfigured
+const DEFAULT_STOP_WORDS: &[&str] = &[
     "a", "an", "the", "is", "of", "to", "in", "and", "for", "that",
     "this", "with", "are", "was", "were", "been", "have", "has", "had",
     "but", "not", "from", "they", "will", "can", "would", "could",
 ];
 
-/// Minimum word length for keyword extraction
-const MIN_WORD_LEN: usize = 4;
+/// Minimum keyword length until reconfigured
+const DEFAULT_MIN_WORD_LEN: usize = 4;
+
+/// First line of serialized data in the current format.
+/// Data without it is the original 7-field format (no task status).
+const FORMAT_HEADER: &str = "#v2";
+
+/// First line of a compact snapshot (see `serialize_compact`)
+const COMPACT_HEADER: &str = "#v2c";
+
+/// Prefix of the compact snapshot's keyword dictionary line
+const DICTIONARY_PREFIX: &str = "#k ";
+
+/// Compact source field meaning "same as the previous entry".
+/// `escape_field` never emits a backslash followed by `^`.
+const SAME_SOURCE: &str = "\\^";
+
+/// Marker line separating the snapshot from WAL ops to replay
+const WAL_MARKER: &str = "#wal";
+
+/// Pending WAL ops that force a full snapshot instead of another append
+const WAL_MAX_OPS: usize = 64;
+
+/// Ops appended to the memory file before `save()` compacts it
+const COMPACT_AFTER_OPS: usize = 256;
+
+/// Extra score (percent) for search results that also match the searcher's focus
+const FOCUS_BONUS_PERCENT: u32 = 50;
+
+/// Most frequent keywords considered by `top_cooccurrences` (pairs grow as its square)
+const COOCCURRENCE_KEYWORDS: usize = 16;
+
+/// Age in ticks (~5 min) at which an entry's recency score has halved
+const RECENCY_HALF_TICKS: u64 = 30_000;
+
+/// Importance points per retrieval, and the retrievals that still count
+const ACCESS_POINTS: u32 = 5;
+const MAX_COUNTED_ACCESSES: u64 = 20;
+
+/// Importance added for a pinned entry (ranks it above any unpinned one)
+const PIN_POINTS: u32 = 1000;
 
 /// What kind of memory this is — matches FeedbackType categories
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,6 +175,8 @@ pub enum MemoryKind {
     Feeling,
     /// General notes
     Observation,
+    /// Actionable item with an open/done status
+    Task,
 }
 
 impl MemoryKind {
@@ -68,9 +188,35 @@ impl MemoryKind {
             MemoryKind::Resource => "resource",
             MemoryKind::Feeling => "feeling",
             MemoryKind::Observation => "observation",
+            MemoryKind::Task => "task",
+        }
+    }
+
+    /// The memory kind and content an agent's feedback is persisted as
+    pub fn from_feedback(feedback: &FeedbackType) -> (MemoryKind, String) {
+        match feedback {
+            FeedbackType::Spark { content, context } => {
+                (MemoryKind::Spark, format!("{} (context: {})", content, context))
+            }
+            FeedbackType::Connection { from, to, pattern } => {
+                (MemoryKind::Connection, format!("{} -> {}: {}", from, to, pattern))
+            }
+            FeedbackType::Resource { description, location } => {
+                (MemoryKind::Resource, format!("{} (at: {})", description, location))
+            }
+            FeedbackType::Feeling { tag, intensity } => {
+                (MemoryKind::Feeling, format!("{} (intensity: {})", tag, intensity))
+            }
         }
     }
 
+    /// Split a Resource entry's content back into (description, location),
+    /// undoing `from_feedback`
+    pub fn split_resource(content: &str) -> Option<(&str, &str)> {
+        let (description, rest) = content.rsplit_once(" (at: ")?;
+        Some((description, rest.strip_suffix(')')?))
+    }
+
     /// Parse from string tag
     pub fn from_str(s: &str) -> Option<MemoryKind> {
         match s {
@@ -79,6 +225,35 @@ impl MemoryKind {
             "resource" => Some(MemoryKind::Resource),
             "feeling" => Some(MemoryKind::Feeling),
             "observation" => Some(MemoryKind::Observation),
+            "task" => Some(MemoryKind::Task),
+            _ => None,
+        }
+    }
+}
+
+/// Completion state of a Task entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// Still to do
+    Open,
+    /// Completed
+    Done,
+}
+
+impl TaskStatus {
+    /// Convert to string tag for serialization
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Open => "open",
+            TaskStatus::Done => "done",
+        }
+    }
+
+    /// Parse from string tag
+    pub fn from_str(s: &str) -> Option<TaskStatus> {
+        match s {
+            "open" => Some(TaskStatus::Open),
+            "done" => Some(TaskStatus::Done),
             _ => None,
         }
     }
@@ -101,6 +276,51 @@ pub struct MemoryEntry {
     pub timestamp: u64,
     /// How often this entry has been retrieved (for ranking boost)
     pub access_count: u64,
+    /// Open/done state (only set for `MemoryKind::Task`)
+    pub task_status: Option<TaskStatus>,
+    /// Boot session that created this entry (0 = unknown)
+    pub session: u64,
+    /// Pinned entries are never evicted to make room
+    pub pinned: bool,
+    /// Operator labels, normalized (see `normalize_tag`)
+    pub tags: Vec<String>,
+}
+
+impl MemoryEntry {
+    /// How much the entry is worth keeping at tick `now` of boot session
+    /// `session` (see module docs)
+    pub fn importance(&self, now: u64, session: u64) -> u32 {
+        let kind_weight = match self.kind {
+            MemoryKind::Spark | MemoryKind::Connection => 40,
+            MemoryKind::Resource | MemoryKind::Task => 25,
+            MemoryKind::Feeling => 15,
+            MemoryKind::Observation => 10,
+        };
+        let access = self.access_count.min(MAX_COUNTED_ACCESSES) as u32 * ACCESS_POINTS;
+        let recency = if self.session == session {
+            let age = now.saturating_sub(self.timestamp);
+            (100 * RECENCY_HALF_TICKS / (RECENCY_HALF_TICKS + age)) as u32
+        } else {
+            0
+        };
+        let pin = if self.pinned { PIN_POINTS } else { 0 };
+        kind_weight + access + recency + pin
+    }
+
+    /// The entry as a single-line JSON object (for tooling, see module docs)
+    pub fn to_json(&self) -> String {
+        let keywords: Vec<String> = self.keywords.iter().map(|k| format!("\"{}\"", json_escape(k))).collect();
+        let tags: Vec<String> = self.tags.iter().map(|t| format!("\"{}\"", json_escape(t))).collect();
+        let task_status = match self.task_status {
+            Some(status) => format!("\"{}\"", status.as_str()),
+            None => String::from("null"),
+        };
+        format!(
+            "{{\"id\":{},\"kind\":\"{}\",\"source\":\"{}\",\"timestamp\":{},\"session\":{},\"access_count\":{},\"task_status\":{},\"pinned\":{},\"tags\":[{}],\"keywords\":[{}],\"content\":\"{}\"}}",
+            self.id, self.kind.as_str(), json_escape(&self.source), self.timestamp, self.session,
+            self.access_count, task_status, self.pinned, tags.join(","), keywords.join(","), json_escape(&self.content),
+        )
+    }
 }
 
 /// Statistics about the memory store
@@ -116,6 +336,52 @@ pub struct MemoryStats {
     pub estimated_bytes: usize,
 }
 
+impl MemoryStats {
+    /// The statistics as a single-line JSON object (for tooling, see module docs)
+    pub fn to_json(&self) -> String {
+        let top: Vec<String> = self.top_keywords
+            .iter()
+            .map(|(keyword, count)| format!("{{\"keyword\":\"{}\",\"count\":{}}}", json_escape(keyword), count))
+            .collect();
+        format!(
+            "{{\"entry_count\":{},\"index_size\":{},\"estimated_bytes\":{},\"top_keywords\":[{}]}}",
+            self.entry_count, self.index_size, self.estimated_bytes, top.join(","),
+        )
+    }
+}
+
+/// Why two entries are related (see `MemoryStore::explain_relation`)
+#[derive(Debug, Clone)]
+pub struct RelationExplanation {
+    /// Keywords both entries have, with each one's document frequency,
+    /// rarest first
+    pub shared: Vec<(String, usize)>,
+    /// Sum of `total_entries / doc_freq` over the shared keywords: rare
+    /// shared words count for more than common ones (0 = unrelated)
+    pub score: u32,
+}
+
+/// Runtime keyword extraction settings.
+///
+/// Changes apply to future `store` calls and queries; existing entries keep
+/// their keywords until `reindex()`.
+#[derive(Debug, Clone)]
+pub struct IndexConfig {
+    /// Lowercase words never indexed
+    pub stop_words: BTreeSet<String>,
+    /// Words shorter than this are skipped
+    pub min_word_len: usize,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        IndexConfig {
+            stop_words: DEFAULT_STOP_WORDS.iter().map(|w| String::from(*w)).collect(),
+            min_word_len: DEFAULT_MIN_WORD_LEN,
+        }
+    }
+}
+
 /// The core memory store — BTreeMap-based inverted index with BM25-lite search
 pub struct MemoryStore {
     /// All entries keyed by ID
@@ -126,6 +392,27 @@ pub struct MemoryStore {
     next_id: u64,
     /// Maximum number of entries (cap for memory budget)
     max_entries: usize,
+    /// Ops not yet flushed to the bridge (see module docs)
+    wal: Vec<String>,
+    /// Ops not yet appended to the memory file
+    file_log: Vec<String>,
+    /// Ops appended to the memory file since it was last compacted
+    file_ops: usize,
+    /// A mutation was logged since the memory file was last written
+    dirty: bool,
+    /// Session stamped on new entries (see module docs)
+    session: u64,
+}
+
+/// Result of `compact()`
+#[derive(Debug, Clone, Copy)]
+pub struct CompactStats {
+    /// File size before compaction
+    pub bytes_before: usize,
+    /// File size after compaction
+    pub bytes_after: usize,
+    /// Appended ops folded into the snapshot
+    pub ops_folded: usize,
 }
 
 impl MemoryStore {
@@ -136,57 +423,179 @@ impl MemoryStore {
             index: BTreeMap::new(),
             next_id: 1,
             max_entries,
+            wal: Vec::new(),
+            file_log: Vec::new(),
+            file_ops: 0,
+            dirty: false,
+            session: 0,
         }
     }
 
+    /// Stamp entries stored from now on with `session`
+    pub fn set_session(&mut self, session: u64) {
+        self.session = session;
+    }
+
+    /// Record a mutation for both the bridge WAL and the memory file
+    fn log_op(&mut self, op: String) {
+        self.file_log.push(op.clone());
+        self.wal.push(op);
+        self.dirty = true;
+    }
+
     /// Store a new memory entry. Returns the assigned ID.
     ///
     /// Extracts keywords from content, adds to entries and index,
     /// and enforces the max_entries cap (removes oldest first).
     pub fn store(&mut self, content: &str, kind: MemoryKind, source: &str) -> u64 {
-        let id = self.next_id;
-        self.next_id += 1;
+        self.store_with_timestamp(content, kind, source, 0)
+    }
 
-        let keywords = extract_keywords(content);
+    /// Store with a specific timestamp
+    pub fn store_with_timestamp(&mut self, content: &str, kind: MemoryKind, source: &str, timestamp: u64) -> u64 {
+        let id = self.next_id;
 
         let entry = MemoryEntry {
             id,
             content: String::from(content),
             kind,
             source: String::from(source),
-            keywords: keywords.clone(),
-            timestamp: 0, // Will be set by caller if needed
+            keywords: extract_keywords(content),
+            timestamp,
             access_count: 0,
+            task_status: if kind == MemoryKind::Task { Some(TaskStatus::Open) } else { None },
+            session: self.session,
+            pinned: false,
+            tags: Vec::new(),
         };
 
-        // Add to entries
-        self.entries.insert(id, entry);
+        self.log_op(format!("+{}", serialize_entry(&entry)));
+        self.insert_entry(entry);
+        self.enforce_capacity(timestamp);
+
+        id
+    }
 
-        // Add to inverted index
-        for keyword in &keywords {
+    /// Add an entry to `entries` and the inverted index, advancing `next_id` past it
+    fn insert_entry(&mut self, entry: MemoryEntry) {
+        let id = entry.id;
+        for keyword in &entry.keywords {
+            if !keyword.is_empty() {
+                self.index
+                    .entry(keyword.clone())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(id);
+            }
+        }
+        for tag in &entry.tags {
             self.index
-                .entry(keyword.clone())
-                .or_insert_with(BTreeSet::new)
+                .entry(tag_key(tag))
+                .or_default()
                 .insert(id);
         }
+        self.entries.insert(id, entry);
 
-        // Enforce capacity — remove oldest entries
+        // Track highest ID for next_id
+        if id >= self.next_id {
+            self.next_id = id + 1;
+        }
+    }
+
+    /// Evict the least important unpinned entries until within `max_entries`,
+    /// logging each eviction. The newest entry is kept, so a store never
+    /// evicts what it just stored; if everything else is pinned the store
+    /// stays over capacity. `now` is the tick of the store that overflowed.
+    fn enforce_capacity(&mut self, now: u64) {
         while self.entries.len() > self.max_entries {
-            if let Some(&oldest_id) = self.entries.keys().next() {
-                self.remove_entry(oldest_id);
-            }
+            let newest = self.entries.keys().next_back().copied();
+            let victim = self.entries
+                .values()
+                .filter(|e| !e.pinned && Some(e.id) != newest)
+                .min_by_key(|e| (e.importance(now, self.session), e.id))
+                .map(|e| e.id);
+            let Some(id) = victim else {
+                break;
+            };
+            self.remove_entry(id);
+            self.log_op(format!("-{}", id));
         }
+    }
 
-        id
+    /// Latest tick stamped this session: "now" for loads and merges, which
+    /// have no tick of their own
+    fn session_tick(&self) -> u64 {
+        self.entries
+            .values()
+            .filter(|e| e.session == self.session)
+            .map(|e| e.timestamp)
+            .max()
+            .unwrap_or(0)
     }
 
-    /// Store with a specific timestamp
-    pub fn store_with_timestamp(&mut self, content: &str, kind: MemoryKind, source: &str, timestamp: u64) -> u64 {
-        let id = self.store(content, kind, source);
-        if let Some(entry) = self.entries.get_mut(&id) {
-            entry.timestamp = timestamp;
+    /// Pin or unpin an entry. Returns false if there is no such entry.
+    pub fn set_pinned(&mut self, id: u64, pinned: bool) -> bool {
+        let Some(entry) = self.entries.get_mut(&id) else {
+            return false;
+        };
+        entry.pinned = pinned;
+        self.log_op(format!("^{}|{}", id, u8::from(pinned)));
+        true
+    }
+
+    /// Tag an entry (see `normalize_tag`). Returns false if there is no such
+    /// entry or nothing is left of the tag after normalizing.
+    pub fn add_tag(&mut self, id: u64, tag: &str) -> bool {
+        let (Some(tag), Some(entry)) = (normalize_tag(tag), self.entries.get(&id)) else {
+            return false;
+        };
+        if !entry.tags.contains(&tag) {
+            let mut tags = entry.tags.clone();
+            tags.push(tag);
+            self.set_tags(id, tags);
         }
-        id
+        true
+    }
+
+    /// Remove a tag from an entry. Returns false if the entry doesn't have it.
+    pub fn remove_tag(&mut self, id: u64, tag: &str) -> bool {
+        let (Some(tag), Some(entry)) = (normalize_tag(tag), self.entries.get(&id)) else {
+            return false;
+        };
+        if !entry.tags.contains(&tag) {
+            return false;
+        }
+        let tags = entry.tags.iter().filter(|t| **t != tag).cloned().collect();
+        self.set_tags(id, tags);
+        true
+    }
+
+    /// Replace an entry's tags, keeping the index in step, and log the op
+    fn set_tags(&mut self, id: u64, tags: Vec<String>) {
+        let Some(entry) = self.entries.get_mut(&id) else {
+            return;
+        };
+        let old = core::mem::replace(&mut entry.tags, tags);
+        for tag in &old {
+            self.unindex(&tag_key(tag), id);
+        }
+        let tags = self.entries[&id].tags.clone();
+        for tag in &tags {
+            self.index
+                .entry(tag_key(tag))
+                .or_default()
+                .insert(id);
+        }
+        self.log_op(format!("={}|{}", id, tags.join(",")));
+    }
+
+    /// Every entry with its importance at tick `now`, most important first
+    pub fn by_importance(&self, now: u64) -> Vec<(u32, &MemoryEntry)> {
+        let mut ranked: Vec<(u32, &MemoryEntry)> = self.entries
+            .values()
+            .map(|e| (e.importance(now, self.session), e))
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.id.cmp(&a.1.id)));
+        ranked
     }
 
     /// Search for entries matching the query. Returns (id, score) pairs sorted by score descending.
@@ -196,8 +605,9 @@ impl MemoryStore {
     ///   tf = count of query term matches in entry keywords
     ///   idf_approx = total_entries / doc_freq (integer division)
     ///   access_bonus = min(access_count, 5)
+    /// `#tag` words in the query match tags (tf = 1).
     pub fn search(&self, query: &str) -> Vec<(u64, u32)> {
-        let query_terms = extract_keywords(query);
+        let query_terms = query_terms(query);
         if query_terms.is_empty() {
             return Vec::new();
         }
@@ -219,9 +629,13 @@ impl MemoryStore {
                 for &entry_id in entry_ids {
                     if let Some(entry) = self.entries.get(&entry_id) {
                         // TF: count how many times this term appears in entry keywords
-                        let tf = entry.keywords.iter()
-                            .filter(|kw| kw.as_str() == term.as_str())
-                            .count() as u32;
+                        let tf = if term.starts_with('#') {
+                            1
+                        } else {
+                            entry.keywords.iter()
+                                .filter(|kw| kw.as_str() == term.as_str())
+                                .count() as u32
+                        };
 
                         // Access bonus: boost frequently-accessed entries (capped at 5)
                         let access_bonus = if entry.access_count > 5 { 5 } else { entry.access_count as u32 };
@@ -239,6 +653,26 @@ impl MemoryStore {
         results
     }
 
+    /// `search`, with results that also match a `focus` topic scored
+    /// `FOCUS_BONUS_PERCENT` higher. Focus never adds results of its own.
+    pub fn search_focused(&self, query: &str, focus: Option<&str>) -> Vec<(u64, u32)> {
+        let mut results = self.search(query);
+        let focus_terms = focus.map(extract_keywords).unwrap_or_default();
+        if focus_terms.is_empty() {
+            return results;
+        }
+        for (id, score) in results.iter_mut() {
+            let on_focus = self.entries.get(id)
+                .is_some_and(|e| e.keywords.iter().any(|kw| focus_terms.contains(kw)));
+            if on_focus {
+                // Round up so low integer scores still gain something
+                *score = (*score * (100 + FOCUS_BONUS_PERCENT)).div_ceil(100);
+            }
+        }
+        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results
+    }
+
     /// Get an entry by ID and bump its access count
     pub fn get(&mut self, id: u64) -> Option<&MemoryEntry> {
         if let Some(entry) = self.entries.get_mut(&id) {
@@ -255,19 +689,14 @@ impl MemoryStore {
     /// Get statistics about the memory store
     pub fn stats(&self) -> MemoryStats {
         // Find top keywords by document frequency
-        let mut keyword_freq: Vec<(String, usize)> = self.index
-            .iter()
-            .map(|(k, v)| (k.clone(), v.len()))
-            .collect();
-        keyword_freq.sort_by(|a, b| b.1.cmp(&a.1));
-        keyword_freq.truncate(10);
+        let keyword_freq = self.top_keywords(10);
 
         // Estimate memory usage
         let entry_bytes: usize = self.entries.values()
             .map(|e| {
                 // Rough estimate per entry
                 8 + e.content.len() + e.source.len()
-                    + e.keywords.iter().map(|k| k.len() + 24).sum::<usize>()
+                    + e.keywords.iter().chain(&e.tags).map(|k| k.len() + 24).sum::<usize>()
                     + 64 // overhead for BTreeMap node, enum, etc.
             })
             .sum();
@@ -284,129 +713,705 @@ impl MemoryStore {
         }
     }
 
+    /// Top N keywords by document frequency, read straight from the index.
+    /// Ties are broken alphabetically so the order is stable between runs.
+    pub fn top_keywords(&self, count: usize) -> Vec<(String, usize)> {
+        let mut keyword_freq: Vec<(String, usize)> = self.index
+            .iter()
+            .map(|(k, v)| (k.clone(), v.len()))
+            .collect();
+        keyword_freq.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        keyword_freq.truncate(count);
+        keyword_freq
+    }
+
+    /// Most frequent pairs of keywords that appear in the same entries,
+    /// with the number of entries sharing both. Each pair is alphabetical.
+    ///
+    /// Only pairs among the `COOCCURRENCE_KEYWORDS` most frequent keywords are
+    /// counted, so the cost stays bounded no matter how large the index grows.
+    pub fn top_cooccurrences(&self, count: usize) -> Vec<((String, String), usize)> {
+        let mut top: Vec<String> = self.top_keywords(COOCCURRENCE_KEYWORDS)
+            .into_iter()
+            .map(|(keyword, _)| keyword)
+            .collect();
+        top.sort();
+
+        let mut pairs = Vec::new();
+        for (i, a) in top.iter().enumerate() {
+            for b in &top[i + 1..] {
+                let shared = self.pair_entries(a, b).len();
+                if shared > 0 {
+                    pairs.push(((a.clone(), b.clone()), shared));
+                }
+            }
+        }
+        pairs.sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+        pairs.truncate(count);
+        pairs
+    }
+
+    /// Shared keywords between two entries and how telling they are.
+    /// None if either id doesn't exist.
+    pub fn explain_relation(&self, id1: u64, id2: u64) -> Option<RelationExplanation> {
+        let first = self.entries.get(&id1)?;
+        let second = self.entries.get(&id2)?;
+        let total = self.entries.len();
+
+        let mut shared: Vec<(String, usize)> = first.keywords
+            .iter()
+            .filter(|k| !k.is_empty() && second.keywords.contains(k))
+            .map(|k| (k.clone(), self.index.get(k).map_or(0, |ids| ids.len())))
+            .collect();
+        shared.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        shared.dedup();
+
+        let score = shared.iter()
+            .map(|(_, freq)| (total / (*freq).max(1)) as u32)
+            .sum();
+        Some(RelationExplanation { shared, score })
+    }
+
+    /// IDs of the entries indexed under both keywords, oldest first
+    pub fn pair_entries(&self, a: &str, b: &str) -> Vec<u64> {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(ids_a), Some(ids_b)) => ids_a.intersection(ids_b).copied().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Set the status of a task. Returns false if `id` isn't a task.
+    pub fn set_task_status(&mut self, id: u64, status: TaskStatus) -> bool {
+        match self.entries.get_mut(&id) {
+            Some(entry) if entry.kind == MemoryKind::Task => {
+                entry.task_status = Some(status);
+                self.log_op(format!("~{}|{}", id, status.as_str()));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// All tasks in ID order, optionally including completed ones
+    pub fn tasks(&self, include_done: bool) -> Vec<&MemoryEntry> {
+        self.entries
+            .values()
+            .filter(|e| e.kind == MemoryKind::Task)
+            .filter(|e| include_done || e.task_status != Some(TaskStatus::Done))
+            .collect()
+    }
+
     /// Get the N most recent entries
     pub fn recent(&self, count: usize) -> Vec<&MemoryEntry> {
         // BTreeMap is sorted by ID (which is monotonically increasing)
         self.entries.values().rev().take(count).collect()
     }
 
+    /// All entries of one kind, newest first (by ID, so across sessions too)
+    pub fn by_kind(&self, kind: MemoryKind) -> Vec<&MemoryEntry> {
+        self.entries.values().rev().filter(|e| e.kind == kind).collect()
+    }
+
+    /// All entries from exactly `source`, newest first (by timestamp, then ID)
+    ///
+    /// A linear scan; fine at the 200-entry cap.
+    pub fn by_source(&self, source: &str) -> Vec<&MemoryEntry> {
+        let mut found: Vec<&MemoryEntry> = self.entries
+            .values()
+            .filter(|e| e.source == source)
+            .collect();
+        found.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(b.id.cmp(&a.id)));
+        found
+    }
+
+    /// Distinct sessions with their entry counts, oldest first
+    pub fn sessions(&self) -> Vec<(u64, usize)> {
+        let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+        for entry in self.entries.values() {
+            *counts.entry(entry.session).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    /// All entries from one session, newest first (by timestamp, then ID)
+    pub fn by_session(&self, session: u64) -> Vec<&MemoryEntry> {
+        let mut found: Vec<&MemoryEntry> = self.entries
+            .values()
+            .filter(|e| e.session == session)
+            .collect();
+        found.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then(b.id.cmp(&a.id)));
+        found
+    }
+
     /// Serialize the entire store to pipe-delimited text
     ///
-    /// Format per line: `id|kind|source|timestamp|access_count|content|kw1,kw2,...\n`
+    /// Starts with the `#v2` header line, then one line per entry:
+    /// `id|kind|source|timestamp|access_count|task_status|content|kw1,kw2,...|session\n`
+    /// (`task_status` is empty for non-task entries; pinned entries add `|1`)
+    /// and ends with `next_id_trailer`.
     pub fn serialize(&self) -> String {
         let mut output = String::new();
+        output.push_str(FORMAT_HEADER);
+        output.push('\n');
 
         for entry in self.entries.values() {
-            // Escape pipes and newlines in content
-            let escaped_content = entry.content.replace('\\', "\\\\").replace('|', "\\p").replace('\n', "\\n").replace('\r', "\\r");
-            let escaped_source = entry.source.replace('\\', "\\\\").replace('|', "\\p").replace('\n', "\\n").replace('\r', "\\r");
+            output.push_str(&serialize_entry(entry));
+            output.push('\n');
+        }
 
-            let keywords_str: String = entry.keywords.join(",");
+        output.push_str(&self.next_id_trailer());
+        output
+    }
+
+    /// A WAL tombstone for the last id handed out, if that entry is gone,
+    /// so a snapshot keeps `next_id` and never reuses it
+    fn next_id_trailer(&self) -> String {
+        let last = self.next_id - 1;
+        if last == 0 || self.entries.contains_key(&last) {
+            String::new()
+        } else {
+            format!("{WAL_MARKER}\n-{last}\n")
+        }
+    }
+
+    /// Serialize the store in the compact `#v2c` format for the serial bridge
+    ///
+    /// Ids and timestamps are deltas from the previous entry, a repeated kind
+    /// or source is elided, content shares its leading characters with the
+    /// previous entry's, and keywords found in several entries are replaced
+    /// by `@<n>` references into a dictionary line. Escaping is the same as
+    /// `serialize()`. Keywords never contain `@` (see `extract_keywords_with`).
+    pub fn serialize_compact(&self) -> String {
+        let dictionary: Vec<&str> = self.index
+            .iter()
+            .filter(|(keyword, ids)| ids.len() > 1 && !keyword.starts_with('#'))
+            .map(|(keyword, _)| keyword.as_str())
+            .collect();
+        let codes: BTreeMap<&str, usize> = dictionary
+            .iter()
+            .enumerate()
+            .map(|(code, keyword)| (*keyword, code))
+            .collect();
+
+        let mut output = String::new();
+        output.push_str(COMPACT_HEADER);
+        output.push('\n');
+        output.push_str(DICTIONARY_PREFIX);
+        output.push_str(&dictionary.join(","));
+        output.push('\n');
+
+        let mut prev: Option<&MemoryEntry> = None;
+        for entry in self.entries.values() {
+            let (prev_id, prev_timestamp) = prev.map_or((0, 0), |p| (p.id, p.timestamp));
+            let session = match prev {
+                Some(p) if p.session == entry.session => String::new(),
+                _ => format!("{}", entry.session),
+            };
+            let kind = match prev {
+                Some(p) if p.kind == entry.kind => "",
+                _ => entry.kind.as_str(),
+            };
+            let source = match prev {
+                Some(p) if p.source == entry.source => String::from(SAME_SOURCE),
+                _ => escape_field(&entry.source),
+            };
+            let shared = prev.map_or(0, |p| {
+                p.content.chars().zip(entry.content.chars()).take_while(|(a, b)| a == b).count()
+            });
+            let rest: String = entry.content.chars().skip(shared).collect();
+            let keywords: Vec<String> = entry.keywords
+                .iter()
+                .map(|keyword| match codes.get(keyword.as_str()) {
+                    Some(code) => format!("@{code}"),
+                    None => keyword.clone(),
+                })
+                .collect();
 
             output.push_str(&format!(
-                "{}|{}|{}|{}|{}|{}|{}\n",
-                entry.id,
-                entry.kind.as_str(),
-                escaped_source,
-                entry.timestamp,
+                "{}|{}|{}|{}|{}|{}|{}:{}|{}|{}{}\n",
+                entry.id - prev_id,
+                kind,
+                source,
+                // Wrapping keeps the delta exact for any pair of u64 timestamps
+                entry.timestamp.wrapping_sub(prev_timestamp) as i64,
                 entry.access_count,
-                escaped_content,
-                keywords_str,
+                entry.task_status.map(|t| t.as_str()).unwrap_or(""),
+                shared,
+                escape_field(&rest),
+                keywords.join(","),
+                session,
+                trailing_fields(entry),
             ));
+            prev = Some(entry);
         }
 
+        output.push_str(&self.next_id_trailer());
         output
     }
 
+    /// Size in bytes `serialize()` would produce, without building it
+    fn serialized_len(&self) -> usize {
+        FORMAT_HEADER.len() + 1
+            + self.entries.values().map(|e| serialize_entry(e).len() + 1).sum::<usize>()
+            + self.next_id_trailer().len()
+    }
+
     /// Deserialize from pipe-delimited text, replacing current contents
-    pub fn deserialize(&mut self, data: &str) {
+    ///
+    /// Accepts the `#v2` format, its compact `#v2c` variant, and the original
+    /// headerless 7-field format.
+    /// Lines after a `#wal` marker are replayed as WAL ops; tombstoned ids
+    /// are skipped up front. Returns the number of replayed ops.
+    pub fn deserialize(&mut self, data: &str) -> usize {
         // Clear existing data
         self.entries.clear();
         self.index.clear();
         self.next_id = 1;
+        self.wal.clear();
+        self.file_log.clear();
+
+        let expanded;
+        let data = if data.lines().find(|l| !l.trim().is_empty()).map(str::trim) == Some(COMPACT_HEADER) {
+            expanded = expand_compact(data);
+            expanded.as_str()
+        } else {
+            data
+        };
+
+        // IDs are never reused, so a tombstone means the id is gone for good
+        let tombstones: BTreeSet<u64> = data
+            .lines()
+            .skip_while(|line| line.trim() != WAL_MARKER)
+            .filter_map(|line| line.trim().strip_prefix('-')?.parse().ok())
+            .collect();
+
+        let mut has_task_field = false;
+        let mut in_wal = false;
+        let mut replayed = 0;
 
         for line in data.lines() {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
+            if line == FORMAT_HEADER {
+                has_task_field = true;
+                continue;
+            }
+            if line == WAL_MARKER {
+                in_wal = true;
+                continue;
+            }
 
-            // Split on pipe, expecting 7 fields
-            let parts: Vec<&str> = line.splitn(7, '|').collect();
-            if parts.len() < 7 {
-                continue; // Skip malformed lines
+            if in_wal {
+                self.replay_op(line, &tombstones);
+                replayed += 1;
+            } else if let Some(entry) = parse_entry(line, has_task_field) {
+                self.insert_live(entry, &tombstones);
             }
+        }
+        if let Some(last) = tombstones.last() {
+            self.next_id = self.next_id.max(last + 1);
+        }
 
-            let id = match parts[0].parse::<u64>() {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let kind = match MemoryKind::from_str(parts[1]) {
-                Some(k) => k,
-                None => continue,
-            };
-            let source = parts[2].replace("\\p", "|").replace("\\r", "\r").replace("\\n", "\n").replace("\\\\", "\\");
-            let timestamp = parts[3].parse::<u64>().unwrap_or(0);
-            let access_count = parts[4].parse::<u64>().unwrap_or(0);
-            let content = parts[5].replace("\\p", "|").replace("\\r", "\r").replace("\\n", "\n").replace("\\\\", "\\");
-            let keywords: Vec<String> = if parts[6].is_empty() {
-                Vec::new()
-            } else {
-                parts[6].split(',').map(|s| String::from(s.trim())).collect()
-            };
+        // Replayed ops are already persisted wherever `data` came from
+        self.wal.clear();
+        self.file_log.clear();
+        self.dirty = false;
+        replayed
+    }
 
-            let entry = MemoryEntry {
-                id,
-                content,
-                kind,
-                source,
-                keywords: keywords.clone(),
-                timestamp,
-                access_count,
-            };
+    /// Merge serialized entries into the current contents without wiping them
+    ///
+    /// `data` is parsed exactly as `deserialize` would. Each incoming entry gets
+    /// a fresh id from `next_id`; entries whose content and kind already exist
+    /// are skipped. Merged entries are logged like new stores.
+    /// Returns the number of entries added.
+    pub fn merge(&mut self, data: &str) -> usize {
+        let mut incoming = MemoryStore::new(usize::MAX);
+        incoming.deserialize(data);
+
+        let mut seen: BTreeSet<(&'static str, String)> = self
+            .entries
+            .values()
+            .map(|entry| (entry.kind.as_str(), entry.content.clone()))
+            .collect();
+
+        let mut added = 0;
+        for (_, mut entry) in incoming.entries {
+            if !seen.insert((entry.kind.as_str(), entry.content.clone())) {
+                continue;
+            }
+            entry.id = self.next_id;
+            self.log_op(format!("+{}", serialize_entry(&entry)));
+            self.insert_entry(entry);
+            added += 1;
+        }
+        self.enforce_capacity(self.session_tick());
+        added
+    }
 
-            // Add to entries
-            self.entries.insert(id, entry);
+    /// Insert a loaded entry unless it was tombstoned (its id is still consumed)
+    fn insert_live(&mut self, entry: MemoryEntry, tombstones: &BTreeSet<u64>) {
+        if tombstones.contains(&entry.id) {
+            self.next_id = self.next_id.max(entry.id + 1);
+        } else {
+            self.remove_entry(entry.id);
+            self.insert_entry(entry);
+            self.enforce_capacity(self.session_tick());
+        }
+    }
 
-            // Rebuild inverted index
-            for keyword in &keywords {
-                if !keyword.is_empty() {
-                    self.index
-                        .entry(keyword.clone())
-                        .or_insert_with(BTreeSet::new)
-                        .insert(id);
+    /// Apply one WAL op line (malformed ops are skipped)
+    fn replay_op(&mut self, op: &str, tombstones: &BTreeSet<u64>) {
+        if let Some(line) = op.strip_prefix('+') {
+            if let Some(entry) = parse_entry(line, true) {
+                self.insert_live(entry, tombstones);
+            }
+        } else if op.starts_with('-') {
+            // Tombstones were applied before the snapshot was loaded
+        } else if let Some(rest) = op.strip_prefix('~') {
+            if let Some((id, status)) = rest.split_once('|') {
+                if let (Ok(id), Some(status)) = (id.parse::<u64>(), TaskStatus::from_str(status)) {
+                    self.set_task_status(id, status);
+                }
+            }
+        } else if let Some(rest) = op.strip_prefix('^') {
+            if let Some((id, pinned)) = rest.split_once('|') {
+                if let Ok(id) = id.parse::<u64>() {
+                    self.set_pinned(id, pinned == "1");
                 }
             }
+        } else if let Some(rest) = op.strip_prefix('=') {
+            if let Some((id, tags)) = rest.split_once('|') {
+                if let Ok(id) = id.parse::<u64>() {
+                    self.set_tags(id, parse_tags(tags));
+                }
+            }
+        }
+    }
+
+    /// Number of ops waiting to be flushed
+    pub fn wal_len(&self) -> usize {
+        self.wal.len()
+    }
+
+    /// Take all pending WAL ops, leaving the log empty
+    pub fn take_wal(&mut self) -> Vec<String> {
+        core::mem::take(&mut self.wal)
+    }
 
-            // Track highest ID for next_id
-            if id >= self.next_id {
-                self.next_id = id + 1;
+    /// Take ops not yet appended to the memory file, leaving the log empty
+    /// and the store clean
+    pub fn take_file_log(&mut self) -> Vec<String> {
+        self.dirty = false;
+        core::mem::take(&mut self.file_log)
+    }
+
+    /// Whether anything changed since the memory file was last written
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Recompute every entry's keywords under `config` and rebuild the index.
+    /// Returns the new number of unique keywords.
+    pub fn reindex(&mut self, config: &IndexConfig) -> usize {
+        self.index.clear();
+        for (id, entry) in self.entries.iter_mut() {
+            entry.keywords = extract_keywords_with(&entry.content, config);
+            for keyword in &entry.keywords {
+                self.index
+                    .entry(keyword.clone())
+                    .or_insert_with(BTreeSet::new)
+                    .insert(*id);
             }
+            for tag in &entry.tags {
+                self.index
+                    .entry(tag_key(tag))
+                    .or_default()
+                    .insert(*id);
+            }
+        }
+        self.index.len()
+    }
+
+    /// Delete an entry for good. Returns false if there is no such entry.
+    pub fn forget(&mut self, id: u64) -> bool {
+        if !self.entries.contains_key(&id) {
+            return false;
         }
+        self.remove_entry(id);
+        self.log_op(format!("-{}", id));
+        true
     }
 
     /// Remove an entry and clean up its index references
     fn remove_entry(&mut self, id: u64) {
         if let Some(entry) = self.entries.remove(&id) {
             for keyword in &entry.keywords {
-                if let Some(id_set) = self.index.get_mut(keyword) {
-                    id_set.remove(&id);
-                    if id_set.is_empty() {
-                        self.index.remove(keyword);
-                    }
-                }
+                self.unindex(keyword, id);
             }
+            for tag in &entry.tags {
+                self.unindex(&tag_key(tag), id);
+            }
+        }
+    }
+
+    /// Drop `id` from one index key, removing the key once it's empty
+    fn unindex(&mut self, key: &str, id: u64) {
+        if let Some(id_set) = self.index.get_mut(key) {
+            id_set.remove(&id);
+            if id_set.is_empty() {
+                self.index.remove(key);
+            }
+        }
+    }
+}
+
+/// Escape a string for the inside of a JSON string literal: quotes,
+/// backslashes and every control character
+pub(crate) fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape pipes, newlines and backslashes for a pipe-delimited field
+pub(crate) fn escape_field(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\p").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+/// Reverse of `escape_field`
+///
+/// Decodes in one pass: chained replaces would misread an escaped backslash
+/// followed by `n` (`\\n`) as a newline.
+pub(crate) fn unescape_field(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('p') => out.push('|'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// One v2 snapshot line for an entry (no trailing newline)
+fn serialize_entry(entry: &MemoryEntry) -> String {
+    format!(
+        "{}|{}|{}|{}|{}|{}|{}|{}|{}{}",
+        entry.id,
+        entry.kind.as_str(),
+        escape_field(&entry.source),
+        entry.timestamp,
+        entry.access_count,
+        entry.task_status.map(|t| t.as_str()).unwrap_or(""),
+        escape_field(&entry.content),
+        entry.keywords.join(","),
+        entry.session,
+        trailing_fields(entry),
+    )
+}
+
+/// Optional fields after the session: `|1` when pinned, or `|<0 or 1>|<tags>`
+/// when tagged (see module docs)
+fn trailing_fields(entry: &MemoryEntry) -> String {
+    match (entry.pinned, entry.tags.is_empty()) {
+        (false, true) => String::new(),
+        (true, true) => String::from("|1"),
+        (pinned, false) => format!("|{}|{}", u8::from(pinned), entry.tags.join(",")),
+    }
+}
+
+/// A tag as stored: leading `#`s dropped, lowercased, and only letters,
+/// digits, `-` and `_` kept. None if nothing is left.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag: String = tag.trim_start_matches('#')
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .flat_map(char::to_lowercase)
+        .collect();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Index key for a tag
+fn tag_key(tag: &str) -> String {
+    format!("#{tag}")
+}
+
+/// Tags from a comma-separated field (normalized, empty ones dropped)
+fn parse_tags(field: &str) -> Vec<String> {
+    field.split(',').filter_map(normalize_tag).collect()
+}
+
+/// Index terms for a search query: `#tag` words match tags, the rest are
+/// extracted like content keywords
+fn query_terms(query: &str) -> Vec<String> {
+    let (tags, words): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|word| word.starts_with('#'));
+    let mut terms = extract_keywords(&words.join(" "));
+    terms.extend(tags.into_iter().filter_map(normalize_tag).map(|tag| tag_key(&tag)));
+    terms
+}
+
+/// Expand a `#v2c` snapshot into plain v2 text (WAL ops pass through).
+/// Malformed entry lines are dropped.
+fn expand_compact(data: &str) -> String {
+    let mut output = String::new();
+    let mut dictionary: Vec<&str> = Vec::new();
+    let mut in_wal = false;
+    let mut prev_id = 0u64;
+    let mut prev_timestamp = 0u64;
+    let mut prev_kind = "";
+    let mut prev_source = String::new();
+    let mut prev_content = String::new();
+    let mut prev_session = 0u64;
+
+    for line in data.lines() {
+        let line = line.trim();
+        if in_wal || line == WAL_MARKER {
+            in_wal = true;
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+        if line == COMPACT_HEADER {
+            output.push_str(FORMAT_HEADER);
+            output.push('\n');
+            continue;
+        }
+        if let Some(words) = line.strip_prefix(DICTIONARY_PREFIX.trim_end()) {
+            dictionary = words.trim().split(',').filter(|w| !w.is_empty()).collect();
+            continue;
+        }
+
+        let parts: Vec<&str> = line.splitn(11, '|').collect();
+        if parts.len() < 8 {
+            continue;
+        }
+        let (Ok(id_delta), Ok(timestamp_delta)) = (parts[0].parse::<u64>(), parts[3].parse::<i64>()) else {
+            continue;
+        };
+        let Some((shared, rest)) = parts[6].split_once(':') else {
+            continue;
+        };
+        let Ok(shared) = shared.parse::<usize>() else {
+            continue;
+        };
+
+        prev_id += id_delta;
+        prev_timestamp = prev_timestamp.wrapping_add(timestamp_delta as u64);
+        if !parts[1].is_empty() {
+            prev_kind = parts[1];
         }
+        if parts[2] != SAME_SOURCE {
+            prev_source = unescape_field(parts[2]);
+        }
+        if let Some(Ok(session)) = parts.get(8).filter(|s| !s.is_empty()).map(|s| s.parse::<u64>()) {
+            prev_session = session;
+        }
+        let mut content: String = prev_content.chars().take(shared).collect();
+        content.push_str(&unescape_field(rest));
+        prev_content = content;
+
+        let keywords: Vec<&str> = parts[7]
+            .split(',')
+            .filter(|k| !k.is_empty())
+            .map(|k| {
+                k.strip_prefix('@')
+                    .and_then(|code| code.parse::<usize>().ok())
+                    .and_then(|code| dictionary.get(code).copied())
+                    .unwrap_or(k)
+            })
+            .collect();
+
+        output.push_str(&format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}{}\n",
+            prev_id,
+            prev_kind,
+            escape_field(&prev_source),
+            prev_timestamp,
+            parts[4],
+            parts[5],
+            escape_field(&prev_content),
+            keywords.join(","),
+            prev_session,
+            parts.get(9..).map(|trailing| trailing.iter().map(|f| format!("|{f}")).collect::<String>())
+                .unwrap_or_default(),
+        ));
+    }
+
+    output
+}
+
+/// Parse one snapshot line: 8 fields in v2 (7 in the original format),
+/// plus an optional trailing session, pin flag and tags
+fn parse_entry(line: &str, has_task_field: bool) -> Option<MemoryEntry> {
+    let field_count = if has_task_field { 8 } else { 7 };
+    let mut parts: Vec<&str> = line.splitn(field_count + 3, '|').collect();
+    if parts.len() < field_count {
+        return None; // Malformed line
     }
+    let task_status = if has_task_field {
+        TaskStatus::from_str(parts.remove(5))
+    } else {
+        None
+    };
+
+    let id = parts[0].parse::<u64>().ok()?;
+    let kind = MemoryKind::from_str(parts[1])?;
+    let keywords: Vec<String> = if parts[6].is_empty() {
+        Vec::new()
+    } else {
+        parts[6].split(',').map(|s| String::from(s.trim())).collect()
+    };
+
+    Some(MemoryEntry {
+        id,
+        content: unescape_field(parts[5]),
+        kind,
+        source: unescape_field(parts[2]),
+        keywords,
+        timestamp: parts[3].parse::<u64>().unwrap_or(0),
+        access_count: parts[4].parse::<u64>().unwrap_or(0),
+        task_status,
+        session: parts.get(7).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0),
+        pinned: parts.get(8) == Some(&"1"),
+        tags: parts.get(9).map(|field| parse_tags(field)).unwrap_or_default(),
+    })
+}
+
+/// Extract keywords from text using the current `IndexConfig`
+fn extract_keywords(text: &str) -> Vec<String> {
+    extract_keywords_with(text, &INDEX_CONFIG.lock())
 }
 
 /// Extract keywords from text for indexing
 ///
 /// - Split on whitespace
 /// - Lowercase
-/// - Keep words > 3 chars
+/// - Keep words of at least `min_word_len` chars
 /// - Skip stop words
-fn extract_keywords(text: &str) -> Vec<String> {
+fn extract_keywords_with(text: &str, config: &IndexConfig) -> Vec<String> {
     let mut keywords = Vec::new();
 
     for word in text.split_whitespace() {
@@ -415,7 +1420,7 @@ fn extract_keywords(text: &str) -> Vec<String> {
             .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
             .collect();
 
-        if cleaned.len() < MIN_WORD_LEN {
+        if cleaned.len() < config.min_word_len {
             continue;
         }
 
@@ -429,8 +1434,7 @@ fn extract_keywords(text: &str) -> Vec<String> {
         }).collect();
 
         // Skip stop words
-        let is_stop = STOP_WORDS.iter().any(|sw| *sw == lower.as_str());
-        if is_stop {
+        if config.stop_words.contains(&lower) {
             continue;
         }
 
@@ -447,16 +1451,45 @@ fn extract_keywords(text: &str) -> Vec<String> {
 lazy_static! {
     /// Global memory store instance
     pub static ref MEMORY: Mutex<MemoryStore> = Mutex::new(MemoryStore::new(200));
+
+    /// Keyword extraction settings (locked after MEMORY, never before)
+    static ref INDEX_CONFIG: Mutex<IndexConfig> = Mutex::new(IndexConfig::default());
+}
+
+/// Current keyword extraction settings
+pub fn index_config() -> IndexConfig {
+    INDEX_CONFIG.lock().clone()
+}
+
+/// Add a stop word (case-insensitive). Returns false if it was already one.
+pub fn add_stop_word(word: &str) -> bool {
+    INDEX_CONFIG.lock().stop_words.insert(word.trim().to_ascii_lowercase())
+}
+
+/// Remove a stop word. Returns false if it wasn't one.
+pub fn remove_stop_word(word: &str) -> bool {
+    INDEX_CONFIG.lock().stop_words.remove(&word.trim().to_ascii_lowercase())
 }
 
-/// Store a new memory entry (convenience function)
-pub fn store(content: &str, kind: MemoryKind, source: &str) -> u64 {
-    MEMORY.lock().store(content, kind, source)
+/// Set the minimum keyword length (at least 1)
+pub fn set_min_word_len(len: usize) {
+    INDEX_CONFIG.lock().min_word_len = len.max(1);
+}
+
+/// Re-extract all entries' keywords under the current config and rebuild
+/// the index. Returns (entries reindexed, unique keywords).
+pub fn reindex() -> (usize, usize) {
+    let config = index_config();
+    let mut mem = MEMORY.lock();
+    let keywords = mem.reindex(&config);
+    (mem.entries.len(), keywords)
 }
 
 /// Store with timestamp (convenience function)
 pub fn store_with_timestamp(content: &str, kind: MemoryKind, source: &str, timestamp: u64) -> u64 {
-    MEMORY.lock().store_with_timestamp(content, kind, source, timestamp)
+    let id = MEMORY.lock().store_with_timestamp(content, kind, source, timestamp);
+    bound_wal();
+    id
 }
 
 /// Search memory (convenience function)
@@ -464,39 +1497,246 @@ pub fn search(query: &str) -> Vec<(u64, u32)> {
     MEMORY.lock().search(query)
 }
 
+/// Search, biased toward entries matching `focus` (see `MemoryStore::search_focused`)
+pub fn search_focused(query: &str, focus: Option<&str>) -> Vec<(u64, u32)> {
+    MEMORY.lock().search_focused(query, focus)
+}
+
+/// The most significant keyword of `text` (the longest; first wins ties)
+pub fn main_keyword(text: &str) -> Option<String> {
+    extract_keywords(text)
+        .into_iter()
+        .rev()
+        .max_by_key(|keyword| keyword.chars().count())
+}
+
 /// Get an entry by ID (returns a clone since we release the lock)
 pub fn get(id: u64) -> Option<MemoryEntry> {
     MEMORY.lock().get(id).cloned()
 }
 
+/// Look up an entry without counting it as a retrieval (returns a clone)
+pub fn peek(id: u64) -> Option<MemoryEntry> {
+    MEMORY.lock().peek(id).cloned()
+}
+
 /// Get recent entries (returns clones)
 pub fn recent(count: usize) -> Vec<MemoryEntry> {
     MEMORY.lock().recent(count).into_iter().cloned().collect()
 }
 
+/// Entries of one kind, newest first (returns clones)
+pub fn by_kind(kind: MemoryKind) -> Vec<MemoryEntry> {
+    MEMORY.lock().by_kind(kind).into_iter().cloned().collect()
+}
+
+/// Entries from one source, newest first (returns clones)
+pub fn by_source(source: &str) -> Vec<MemoryEntry> {
+    MEMORY.lock().by_source(source).into_iter().cloned().collect()
+}
+
+/// Start a new session: entries stored from now on are tagged with `session`
+pub fn begin_session(session: u64) {
+    MEMORY.lock().set_session(session);
+}
+
+/// Distinct sessions with their entry counts, oldest first
+pub fn sessions() -> Vec<(u64, usize)> {
+    MEMORY.lock().sessions()
+}
+
+/// All entries from one session, newest first
+pub fn by_session(session: u64) -> Vec<MemoryEntry> {
+    MEMORY.lock().by_session(session).into_iter().cloned().collect()
+}
+
 /// Get memory statistics
 pub fn stats() -> MemoryStats {
     MEMORY.lock().stats()
 }
 
+/// Top N keywords by document frequency (global)
+pub fn top_keywords(count: usize) -> Vec<(String, usize)> {
+    MEMORY.lock().top_keywords(count)
+}
+
+/// Most frequent co-occurring keyword pairs (global)
+pub fn top_cooccurrences(count: usize) -> Vec<((String, String), usize)> {
+    MEMORY.lock().top_cooccurrences(count)
+}
+
+/// Shared keywords between two entries (global)
+pub fn explain_relation(id1: u64, id2: u64) -> Option<RelationExplanation> {
+    MEMORY.lock().explain_relation(id1, id2)
+}
+
+/// IDs of entries indexed under both keywords (global)
+pub fn pair_entries(a: &str, b: &str) -> Vec<u64> {
+    MEMORY.lock().pair_entries(a, b)
+}
+
+/// Pin or unpin an entry (convenience function). Returns false if there is no such entry.
+pub fn set_pinned(id: u64, pinned: bool) -> bool {
+    let found = MEMORY.lock().set_pinned(id, pinned);
+    bound_wal();
+    found
+}
+
+/// Tag an entry. Returns false if there is no such entry or the tag is empty.
+pub fn add_tag(id: u64, tag: &str) -> bool {
+    let found = MEMORY.lock().add_tag(id, tag);
+    bound_wal();
+    found
+}
+
+/// Remove a tag from an entry. Returns false if the entry doesn't have it.
+pub fn remove_tag(id: u64, tag: &str) -> bool {
+    let found = MEMORY.lock().remove_tag(id, tag);
+    bound_wal();
+    found
+}
+
+/// Entries ranked by importance at tick `now`, most important first
+pub fn by_importance(now: u64) -> Vec<(u32, MemoryEntry)> {
+    MEMORY.lock().by_importance(now).into_iter().map(|(score, e)| (score, e.clone())).collect()
+}
+
+/// Add a task (convenience function). Returns the assigned ID.
+pub fn add_task(content: &str, source: &str, timestamp: u64) -> u64 {
+    store_with_timestamp(content, MemoryKind::Task, source, timestamp)
+}
+
+/// Mark a task as done. Returns false if `id` isn't a task.
+pub fn complete_task(id: u64) -> bool {
+    let done = MEMORY.lock().set_task_status(id, TaskStatus::Done);
+    bound_wal();
+    done
+}
+
+/// Force a full snapshot once the WAL reaches `WAL_MAX_OPS`
+fn bound_wal() {
+    let full = MEMORY.lock().wal_len() >= WAL_MAX_OPS;
+    if full {
+        persist_to_serial();
+    }
+}
+
+/// Flush pending WAL ops via the serial bridge.
+///
+/// Protocol:
+///   [MEMORY_WAL] <op>   (one per op, appended to the bridge's WAL file)
+///
+/// Called periodically by the Supervisor. A full WAL is written as a
+/// snapshot instead. Returns the number of ops flushed.
+pub fn checkpoint() -> usize {
+    use crate::serial_println;
+
+    let ops = {
+        let mut mem = MEMORY.lock();
+        if mem.wal_len() >= WAL_MAX_OPS {
+            None
+        } else {
+            Some(mem.take_wal())
+        }
+    };
+
+    match ops {
+        Some(ops) => {
+            for op in &ops {
+                serial_println!("[MEMORY_WAL] {}", op);
+            }
+            ops.len()
+        }
+        None => {
+            let count = MEMORY.lock().wal_len();
+            persist_to_serial();
+            count
+        }
+    }
+}
+
+/// Get tasks (returns clones), optionally including completed ones
+pub fn tasks(include_done: bool) -> Vec<MemoryEntry> {
+    MEMORY.lock().tasks(include_done).into_iter().cloned().collect()
+}
+
+/// Whether memory changed since the last `save()` (autosave skips clean stores)
+pub fn is_dirty() -> bool {
+    MEMORY.lock().is_dirty()
+}
+
 /// Save memory to filesystem (in-memory) and persist via serial bridge
+///
+/// Appends ops logged since the last save to the memory file (compacting
+/// it when it has grown too long or doesn't exist yet), then flushes the
+/// bridge WAL.
 pub fn save() {
     use crate::storage::filesystem;
     use crate::serial_println;
 
-    let data = MEMORY.lock().serialize();
+    let (ops, appended) = {
+        let mut mem = MEMORY.lock();
+        let ops = mem.take_file_log();
+        (ops, mem.file_ops)
+    };
 
-    // Save to in-memory filesystem (for intra-session use)
-    let _ = filesystem::create_dir("/storage/memory");
-    match filesystem::write_file_string(MEMORY_FILE_PATH, &data) {
-        Ok(()) => {}
-        Err(e) => {
-            serial_println!("[MEMORY_STORE] FS save failed: {:?}", e);
+    if !filesystem::file_exists(MEMORY_FILE_PATH) || appended + ops.len() > COMPACT_AFTER_OPS {
+        let stats = compact();
+        serial_println!("[MEMORY_STORE] Compacted {}: {} -> {} bytes",
+            MEMORY_FILE_PATH, stats.bytes_before, stats.bytes_after);
+        return;
+    }
+
+    // Append to in-memory filesystem (for intra-session use)
+    if !ops.is_empty() {
+        let mut tail = String::new();
+        if appended == 0 {
+            tail.push_str(WAL_MARKER);
+            tail.push('\n');
+        }
+        for op in &ops {
+            tail.push_str(op);
+            tail.push('\n');
+        }
+        match filesystem::append_file(MEMORY_FILE_PATH, tail.as_bytes()) {
+            Ok(()) => MEMORY.lock().file_ops += ops.len(),
+            Err(e) => {
+                serial_println!("[MEMORY_STORE] FS save failed: {:?}", e);
+            }
         }
     }
 
     // Persist via serial bridge (for cross-session persistence)
+    checkpoint();
+}
+
+/// Rewrite the memory file as a clean snapshot, dropping tombstones and
+/// superseded ops, and send a full snapshot to the bridge.
+pub fn compact() -> CompactStats {
+    use crate::storage::filesystem;
+    use crate::serial_println;
+
+    let bytes_before = filesystem::read_file(MEMORY_FILE_PATH).map(|d| d.len()).unwrap_or(0);
+    let (data, ops_folded) = {
+        let mut mem = MEMORY.lock();
+        mem.take_file_log();
+        let folded = core::mem::take(&mut mem.file_ops);
+        (mem.serialize(), folded)
+    };
+
+    let _ = filesystem::create_dir("/storage/memory");
+    if let Err(e) = filesystem::write_file_atomic(MEMORY_FILE_PATH, data.as_bytes()) {
+        serial_println!("[MEMORY_STORE] FS compact failed: {:?}", e);
+    }
+
+    // The bridge's snapshot + WAL compacts the same way
     persist_to_serial();
+
+    CompactStats {
+        bytes_before,
+        bytes_after: data.len(),
+        ops_folded,
+    }
 }
 
 /// Persist all memory entries via serial port so the bridge can save to disk.
@@ -505,12 +1745,58 @@ pub fn save() {
 ///   [MEMORY_PERSIST] <serialized_line>   (one per entry)
 ///   [MEMORY_DONE]                         (signals end of dump)
 ///
-/// The bridge catches these tags and writes them to ~/.genesis/memory.dat
+/// The bridge catches these tags and writes them to ~/.genesis/memory.dat.
+/// A full snapshot supersedes the WAL, so pending ops are discarded.
 pub fn persist_to_serial() {
     use crate::serial_println;
 
-    let data = MEMORY.lock().serialize();
-    let count = MEMORY.lock().entries.len();
+    let (data, count, plain_len) = {
+        let mut mem = MEMORY.lock();
+        mem.wal.clear();
+        (mem.serialize_compact(), mem.entries.len(), mem.serialized_len())
+    };
+    let saved = plain_len.saturating_sub(data.len());
+    serial_println!("[MEMORY_STORE] Compact snapshot: {} bytes (plain {} bytes, {}% smaller)",
+        data.len(), plain_len, saved * 100 / plain_len.max(1));
+    emit_persist(&data, count);
+}
+
+/// `persist_to_serial` for the panic handler: gives up instead of blocking
+/// if the store is locked. Returns false if nothing was persisted.
+///
+/// Uses the plain format, which needs fewer intermediate allocations when
+/// the heap may be exhausted.
+pub fn try_persist_to_serial() -> bool {
+    let (data, count) = match MEMORY.try_lock() {
+        Some(mut mem) => {
+            mem.wal.clear();
+            (mem.serialize(), mem.entries.len())
+        }
+        None => return false,
+    };
+    emit_persist(&data, count);
+    true
+}
+
+/// Print the last `count` entries for a crash report, without blocking.
+/// Returns false if the store was locked.
+pub fn panic_dump(count: usize) -> bool {
+    use crate::serial_println;
+
+    let Some(mem) = MEMORY.try_lock() else {
+        return false;
+    };
+    serial_println!("[PANIC] Memory store: {} entries, {} unflushed WAL ops",
+        mem.entries.len(), mem.wal.len());
+    for entry in mem.entries.values().rev().take(count) {
+        serial_println!("[PANIC]   #{} ({}) {}", entry.id, entry.kind.as_str(), entry.content);
+    }
+    true
+}
+
+/// Write a serialized snapshot as `[MEMORY_PERSIST]` lines
+fn emit_persist(data: &str, count: usize) {
+    use crate::serial_println;
 
     for line in data.lines() {
         if !line.trim().is_empty() {
@@ -533,9 +1819,28 @@ pub fn load_from_serial_data(data: &str) {
         return;
     }
 
-    MEMORY.lock().deserialize(data);
-    let count = MEMORY.lock().entries.len();
-    serial_println!("[MEMORY_STORE] Loaded {} entries from serial bridge", count);
+    let mut mem = MEMORY.lock();
+    if mem.entries.is_empty() {
+        mem.deserialize(data);
+        let count = mem.entries.len();
+        drop(mem);
+        serial_println!("[MEMORY_STORE] Loaded {} entries from serial bridge", count);
+    } else {
+        // Memories were created this session before the load arrived: keep them
+        let added = mem.merge(data);
+        let count = mem.entries.len();
+        drop(mem);
+        serial_println!("[MEMORY_STORE] Merged {} entries from serial bridge ({} total)", added, count);
+    }
+}
+
+/// Merge the persisted memory file into the current store.
+/// Returns entries added, or `None` if the file can't be read.
+pub fn merge_from_file() -> Option<usize> {
+    use crate::storage::filesystem;
+
+    let data = filesystem::read_file_string(MEMORY_FILE_PATH).ok()?;
+    Some(MEMORY.lock().merge(&data))
 }
 
 /// Load memory from filesystem
@@ -545,8 +1850,10 @@ pub fn load() {
 
     match filesystem::read_file_string(MEMORY_FILE_PATH) {
         Ok(data) => {
-            MEMORY.lock().deserialize(&data);
-            let count = MEMORY.lock().entries.len();
+            let mut mem = MEMORY.lock();
+            mem.file_ops = mem.deserialize(&data);
+            let count = mem.entries.len();
+            drop(mem);
             serial_println!("[MEMORY_STORE] Loaded {} entries from {}", count, MEMORY_FILE_PATH);
         }
         Err(_) => {
@@ -554,3 +1861,396 @@ pub fn load() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tied_keywords_sort_alphabetically() {
+        let mut store = MemoryStore::new(16);
+        store.store("zebra mango apple", MemoryKind::Spark, "test");
+        store.store("pear", MemoryKind::Spark, "test");
+        store.store("apple zebra mango", MemoryKind::Spark, "test");
+
+        let top = store.top_keywords(10);
+        let top: Vec<(&str, usize)> = top.iter().map(|(k, n)| (k.as_str(), *n)).collect();
+        assert_eq!(top, alloc::vec![("apple", 2), ("mango", 2), ("zebra", 2), ("pear", 1)]);
+    }
+
+    #[test]
+    fn cooccurrences_count_entries_sharing_both_keywords() {
+        let mut store = MemoryStore::new(16);
+        store.store("kernel scheduler latency", MemoryKind::Spark, "test");
+        store.store("scheduler latency spikes", MemoryKind::Spark, "test");
+        store.store("kernel boot", MemoryKind::Spark, "test");
+
+        let top = store.top_cooccurrences(2);
+        assert_eq!(top[0], ((String::from("latency"), String::from("scheduler")), 2));
+        assert_eq!(top[1].1, 1);
+        assert_eq!(store.pair_entries("latency", "scheduler"), alloc::vec![1, 2]);
+        assert!(store.pair_entries("boot", "spikes").is_empty());
+    }
+
+    #[test]
+    fn relation_lists_shared_keywords_rarest_first() {
+        let mut store = MemoryStore::new(16);
+        let a = store.store("kernel scheduler latency", MemoryKind::Spark, "test");
+        let b = store.store("scheduler latency kernel", MemoryKind::Spark, "test");
+        store.store("kernel boot", MemoryKind::Spark, "test");
+        let c = store.store("garden", MemoryKind::Spark, "test");
+
+        let why = store.explain_relation(a, b).unwrap();
+        let shared: Vec<&str> = why.shared.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(shared, alloc::vec!["latency", "scheduler", "kernel"]);
+        // 4 entries: latency and scheduler appear in 2 (2 each), kernel in 3 (1)
+        assert_eq!(why.score, 5);
+
+        assert_eq!(store.explain_relation(a, c).unwrap().score, 0);
+        assert!(store.explain_relation(a, 99).is_none());
+    }
+
+    /// Minimal decoder for the escapes `json_escape` produces
+    fn json_unescape(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    out.push(char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap());
+                }
+                Some(other) => out.push(other),
+                None => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn json_escape_round_trips_tricky_content() {
+        let tricky = "she said \"hi\"\nC:\\path\t\u{1}end";
+        let escaped = json_escape(tricky);
+        assert_eq!(escaped, "she said \\\"hi\\\"\\nC:\\\\path\\t\\u0001end");
+        assert!(!escaped.chars().any(|c| (c as u32) < 0x20));
+        assert_eq!(json_unescape(&escaped), tricky);
+
+        let mut store = MemoryStore::new(4);
+        let id = store.store(tricky, MemoryKind::Spark, "test");
+        let json = store.peek(id).unwrap().to_json();
+        assert!(json.starts_with("{\"id\":1,\"kind\":\"spark\""));
+        assert!(json.ends_with(&format!("\"content\":\"{escaped}\"}}")));
+    }
+
+    #[test]
+    fn forget_unindexes_entry_and_logs_tombstone() {
+        let mut store = MemoryStore::new(4);
+        let id = store.store("quasar lattice probe", MemoryKind::Observation, "thomas");
+        store.take_wal();
+
+        assert!(store.forget(id));
+        assert!(store.peek(id).is_none());
+        assert!(store.search("quasar").is_empty());
+        assert_eq!(store.take_wal(), [format!("-{id}")]);
+        assert!(!store.forget(id));
+    }
+
+    #[test]
+    fn wal_ops_replay_over_a_snapshot() {
+        let mut live = MemoryStore::new(16);
+        live.store("snapshot observation", MemoryKind::Observation, "shell");
+        let doomed = live.store("soon forgotten spark", MemoryKind::Spark, "shell");
+        let mut data = live.serialize();
+        live.take_wal();
+
+        let task = live.store("write the replay test", MemoryKind::Task, "shell");
+        live.set_task_status(task, TaskStatus::Done);
+        live.forget(doomed);
+        let last = live.store("stored then forgotten", MemoryKind::Observation, "shell");
+        live.forget(last);
+
+        data.push_str(WAL_MARKER);
+        data.push('\n');
+        for op in live.take_wal() {
+            data.push_str(&op);
+            data.push('\n');
+        }
+
+        let mut replayed = MemoryStore::new(16);
+        assert_eq!(replayed.deserialize(&data), 5);
+        assert_eq!(replayed.serialize(), live.serialize());
+        assert_eq!(replayed.next_id, live.next_id);
+        assert_eq!(replayed.peek(task).unwrap().task_status, Some(TaskStatus::Done));
+        assert!(replayed.peek(doomed).is_none());
+        assert!(replayed.peek(last).is_none());
+    }
+
+    #[test]
+    fn headerless_v1_lines_still_load() {
+        let mut store = MemoryStore::new(16);
+        store.deserialize("3|spark|archimedes|120|2|Old idea\\p kept|idea,kept\n9|observation|shell|0|0|second|second\n");
+        let entry = store.peek(3).unwrap();
+        assert_eq!(entry.kind, MemoryKind::Spark);
+        assert_eq!(entry.content, "Old idea| kept");
+        assert_eq!(entry.source, "archimedes");
+        assert_eq!((entry.timestamp, entry.access_count), (120, 2));
+        assert_eq!(entry.keywords, ["idea", "kept"]);
+        assert_eq!(entry.task_status, None);
+        assert_eq!(entry.session, 0);
+        assert_eq!(store.peek(9).unwrap().content, "second");
+        assert_eq!(store.next_id, 10);
+    }
+
+    #[test]
+    fn task_status_survives_both_snapshot_formats() {
+        let mut store = MemoryStore::new(16);
+        let open = store.store("draft the outline", MemoryKind::Task, "shell");
+        let done = store.store("book the venue", MemoryKind::Task, "shell");
+        store.set_task_status(done, TaskStatus::Done);
+
+        for data in [store.serialize(), store.serialize_compact()] {
+            let mut loaded = MemoryStore::new(16);
+            loaded.deserialize(&data);
+            assert_eq!(loaded.peek(open).unwrap().task_status, Some(TaskStatus::Open));
+            assert_eq!(loaded.peek(done).unwrap().task_status, Some(TaskStatus::Done));
+            assert_eq!(loaded.tasks(false).len(), 1);
+        }
+    }
+
+    #[test]
+    fn memory_file_tombstones_stick_and_compaction_keeps_next_id() {
+        // `compact()` writes a snapshot; `save()` then appends the file log
+        let mut live = MemoryStore::new(16);
+        live.store("kept observation", MemoryKind::Observation, "shell");
+        let mut file = live.serialize();
+        live.take_file_log();
+
+        let gone = live.store("appended then forgotten", MemoryKind::Spark, "shell");
+        let kept = live.store("appended and kept", MemoryKind::Spark, "shell");
+        let last = live.store("newest, also forgotten", MemoryKind::Observation, "shell");
+        live.forget(gone);
+        live.forget(last);
+        file.push_str(WAL_MARKER);
+        file.push('\n');
+        for op in live.take_file_log() {
+            file.push_str(&op);
+            file.push('\n');
+        }
+
+        let mut loaded = MemoryStore::new(16);
+        loaded.deserialize(&file);
+        assert!(loaded.peek(gone).is_none());
+        assert!(loaded.peek(last).is_none());
+        assert!(loaded.peek(kept).is_some());
+        assert_eq!(loaded.next_id, live.next_id);
+
+        // Compaction drops the tombstoned entries but not the ids they used
+        for compacted in [loaded.serialize(), loaded.serialize_compact()] {
+            assert!(!compacted.contains("forgotten"));
+            let mut reloaded = MemoryStore::new(16);
+            reloaded.deserialize(&compacted);
+            assert_eq!(reloaded.entries.len(), 2);
+            assert_eq!(reloaded.next_id, live.next_id);
+            assert_eq!(reloaded.store("after compaction", MemoryKind::Spark, "shell"), last + 1);
+        }
+    }
+
+    #[test]
+    fn tagged_entry_is_found_by_its_tag_and_keeps_it_through_snapshots() {
+        let mut store = MemoryStore::new(8);
+        let tagged = store.store("quarterly budget review", MemoryKind::Observation, "shell");
+        store.store("important budget meeting", MemoryKind::Observation, "shell");
+        assert!(store.add_tag(tagged, "#Important"));
+        assert!(!store.add_tag(999, "important"));
+        assert_eq!(store.peek(tagged).unwrap().tags, ["important"]);
+
+        // The tag matches only the tagged entry, not content mentioning the word
+        let hits: Vec<u64> = store.search("#important").iter().map(|(id, _)| *id).collect();
+        assert_eq!(hits, [tagged]);
+
+        for data in [store.serialize(), store.serialize_compact()] {
+            let mut loaded = MemoryStore::new(8);
+            loaded.deserialize(&data);
+            assert_eq!(loaded.peek(tagged).unwrap().tags, ["important"]);
+            assert_eq!(loaded.search("#important")[0].0, tagged);
+        }
+
+        assert!(store.remove_tag(tagged, "important"));
+        assert!(!store.remove_tag(tagged, "important"));
+        assert!(store.search("#important").is_empty());
+        assert!(store.take_wal().iter().any(|op| op == &format!("={tagged}|important")));
+    }
+
+    #[test]
+    fn mutations_mark_store_dirty_until_file_log_taken() {
+        let mut store = MemoryStore::new(4);
+        assert!(!store.is_dirty());
+        let id = store.store("nebula drift note", MemoryKind::Observation, "shell");
+        assert!(store.is_dirty());
+        store.take_file_log();
+        assert!(!store.is_dirty());
+
+        // Flushing the bridge WAL alone doesn't make the file current
+        store.forget(id);
+        store.take_wal();
+        assert!(store.is_dirty());
+        store.take_file_log();
+        assert!(!store.is_dirty());
+    }
+
+    #[test]
+    fn accessed_old_entry_outranks_untouched_newer_one() {
+        let mut store = MemoryStore::new(2);
+        let old = store.store_with_timestamp("often recalled observation", MemoryKind::Observation, "shell", 0);
+        for _ in 0..10 {
+            store.get(old);
+        }
+        let newer = store.store_with_timestamp("never recalled observation", MemoryKind::Observation, "shell", 50_000);
+        let now = 60_000;
+        assert!(store.peek(old).unwrap().importance(now, 0) > store.peek(newer).unwrap().importance(now, 0));
+
+        // Full: the store evicts the untouched entry, not the oldest
+        let newest = store.store_with_timestamp("fresh observation", MemoryKind::Observation, "shell", now);
+        assert!(store.peek(old).is_some());
+        assert!(store.peek(newer).is_none());
+        assert!(store.peek(newest).is_some());
+
+        // Pinned entries survive even when least important
+        store.set_pinned(newest, true);
+        store.store_with_timestamp("another observation", MemoryKind::Observation, "shell", now);
+        assert!(store.peek(old).is_none());
+        assert!(store.peek(newest).unwrap().pinned);
+
+        let mut loaded = MemoryStore::new(2);
+        loaded.deserialize(&store.serialize_compact());
+        assert!(loaded.peek(newest).unwrap().pinned);
+    }
+
+    #[test]
+    fn entries_from_an_earlier_boot_are_older_than_this_one() {
+        let mut store = MemoryStore::new(2);
+        store.set_session(20260101090000);
+        let earlier = store.store_with_timestamp("late tick last boot", MemoryKind::Observation, "shell", 90_000);
+        store.set_session(20260102090000);
+        let current = store.store_with_timestamp("early tick this boot", MemoryKind::Observation, "shell", 100);
+
+        // The earlier boot's larger tick doesn't make its entry newer
+        store.store_with_timestamp("fresh observation", MemoryKind::Observation, "shell", 200);
+        assert!(store.peek(earlier).is_none());
+        assert!(store.peek(current).is_some());
+    }
+
+    #[test]
+    fn resource_content_splits_back_into_description_and_location() {
+        let feedback = FeedbackType::Resource {
+            description: String::from("Notes (draft) on scheduling"),
+            location: String::from("/workspace/notes (v2).md"),
+        };
+        let (kind, content) = MemoryKind::from_feedback(&feedback);
+        assert_eq!(kind, MemoryKind::Resource);
+        assert_eq!(MemoryKind::split_resource(&content),
+            Some(("Notes (draft) on scheduling", "/workspace/notes (v2).md")));
+        assert_eq!(MemoryKind::split_resource("plain observation"), None);
+    }
+
+    #[test]
+    fn merge_remaps_ids_and_skips_duplicates() {
+        let mut persisted = MemoryStore::new(16);
+        persisted.store("shared observation", MemoryKind::Observation, "bridge");
+        persisted.store("only on disk", MemoryKind::Spark, "bridge");
+        persisted.store("shared observation", MemoryKind::Spark, "bridge");
+        let data = persisted.serialize();
+
+        let mut session = MemoryStore::new(16);
+        session.store("fresh this session", MemoryKind::Spark, "shell");
+        session.store("shared observation", MemoryKind::Observation, "shell");
+
+        assert_eq!(session.merge(&data), 2);
+        assert_eq!(session.entries.len(), 4);
+        assert_eq!(session.peek(1).unwrap().content, "fresh this session");
+        assert_eq!(session.peek(3).unwrap().content, "only on disk");
+        assert_eq!(session.peek(4).unwrap().kind, MemoryKind::Spark);
+        assert!(session.search("disk").iter().any(|&(id, _)| id == 3));
+
+        // Merging the same data again adds nothing
+        assert_eq!(session.merge(&data), 0);
+    }
+
+    #[test]
+    fn compact_timestamps_round_trip_exactly() {
+        let timestamps = [500, 0, 0, 12, 7, u64::MAX, 3, i64::MAX as u64 + 9, 1];
+        let mut store = MemoryStore::new(16);
+        for (n, timestamp) in timestamps.iter().enumerate() {
+            store.store_with_timestamp(&format!("entry {n}"), MemoryKind::Observation, "test", *timestamp);
+        }
+
+        let mut loaded = MemoryStore::new(16);
+        loaded.deserialize(&store.serialize_compact());
+        let restored: Vec<u64> = loaded.entries.values().map(|e| e.timestamp).collect();
+        assert_eq!(restored, timestamps);
+    }
+
+    #[test]
+    fn feedback_maps_to_memory_kind_and_content() {
+        let s = String::from;
+        let cases = [
+            (FeedbackType::Spark { content: s("idea"), context: s("bench") },
+                MemoryKind::Spark, "idea (context: bench)"),
+            (FeedbackType::Connection { from: s("monday"), to: s("today"), pattern: s("same bug") },
+                MemoryKind::Connection, "monday -> today: same bug"),
+            (FeedbackType::Resource { description: s("paper"), location: s("/docs/p.pdf") },
+                MemoryKind::Resource, "paper (at: /docs/p.pdf)"),
+            (FeedbackType::Feeling { tag: s("relieved"), intensity: 70 },
+                MemoryKind::Feeling, "relieved (intensity: 70)"),
+        ];
+        for (feedback, kind, content) in cases {
+            assert_eq!(MemoryKind::from_feedback(&feedback), (kind, s(content)));
+        }
+    }
+
+    #[test]
+    fn focus_boosts_matching_results() {
+        let mut store = MemoryStore::new(16);
+        let plain = store.store("kernel build", MemoryKind::Observation, "test");
+        let focused = store.store("kernel health check", MemoryKind::Observation, "test");
+
+        let unbiased = store.search_focused("kernel", None);
+        assert_eq!(unbiased, store.search("kernel"));
+
+        let biased = store.search_focused("kernel", Some("system health"));
+        assert_eq!(biased[0].0, focused);
+        assert!(biased[0].1 > biased[1].1);
+        assert_eq!(biased[1].0, plain);
+    }
+
+    #[test]
+    fn sessions_survive_both_snapshot_formats() {
+        let mut store = MemoryStore::new(16);
+        store.store("before sessions", MemoryKind::Observation, "shell");
+        store.set_session(20261015090000);
+        store.store("yesterday's work", MemoryKind::Spark, "shell");
+        store.set_session(20261016083000);
+        store.store("today's work", MemoryKind::Spark, "shell");
+        store.store("more of today", MemoryKind::Spark, "shell");
+
+        let expected = alloc::vec![(0, 1), (20261015090000, 1), (20261016083000, 2)];
+        for data in [store.serialize(), store.serialize_compact()] {
+            let mut loaded = MemoryStore::new(16);
+            loaded.deserialize(&data);
+            assert_eq!(loaded.sessions(), expected);
+            assert_eq!(loaded.by_session(20261015090000)[0].content, "yesterday's work");
+        }
+
+        // Lines from before sessions existed load as session 0
+        let mut legacy = MemoryStore::new(16);
+        legacy.deserialize("#v2\n7|spark|shell|0|0||old line|line\n");
+        assert_eq!(legacy.sessions(), alloc::vec![(0, 1)]);
+    }
+}
@@ -3,7 +3,28 @@
 //! This module provides access to the VGA text buffer for displaying text
 //! on screen. In the agentic future, this will be one of many output channels
 //! managed by the Action Agent.
+//!
+//! ## Color Markers
+//!
+//! `write_marked` (and `shell_print!`) understand a few inline markers that
+//! switch the foreground color until the next marker or the end of the text:
+//!
+//! ```text
+//! {g} green   {r} red   {y} yellow   {b} blue   {m} magenta   {/} reset
+//! ```
+//!
+//! In `format!`-style strings the braces must be doubled (`{{g}}`), or use
+//! the `MARK_*` constants. `strip_markers` removes them for serial output.
+//!
+//! ## Color Attributes
+//!
+//! For full control over the 16 text-mode colors (foreground and
+//! background), `with_color(fg, bg, || ...)` prints a span in a color and
+//! restores the previous one afterwards; `set_color`/`reset_color` change
+//! it until further notice. Markers inside a colored span reset to that
+//! span's color, not the default.
 
+use alloc::string::String;
 use core::fmt;
 use spin::Mutex;
 use core::ptr;
@@ -15,10 +36,21 @@ const VGA_BUFFER_ADDR: usize = 0xb8000;
 const BUFFER_HEIGHT: usize = 25;
 const BUFFER_WIDTH: usize = 80;
 
+/// Default text color
+const DEFAULT_COLOR: ColorCode = ColorCode::new(Color::Cyan, Color::Black);
+
+/// Color markers (see module docs)
+pub const MARK_GREEN: &str = "{g}";
+pub const MARK_RED: &str = "{r}";
+pub const MARK_YELLOW: &str = "{y}";
+pub const MARK_BLUE: &str = "{b}";
+pub const MARK_MAGENTA: &str = "{m}";
+pub const MARK_RESET: &str = "{/}";
+
 /// Lazy-initialized global writer
 pub static WRITER: Mutex<Writer> = Mutex::new(Writer {
     column_position: 0,
-    color_code: ColorCode::new(Color::Cyan, Color::Black),
+    color_code: DEFAULT_COLOR,
 });
 
 /// VGA color codes
@@ -54,6 +86,11 @@ impl ColorCode {
     pub const fn new(foreground: Color, background: Color) -> ColorCode {
         ColorCode((background as u8) << 4 | (foreground as u8))
     }
+
+    /// Same background, different foreground
+    const fn with_foreground(self, foreground: Color) -> ColorCode {
+        ColorCode((self.0 & 0xf0) | (foreground as u8))
+    }
 }
 
 /// A screen character with its color attribute
@@ -121,11 +158,46 @@ impl Writer {
         for byte in s.bytes() {
             match byte {
                 0x20..=0x7e | b'\n' => self.write_byte(byte),
+                // Backspace moves the cursor left so the shell can erase input
+                0x08 => self.column_position = self.column_position.saturating_sub(1),
                 _ => self.write_byte(0xfe),
             }
         }
     }
 
+    /// Write a string, interpreting color markers. The color is reset to
+    /// what it was before the call, so an unclosed marker doesn't leak.
+    pub fn write_marked(&mut self, s: &str) {
+        let base = self.color_code;
+        let mut rest = s;
+        while let Some(start) = rest.find('{') {
+            self.write_string(&rest[..start]);
+            let tail = &rest[start..];
+            match parse_marker(tail) {
+                Some(color) => {
+                    self.color_code = color.map_or(base, |c| base.with_foreground(c));
+                    rest = &tail[3..];
+                }
+                None => {
+                    self.write_string("{");
+                    rest = &tail[1..];
+                }
+            }
+        }
+        self.write_string(rest);
+        self.color_code = base;
+    }
+
+    /// Set the attribute used for subsequent text
+    pub fn set_color_code(&mut self, color_code: ColorCode) {
+        self.color_code = color_code;
+    }
+
+    /// The attribute used for subsequent text
+    pub fn color_code(&self) -> ColorCode {
+        self.color_code
+    }
+
     /// Move to new line
     fn new_line(&mut self) {
         for row in 1..BUFFER_HEIGHT {
@@ -164,11 +236,66 @@ impl fmt::Write for Writer {
     }
 }
 
+/// Color for a marker at the start of `s`: Some(None) for `{/}`, None if
+/// `s` doesn't start with a marker
+fn parse_marker(s: &str) -> Option<Option<Color>> {
+    let color = match s.get(..3)? {
+        "{g}" => Color::LightGreen,
+        "{r}" => Color::LightRed,
+        "{y}" => Color::Yellow,
+        "{b}" => Color::LightBlue,
+        "{m}" => Color::Pink,
+        "{/}" => return Some(None),
+        _ => return None,
+    };
+    Some(Some(color))
+}
+
+/// Remove color markers, leaving plain text (for serial output)
+pub fn strip_markers(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if parse_marker(tail).is_some() {
+            rest = &tail[3..];
+        } else {
+            out.push('{');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Print a line to the VGA buffer, interpreting color markers
+pub fn println_marked(s: &str) {
+    let mut writer = WRITER.lock();
+    writer.write_marked(s);
+    writer.write_byte(b'\n');
+}
+
 /// Clear the entire screen
 pub fn clear_screen() {
     WRITER.lock().clear_screen();
 }
 
+/// Run `f` (which prints) in the given colors, then restore the previous color
+///
+/// The writer isn't locked while `f` runs, so it can use `print!`/`println!`.
+pub fn with_color<R>(foreground: Color, background: Color, f: impl FnOnce() -> R) -> R {
+    let previous = {
+        let mut writer = WRITER.lock();
+        let previous = writer.color_code();
+        writer.set_color_code(ColorCode::new(foreground, background));
+        previous
+    };
+    let result = f();
+    WRITER.lock().set_color_code(previous);
+    result
+}
+
 /// Print macro
 #[macro_export]
 macro_rules! print {
@@ -188,3 +315,36 @@ pub fn _print(args: fmt::Arguments) {
     use core::fmt::Write;
     WRITER.lock().write_fmt(args).unwrap();
 }
+
+/// Print a line in the given colors for the panic handler, never waiting on
+/// `WRITER`: the code that panicked may hold it forever, in which case a
+/// scratch writer starts a fresh line on the same buffer instead
+pub fn panic_println(foreground: Color, background: Color, args: fmt::Arguments) {
+    use core::fmt::Write;
+    let color_code = ColorCode::new(foreground, background);
+    match WRITER.try_lock() {
+        Some(mut writer) => {
+            let previous = writer.color_code();
+            writer.set_color_code(color_code);
+            let _ = writeln!(writer, "{args}");
+            writer.set_color_code(previous);
+        }
+        None => {
+            let mut scratch = Writer { column_position: 0, color_code };
+            scratch.new_line();
+            let _ = write!(scratch, "{args}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_markers_keeps_plain_braces() {
+        assert_eq!(strip_markers("{g}PASS{/} {x} {r}"), "PASS {x} ");
+        assert_eq!(strip_markers("no markers"), "no markers");
+        assert_eq!(strip_markers("{g"), "{g");
+    }
+}
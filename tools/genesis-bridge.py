@@ -35,8 +35,11 @@ from pathlib import Path
 # the kernel serializes entries via serial [MEMORY_PERSIST] tags, and the bridge
 # writes them to a real file on the host Mac. On boot, the kernel sends
 # [MEMORY_REQUEST] and the bridge sends the data back via [MEMORY_LOAD] tags.
+# Between full snapshots the kernel streams [MEMORY_WAL] op lines, appended to
+# memory.wal and replayed (after a "#wal" marker) on the next load.
 MEMORY_PERSIST_DIR = os.path.expanduser("~/.genesis")
 MEMORY_PERSIST_FILE = os.path.join(MEMORY_PERSIST_DIR, "memory.dat")
+MEMORY_WAL_FILE = os.path.join(MEMORY_PERSIST_DIR, "memory.wal")
 _memory_persist_buffer = []  # accumulates [MEMORY_PERSIST] lines until [MEMORY_DONE]
 
 # ── Agent Journal ("As the Kernel Turns") ──────────────────────────────────
@@ -959,11 +962,24 @@ def _save_memory_to_disk(lines):
         with open(MEMORY_PERSIST_FILE, "w") as f:
             for entry_line in lines:
                 f.write(entry_line + "\n")
+        # The snapshot supersedes any logged ops
+        if os.path.exists(MEMORY_WAL_FILE):
+            os.remove(MEMORY_WAL_FILE)
         print(f"[MEMORY] Saved {len(lines)} entries to {MEMORY_PERSIST_FILE}")
     except Exception as e:
         print(f"[MEMORY] Save failed: {e}", file=sys.stderr)
 
 
+def _append_memory_wal(op):
+    """Append one WAL op line to host disk."""
+    try:
+        os.makedirs(MEMORY_PERSIST_DIR, exist_ok=True)
+        with open(MEMORY_WAL_FILE, "a") as f:
+            f.write(op + "\n")
+    except Exception as e:
+        print(f"[MEMORY] WAL append failed: {e}", file=sys.stderr)
+
+
 def _save_journal_entries(entries):
     """Write journal entries to daily markdown file and optionally notify Telegram."""
     try:
@@ -1081,8 +1097,8 @@ def _send_ambition_to_genesis(process):
 
 
 def _send_memory_to_genesis(process):
-    """Read persisted memory from disk and send to Genesis via serial."""
-    if not os.path.exists(MEMORY_PERSIST_FILE):
+    """Read persisted memory (snapshot, then WAL) from disk and send to Genesis via serial."""
+    if not os.path.exists(MEMORY_PERSIST_FILE) and not os.path.exists(MEMORY_WAL_FILE):
         print("[MEMORY] No persisted memory file found — fresh start")
         try:
             process.stdin.write(b"[MEMORY_LOAD_DONE]\n")
@@ -1092,8 +1108,16 @@ def _send_memory_to_genesis(process):
         return
 
     try:
-        with open(MEMORY_PERSIST_FILE, "r") as f:
-            lines = f.readlines()
+        lines = []
+        if os.path.exists(MEMORY_PERSIST_FILE):
+            with open(MEMORY_PERSIST_FILE, "r") as f:
+                lines = f.readlines()
+        if os.path.exists(MEMORY_WAL_FILE):
+            with open(MEMORY_WAL_FILE, "r") as f:
+                wal = [l for l in f.readlines() if l.strip()]
+            if wal:
+                lines.append("#wal\n")
+                lines.extend(wal)
 
         count = 0
         for line in lines:
@@ -1145,6 +1169,10 @@ def listen_to_genesis(process, input_queue):
                             entry_data = re.sub(r'.*\[MEMORY_PERSIST\]\s*', '', line)
                             if entry_data:
                                 _memory_persist_buffer.append(entry_data)
+                        elif "[MEMORY_WAL]" in line:
+                            op = re.sub(r'.*\[MEMORY_WAL\]\s*', '', line)
+                            if op:
+                                _append_memory_wal(op)
                         elif "[MEMORY_DONE]" in line:
                             if _memory_persist_buffer:
                                 _save_memory_to_disk(_memory_persist_buffer)
@@ -45,12 +45,14 @@ fi
 # -vga std: Standard VGA emulation (required for Mode 12h)
 # -m 128M: 128MB of RAM
 # -no-reboot: Exit on triple fault instead of rebooting
+# -device isa-debug-exit: Lets the `shutdown` command exit QEMU via port 0xf4
 $QEMU_BINARY \
     -drive format=raw,file="$KERNEL_PATH" \
     -serial stdio \
     -vga std \
     -m 128M \
     -no-reboot \
+    -device isa-debug-exit,iobase=0xf4,iosize=0x04 \
     -no-shutdown
 
 